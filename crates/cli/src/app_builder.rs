@@ -11,10 +11,17 @@ use clap::Command;
 use clap::ValueHint;
 
 use crate::args::HostMode;
+use crate::command::DebugArg;
 use crate::command::DryRunArg;
+use crate::command::EstimateArg;
+use crate::command::InspectArg;
+use crate::command::MigrateArg;
 use crate::command::ProveArg;
+use crate::command::ProveSliceArg;
+use crate::command::ReplayArg;
 use crate::command::RunningArg;
 use crate::command::SetupArg;
+use crate::command::StatisticArg;
 use crate::command::Subcommands;
 use crate::command::VerifyArg;
 use crate::ZkWasmCli;
@@ -52,10 +59,23 @@ impl ArgBuilder<PathBuf> for OutputDirArg {
     }
 }
 
+struct TraceDirArg;
+impl ArgBuilder<PathBuf> for TraceDirArg {
+    fn builder() -> Arg<'static> {
+        arg!(--traces <TRACES> "Path to the directory of traces written by prove")
+            .value_parser(value_parser!(PathBuf))
+            .value_hint(ValueHint::DirPath)
+    }
+
+    fn parse(matches: &ArgMatches) -> PathBuf {
+        matches.get_one::<PathBuf>("traces").cloned().unwrap()
+    }
+}
+
 struct PublicInputsArg;
 impl ArgBuilder<Vec<String>> for PublicInputsArg {
     fn builder() -> Arg<'static> {
-        arg!(--public <PUBLIC_INPUT> ... "Public inputs with format value:type where type=i64|bytes|bytes-packed, values can be separated by `,` or multiple occurrence of `--public`")
+        arg!(--public <PUBLIC_INPUT> ... "Public inputs with format value:type where type=i64|u64|i32|bytes|bytes-packed|hex-words|base64|json|file-le|file-be|file, values can be separated by `,` or multiple occurrence of `--public`")
      .takes_value(true).value_delimiter(',').required(false)
     }
 
@@ -71,7 +91,7 @@ impl ArgBuilder<Vec<String>> for PublicInputsArg {
 struct PrivateInputsArg;
 impl ArgBuilder<Vec<String>> for PrivateInputsArg {
     fn builder() -> Arg<'static> {
-        arg!(--private <PRIVATE_INPUT> ... "Private inputs with format value:type where type=i64|bytes|bytes-packed, values can be separated by `,` or multiple occurrence of `--private`")
+        arg!(--private <PRIVATE_INPUT> ... "Private inputs with format value:type where type=i64|u64|i32|bytes|bytes-packed|hex-words|base64|json|file-le|file-be|file, values can be separated by `,` or multiple occurrence of `--private`")
        .takes_value(true).value_delimiter(',').required(false)
     }
 
@@ -87,7 +107,7 @@ impl ArgBuilder<Vec<String>> for PrivateInputsArg {
 struct ContextInputsArg;
 impl ArgBuilder<Vec<String>> for ContextInputsArg {
     fn builder() -> Arg<'static> {
-        arg!(--ctxin <CONTEXT_INPUT> ... "Context inputs with format value:type where type=i64|bytes|bytes-packed, values can be separated by `,` or multiple occurrence of `--ctxin`")
+        arg!(--ctxin <CONTEXT_INPUT> ... "Context inputs with format value:type where type=i64|u64|i32|bytes|bytes-packed|hex-words|base64|json|file-le|file-be|file, values can be separated by `,` or multiple occurrence of `--ctxin`")
        .takes_value(true).value_delimiter(',').required(false)
     }
 
@@ -100,6 +120,60 @@ impl ArgBuilder<Vec<String>> for ContextInputsArg {
     }
 }
 
+struct InputManifestArg;
+impl ArgBuilder<Option<PathBuf>> for InputManifestArg {
+    fn builder() -> Arg<'static> {
+        arg!(--inputs [INPUT_MANIFEST] "Path to the JSON manifest listing the inputs of each kind with format value:type, e.g. {\"public\": [\"0x2a:i64\"], \"private\": [\"[1,2,3]:json\"], \"context\": []}. The inputs of `--public`, `--private` and `--ctxin` are appended.")
+            .value_parser(value_parser!(PathBuf))
+            .value_hint(ValueHint::FilePath)
+    }
+
+    fn parse(matches: &ArgMatches) -> Option<PathBuf> {
+        matches.get_one::<PathBuf>("inputs").cloned()
+    }
+}
+
+struct PrivateStreamArg;
+impl ArgBuilder<Option<String>> for PrivateStreamArg {
+    fn builder() -> Arg<'static> {
+        arg!(--"private-stream" [PRIVATE_STREAM] "Private inputs read lazily by `wasm_input(0)` with format file:type where type=file-le|file-be, the file `-` is the stdin. It's exclusive with the other private inputs.")
+            .value_hint(ValueHint::FilePath)
+    }
+
+    fn parse(matches: &ArgMatches) -> Option<String> {
+        matches.get_one::<String>("private-stream").cloned()
+    }
+}
+
+struct OutputChannelsArg;
+impl ArgBuilder<Option<PathBuf>> for OutputChannelsArg {
+    fn builder() -> Arg<'static> {
+        arg!(--"output-channels" [OUTPUT_CHANNELS] "Path to the JSON list of the named output channels selected by `wasm_output_channel`, e.g. [{\"id\": 1, \"name\": \"balances\", \"committed\": false, \"format\": \"json\"}]. The channels are written to the directory `outputs` of the output directory.")
+            .value_parser(value_parser!(PathBuf))
+            .value_hint(ValueHint::FilePath)
+    }
+
+    fn parse(matches: &ArgMatches) -> Option<PathBuf> {
+        matches.get_one::<PathBuf>("output-channels").cloned()
+    }
+}
+
+struct WitnessArg;
+impl ArgBuilder<Vec<String>> for WitnessArg {
+    fn builder() -> Arg<'static> {
+        arg!(--witness <WITNESS> ... "Indexed witness with format index:file, the file lists inputs with format value:type popped in order by `wasm_witness_indexed_pop`, multiple occurrence of `--witness` are allowed")
+       .takes_value(true).required(false)
+    }
+
+    fn parse(matches: &ArgMatches) -> Vec<String> {
+        matches
+            .get_many::<String>("witness")
+            .unwrap_or_default()
+            .map(|s| s.to_string())
+            .collect()
+    }
+}
+
 struct ContextOutputArg;
 impl ArgBuilder<Option<String>> for ContextOutputArg {
     fn builder() -> Arg<'static> {
@@ -134,6 +208,18 @@ impl ArgBuilder<bool> for MockTestArg {
     }
 }
 
+struct ProfileArg;
+impl ArgBuilder<bool> for ProfileArg {
+    fn builder() -> Arg<'static> {
+        arg!(--profile "Profile the functions of the guest, the call stacks are written to the output directory in the folded format of flamegraph")
+            .action(ArgAction::SetTrue)
+    }
+
+    fn parse(matches: &ArgMatches) -> bool {
+        matches.get_flag("profile")
+    }
+}
+
 struct SkipArg;
 impl ArgBuilder<usize> for SkipArg {
     fn builder() -> Arg<'static> {
@@ -161,6 +247,42 @@ impl ArgBuilder<Option<usize>> for PaddingArg {
     }
 }
 
+struct ResumeArg;
+impl ArgBuilder<bool> for ResumeArg {
+    fn builder() -> Arg<'static> {
+        arg!(--resume "Resume proving from the first slice without a proof, using the traces of the previous execution")
+            .action(ArgAction::SetTrue)
+    }
+
+    fn parse(matches: &ArgMatches) -> bool {
+        matches.get_flag("resume")
+    }
+}
+
+struct WorkersArg;
+impl ArgBuilder<Option<usize>> for WorkersArg {
+    fn builder() -> Arg<'static> {
+        arg!(--workers [WORKERS] "Prove slices concurrently in WORKERS worker processes")
+            .value_parser(value_parser!(usize))
+            .multiple_values(false)
+    }
+
+    fn parse(matches: &ArgMatches) -> Option<usize> {
+        matches.get_one("workers").copied()
+    }
+}
+
+struct SliceIndexArg;
+impl ArgBuilder<usize> for SliceIndexArg {
+    fn builder() -> Arg<'static> {
+        arg!(--index <INDEX> "Index of the slice to prove").value_parser(value_parser!(usize))
+    }
+
+    fn parse(matches: &ArgMatches) -> usize {
+        *matches.get_one("index").unwrap()
+    }
+}
+
 struct InstructionLimitArg;
 impl ArgBuilder<Option<usize>> for InstructionLimitArg {
     fn builder() -> Arg<'static> {
@@ -174,6 +296,19 @@ impl ArgBuilder<Option<usize>> for InstructionLimitArg {
     }
 }
 
+struct TreeDbArg;
+impl ArgBuilder<Option<PathBuf>> for TreeDbArg {
+    fn builder() -> Arg<'static> {
+        arg!(--"tree-db" [TREE_DB] "Path to the directory of the local db for the merkle and data cache host functions, created if not exists. MongoDB is used if not specified.")
+            .value_parser(value_parser!(PathBuf))
+            .value_hint(ValueHint::DirPath)
+    }
+
+    fn parse(matches: &ArgMatches) -> Option<PathBuf> {
+        matches.get_one::<PathBuf>("tree-db").cloned()
+    }
+}
+
 fn setup_command() -> Command<'static> {
     let command = Command::new("setup")
         .about("Setup a new zkWasm circuit for provided Wasm image")
@@ -191,6 +326,13 @@ fn setup_command() -> Command<'static> {
             .value_parser(value_parser!(HostMode))
             .required(false),
         )
+        .arg(
+            arg!(
+                --"host-config" [HOST_CONFIG] "Path to the host config listing the ops of the standard host environment among POSEIDONHASH, MERKLE and JUBJUBSUM, e.g. {\"ops\": [\"POSEIDONHASH\", \"MERKLE\"], \"sha256\": true}. Defaults to the standard ops."
+            )
+            .value_parser(value_parser!(PathBuf))
+            .value_hint(ValueHint::FilePath),
+        )
         .arg(
             arg!(
                 --phantom <PHANTOM_FUNCTIONS> "Specify phantom functions whose body will be ignored in the circuit"
@@ -215,9 +357,32 @@ fn dry_run_command() -> Command<'static> {
         .arg(PublicInputsArg::builder())
         .arg(PrivateInputsArg::builder())
         .arg(ContextInputsArg::builder())
+        .arg(InputManifestArg::builder())
+        .arg(PrivateStreamArg::builder())
+        .arg(OutputChannelsArg::builder())
+        .arg(WitnessArg::builder())
         .arg(ContextOutputArg::builder())
         .arg(OutputDirArg::builder())
+        .arg(TreeDbArg::builder())
         .arg(InstructionLimitArg::builder())
+        .arg(ProfileArg::builder())
+}
+
+fn debug_command() -> Command<'static> {
+    Command::new("debug")
+        .about("Step through the execution trace of the Wasm image")
+        .arg(WasmImageArg::builder())
+        .arg(PublicInputsArg::builder())
+        .arg(PrivateInputsArg::builder())
+        .arg(ContextInputsArg::builder())
+        .arg(InputManifestArg::builder())
+        .arg(PrivateStreamArg::builder())
+        .arg(OutputChannelsArg::builder())
+        .arg(WitnessArg::builder())
+        .arg(TreeDbArg::builder())
+        .arg(TraceDirArg::builder().required(false).help(
+            "Path to the directory of traces written by prove, the image is executed if not specified",
+        ))
 }
 
 fn prove_command() -> Command<'static> {
@@ -227,24 +392,104 @@ fn prove_command() -> Command<'static> {
         .arg(PublicInputsArg::builder())
         .arg(PrivateInputsArg::builder())
         .arg(ContextInputsArg::builder())
+        .arg(InputManifestArg::builder())
+        .arg(PrivateStreamArg::builder())
+        .arg(OutputChannelsArg::builder())
+        .arg(WitnessArg::builder())
         .arg(ContextOutputArg::builder())
         .arg(OutputDirArg::builder())
+        .arg(TreeDbArg::builder())
         .arg(MockTestArg::builder())
-        .arg(FileBackendArg::builder());
+        .arg(FileBackendArg::builder())
+        .arg(ResumeArg::builder());
 
     if cfg!(feature = "continuation") {
-        command.arg(SkipArg::builder()).arg(PaddingArg::builder())
+        command
+            .arg(SkipArg::builder())
+            .arg(PaddingArg::builder())
+            .arg(WorkersArg::builder())
     } else {
         command
     }
 }
 
+fn prove_slice_command() -> Command<'static> {
+    Command::new("prove-slice")
+        .about("Prove a single slice from the traces and slice states written by `prove --workers`")
+        .arg(WasmImageArg::builder())
+        .arg(OutputDirArg::builder())
+        .arg(SliceIndexArg::builder())
+        .arg(MockTestArg::builder())
+}
+
+fn replay_command() -> Command<'static> {
+    Command::new("replay")
+        .about("Generate proofs from the traces written by prove without executing the Wasm image")
+        .arg(TraceDirArg::builder())
+        .arg(OutputDirArg::builder())
+        .arg(MockTestArg::builder())
+}
+
+fn statistic_command() -> Command<'static> {
+    Command::new("statistic")
+        .about(
+            "Summarize the executed instructions by opcode class from the traces written by prove",
+        )
+        .arg(TraceDirArg::builder())
+}
+
 fn verify_command() -> Command<'static> {
     Command::new("verify")
         .about("Verify the proof")
         .arg(OutputDirArg::builder())
 }
 
+fn inspect_command() -> Command<'static> {
+    Command::new("inspect")
+        .about("Inspect the Wasm image and the configuration without setup")
+        .arg(
+            arg!(-k [K] "Size of the circuit. Defaults to the one in the configuration.")
+                .value_parser(value_parser!(u32).range(18..23)),
+        )
+        .arg(
+            arg!(
+                --host [HOST_MODE] "Specify execution host environment for the runtime. Defaults to the one in the configuration."
+            )
+            .value_parser(value_parser!(HostMode)),
+        )
+        .arg(WasmImageArg::builder().required(false))
+}
+
+fn estimate_command() -> Command<'static> {
+    Command::new("estimate")
+        .about("Execute the Wasm image and estimate the rows of the tables and the minimal K without setup")
+        .arg(
+            arg!(
+                --host [HOST_MODE] "Specify execution host environment for the runtime. Defaults to the one in the configuration."
+            )
+            .value_parser(value_parser!(HostMode)),
+        )
+        .arg(WasmImageArg::builder())
+        .arg(PublicInputsArg::builder())
+        .arg(PrivateInputsArg::builder())
+        .arg(ContextInputsArg::builder())
+        .arg(InputManifestArg::builder())
+        .arg(PrivateStreamArg::builder())
+        .arg(OutputChannelsArg::builder())
+        .arg(WitnessArg::builder())
+        .arg(TreeDbArg::builder())
+}
+
+fn migrate_command() -> Command<'static> {
+    Command::new("migrate")
+        .about("Upgrade a legacy configuration to the current version")
+        .arg(
+            WasmImageArg::builder()
+                .required(false)
+                .help("Path to the Wasm image, required if the configuration records its digest"),
+        )
+}
+
 pub(crate) fn app() -> App<'static> {
     command!()
         .author("delphinus-lab")
@@ -255,10 +500,22 @@ pub(crate) fn app() -> App<'static> {
             )
             .value_parser(value_parser!(PathBuf)),
         )
+        .arg(
+            arg!(--json "Emit a single JSON document to stdout instead of the human readable output, the debug output of the guest (`wasm_dbg`) goes to stderr")
+                .action(ArgAction::SetTrue)
+                .global(true),
+        )
         .subcommand(setup_command())
         .subcommand(dry_run_command())
+        .subcommand(debug_command())
         .subcommand(prove_command())
+        .subcommand(prove_slice_command())
+        .subcommand(replay_command())
+        .subcommand(statistic_command())
         .subcommand(verify_command())
+        .subcommand(inspect_command())
+        .subcommand(estimate_command())
+        .subcommand(migrate_command())
         .subcommand_required(true)
 }
 
@@ -267,6 +524,7 @@ impl From<&ArgMatches> for SetupArg {
         SetupArg {
             k: *val.get_one::<u32>("K").unwrap(),
             host_mode: *val.get_one::<HostMode>("host").unwrap(),
+            host_config: val.get_one::<PathBuf>("host-config").cloned(),
             phantom_functions: val
                 .get_many::<String>("phantom")
                 .unwrap_or_default()
@@ -284,7 +542,12 @@ impl From<&ArgMatches> for RunningArg {
             public_inputs: PublicInputsArg::parse(val),
             private_inputs: PrivateInputsArg::parse(val),
             context_inputs: ContextInputsArg::parse(val),
+            input_manifest: InputManifestArg::parse(val),
+            private_stream: PrivateStreamArg::parse(val),
+            output_channels: OutputChannelsArg::parse(val),
             context_output: ContextOutputArg::parse(val),
+            witness: WitnessArg::parse(val),
+            tree_db: TreeDbArg::parse(val),
         }
     }
 }
@@ -295,6 +558,24 @@ impl From<&ArgMatches> for DryRunArg {
             wasm_image: WasmImageArg::parse(val).unwrap(),
             running_arg: val.into(),
             instruction_limit: InstructionLimitArg::parse(val),
+            profile: ProfileArg::parse(val),
+        }
+    }
+}
+
+impl From<&ArgMatches> for DebugArg {
+    fn from(val: &ArgMatches) -> Self {
+        DebugArg {
+            wasm_image: WasmImageArg::parse(val).unwrap(),
+            public_inputs: PublicInputsArg::parse(val),
+            private_inputs: PrivateInputsArg::parse(val),
+            context_inputs: ContextInputsArg::parse(val),
+            input_manifest: InputManifestArg::parse(val),
+            private_stream: PrivateStreamArg::parse(val),
+            output_channels: OutputChannelsArg::parse(val),
+            witness: WitnessArg::parse(val),
+            tree_db: TreeDbArg::parse(val),
+            trace_dir: val.get_one::<PathBuf>("traces").cloned(),
         }
     }
 }
@@ -307,8 +588,39 @@ impl From<&ArgMatches> for ProveArg {
             running_arg: val.into(),
             mock_test: MockTestArg::parse(val),
             file_backend: FileBackendArg::parse(val),
+            resume: ResumeArg::parse(val),
             skip: SkipArg::parse(val),
             padding: PaddingArg::parse(val),
+            workers: WorkersArg::parse(val),
+        }
+    }
+}
+
+impl From<&ArgMatches> for ProveSliceArg {
+    fn from(val: &ArgMatches) -> Self {
+        ProveSliceArg {
+            wasm_image: WasmImageArg::parse(val).unwrap(),
+            output_dir: OutputDirArg::parse(val),
+            index: SliceIndexArg::parse(val),
+            mock_test: MockTestArg::parse(val),
+        }
+    }
+}
+
+impl From<&ArgMatches> for ReplayArg {
+    fn from(val: &ArgMatches) -> Self {
+        ReplayArg {
+            trace_dir: TraceDirArg::parse(val),
+            output_dir: OutputDirArg::parse(val),
+            mock_test: MockTestArg::parse(val),
+        }
+    }
+}
+
+impl From<&ArgMatches> for StatisticArg {
+    fn from(val: &ArgMatches) -> Self {
+        StatisticArg {
+            trace_dir: TraceDirArg::parse(val),
         }
     }
 }
@@ -321,19 +633,62 @@ impl From<&ArgMatches> for VerifyArg {
     }
 }
 
+impl From<&ArgMatches> for InspectArg {
+    fn from(val: &ArgMatches) -> Self {
+        InspectArg {
+            k: val.get_one::<u32>("K").copied(),
+            host_mode: val.get_one::<HostMode>("host").copied(),
+            wasm_image: WasmImageArg::parse(val),
+        }
+    }
+}
+
+impl From<&ArgMatches> for EstimateArg {
+    fn from(val: &ArgMatches) -> Self {
+        EstimateArg {
+            host_mode: val.get_one::<HostMode>("host").copied(),
+            wasm_image: WasmImageArg::parse(val).unwrap(),
+            public_inputs: PublicInputsArg::parse(val),
+            private_inputs: PrivateInputsArg::parse(val),
+            context_inputs: ContextInputsArg::parse(val),
+            input_manifest: InputManifestArg::parse(val),
+            private_stream: PrivateStreamArg::parse(val),
+            output_channels: OutputChannelsArg::parse(val),
+            witness: WitnessArg::parse(val),
+            tree_db: TreeDbArg::parse(val),
+        }
+    }
+}
+
+impl From<&ArgMatches> for MigrateArg {
+    fn from(val: &ArgMatches) -> Self {
+        MigrateArg {
+            wasm_image: WasmImageArg::parse(val),
+        }
+    }
+}
+
 impl From<ArgMatches> for ZkWasmCli {
     fn from(arg: ArgMatches) -> ZkWasmCli {
         let subcommand = match arg.subcommand() {
             Some(("setup", sub_matches)) => Subcommands::Setup(sub_matches.into()),
             Some(("dry-run", sub_matches)) => Subcommands::DryRun(sub_matches.into()),
+            Some(("debug", sub_matches)) => Subcommands::Debug(sub_matches.into()),
             Some(("prove", sub_matches)) => Subcommands::Prove(sub_matches.into()),
+            Some(("prove-slice", sub_matches)) => Subcommands::ProveSlice(sub_matches.into()),
+            Some(("replay", sub_matches)) => Subcommands::Replay(sub_matches.into()),
+            Some(("statistic", sub_matches)) => Subcommands::Statistic(sub_matches.into()),
             Some(("verify", sub_matches)) => Subcommands::Verify(sub_matches.into()),
+            Some(("inspect", sub_matches)) => Subcommands::Inspect(sub_matches.into()),
+            Some(("estimate", sub_matches)) => Subcommands::Estimate(sub_matches.into()),
+            Some(("migrate", sub_matches)) => Subcommands::Migrate(sub_matches.into()),
             _ => unreachable!("unknown subcommand"),
         };
 
         ZkWasmCli {
             name: arg.get_one::<String>("NAME").unwrap().to_owned(),
             params_dir: arg.get_one::<PathBuf>("params").unwrap().to_owned(),
+            json: arg.get_flag("json"),
             subcommand,
         }
     }
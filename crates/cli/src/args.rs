@@ -1,5 +1,21 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use delphinus_host::HostEnvConfig;
+use delphinus_host::StandardHostEnvBuilder;
+use delphinus_zkwasm::foreign::output_helper::OutputChannelConfig;
+use delphinus_zkwasm::foreign::wasm_input_helper::source::Endianness;
+use delphinus_zkwasm::foreign::wasm_input_helper::source::PrivateInputSource;
+use delphinus_zkwasm::foreign::wasm_input_helper::source::ReaderSource;
+use delphinus_zkwasm::runtime::host::default_env::DefaultHostEnvBuilder;
+use delphinus_zkwasm::runtime::host::HostEnvBuilder;
 use serde::Deserialize;
 use serde::Serialize;
+use specs::args::parse_args;
 
 #[derive(clap::ArgEnum, Copy, Clone, Debug, Default, Serialize, Deserialize)]
 pub enum HostMode {
@@ -10,3 +26,245 @@ pub enum HostMode {
     /// Wasm Host Environment with more Zk plugins
     Standard,
 }
+
+impl HostMode {
+    /// `host_config` replaces the ops of the standard host env, it's only valid with
+    /// `HostMode::Standard`.
+    pub(crate) fn env_builder(
+        self,
+        k: u32,
+        host_config: Option<&HostEnvConfig>,
+    ) -> Box<dyn HostEnvBuilder> {
+        match self {
+            HostMode::Default => Box::new(DefaultHostEnvBuilder::new(k)),
+            HostMode::Standard => match host_config {
+                Some(host_config) => {
+                    Box::new(StandardHostEnvBuilder::with_config(k, host_config.clone()))
+                }
+                None => Box::new(StandardHostEnvBuilder::new(k)),
+            },
+        }
+    }
+}
+
+/// Read the host config file, which is the JSON of `HostEnvConfig`, e.g.
+/// `{ "ops": ["POSEIDONHASH", "MERKLE", "JUBJUBSUM"], "sha256": true }`.
+pub(crate) fn read_host_config(path: &Path) -> anyhow::Result<HostEnvConfig> {
+    let config: HostEnvConfig = serde_json::from_reader(File::open(path)?)
+        .map_err(|err| anyhow::anyhow!("Failed to parse the host config {:?}: {}", path, err))?;
+
+    // The calls of an ungrouped op could be split into different slices.
+    let ungrouped_ops = config.ungrouped_ops();
+    if !ungrouped_ops.is_empty() {
+        anyhow::bail!(
+            "The host config {:?} selects {:?}, which are not supported by a host config yet.",
+            path,
+            ungrouped_ops
+        );
+    }
+
+    Ok(config)
+}
+
+/// Read the indexed witness of `--witness <index>:<file>`. The file lists the witness in the
+/// input format `value:type`, separated by whitespaces or lines, lines starting with `#` are
+/// ignored. The files of the same index are concatenated.
+///
+/// The buffers are reversed so that `wasm_witness_indexed_pop` returns the witness in the order
+/// of the files.
+pub(crate) fn read_indexed_witness(values: &[String]) -> anyhow::Result<HashMap<u64, Vec<u64>>> {
+    let mut indexed_witness: HashMap<u64, Vec<u64>> = HashMap::new();
+
+    for value in values {
+        let Some((index, path)) = value.split_once(':') else {
+            anyhow::bail!("Invalid witness {}, expect <index>:<file>", value);
+        };
+
+        let index = match index.strip_prefix("0x") {
+            Some(index) => u64::from_str_radix(index, 16),
+            None => index.parse::<u64>(),
+        }
+        .map_err(|err| anyhow::anyhow!("Invalid witness index {}: {}", index, err))?;
+
+        let content = fs::read_to_string(path)
+            .map_err(|err| anyhow::anyhow!("Failed to read witness file {}: {}", path, err))?;
+        let entries = content
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .flat_map(|line| line.split_whitespace())
+            .collect::<Vec<_>>();
+
+        indexed_witness
+            .entry(index)
+            .or_default()
+            .extend(parse_args(&entries)?);
+    }
+
+    for witness in indexed_witness.values_mut() {
+        witness.reverse();
+    }
+
+    Ok(indexed_witness)
+}
+
+/// The input manifest of `--inputs`, listing the inputs of each kind in the input format
+/// `value:type`, e.g. `{ "public": ["0x2a:i64"], "private": ["[1,2,3]:json"], "context": [] }`.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct InputManifest {
+    public: Vec<String>,
+    private: Vec<String>,
+    context: Vec<String>,
+}
+
+/// Read the public, private and context inputs. The inputs of the manifest come first, followed
+/// by the ones of the command line.
+pub(crate) fn read_inputs(
+    manifest: Option<&Path>,
+    public_inputs: &[String],
+    private_inputs: &[String],
+    context_inputs: &[String],
+) -> anyhow::Result<(Vec<u64>, Vec<u64>, Vec<u64>)> {
+    let mut manifest = match manifest {
+        Some(path) => {
+            let file = File::open(path).map_err(|err| {
+                anyhow::anyhow!("Failed to read the input manifest {:?}: {}", path, err)
+            })?;
+
+            serde_json::from_reader::<_, InputManifest>(file).map_err(|err| {
+                anyhow::anyhow!("Failed to parse the input manifest {:?}: {}", path, err)
+            })?
+        }
+        None => InputManifest::default(),
+    };
+
+    manifest.public.extend_from_slice(public_inputs);
+    manifest.private.extend_from_slice(private_inputs);
+    manifest.context.extend_from_slice(context_inputs);
+
+    Ok((
+        parse_args(&manifest.public)?,
+        parse_args(&manifest.private)?,
+        parse_args(&manifest.context)?,
+    ))
+}
+
+/// Open the private inputs of `wasm_input(0)`. The inputs are read lazily from `stream` with format
+/// `<file>:file-le|file-be` if specified, the file `-` is the stdin.
+pub(crate) fn open_private_inputs(
+    private_inputs: Vec<u64>,
+    stream: Option<&str>,
+) -> anyhow::Result<Box<dyn PrivateInputSource>> {
+    let Some(stream) = stream else {
+        return Ok(private_inputs.into());
+    };
+
+    if !private_inputs.is_empty() {
+        anyhow::bail!(
+            "The private inputs are given by both --private-stream and --private or --inputs"
+        );
+    }
+
+    let (path, endianness) = match stream.rsplit_once(':') {
+        Some((path, "file-le")) => (path, Endianness::Little),
+        Some((path, "file-be")) => (path, Endianness::Big),
+        _ => anyhow::bail!(
+            "Invalid private stream {}, expect <file>:file-le or <file>:file-be",
+            stream
+        ),
+    };
+
+    if path == "-" {
+        return Ok(Box::new(ReaderSource::new(io::stdin(), endianness)));
+    }
+
+    let source = ReaderSource::open(path, endianness)
+        .map_err(|err| anyhow::anyhow!("Failed to open the private stream {}: {}", path, err))?;
+
+    Ok(Box::new(source))
+}
+
+/// Read the output channels of `--output-channels`, the file is the JSON list of
+/// `OutputChannelConfig`. The names are used as the file names of the outputs.
+pub(crate) fn read_output_channels(
+    path: Option<&Path>,
+) -> anyhow::Result<Vec<OutputChannelConfig>> {
+    let Some(path) = path else {
+        return Ok(vec![]);
+    };
+
+    let file = File::open(path)
+        .map_err(|err| anyhow::anyhow!("Failed to read the output channels {:?}: {}", path, err))?;
+    let channels: Vec<OutputChannelConfig> = serde_json::from_reader(file).map_err(|err| {
+        anyhow::anyhow!("Failed to parse the output channels {:?}: {}", path, err)
+    })?;
+
+    let mut ids = HashSet::new();
+    let mut names = HashSet::new();
+    for channel in &channels {
+        if channel.name.is_empty()
+            || !channel
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            anyhow::bail!(
+                "Invalid output channel name {:?}, expect letters, digits, `-` and `_`",
+                channel.name
+            );
+        }
+
+        if !ids.insert(channel.id) {
+            anyhow::bail!("Duplicated output channel id {}", channel.id);
+        }
+
+        if !names.insert(&channel.name) {
+            anyhow::bail!("Duplicated output channel name {}", channel.name);
+        }
+    }
+
+    Ok(channels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_indexed_witness() {
+        let dir = std::env::temp_dir().join("zkwasm-cli-test-witness");
+        fs::create_dir_all(&dir).unwrap();
+
+        let first = dir.join("first.txt");
+        let second = dir.join("second.txt");
+        let other = dir.join("other.txt");
+        fs::write(
+            &first,
+            "# the first witness\n1:i64 2:i64\n  # indented comment\n3:i64\n",
+        )
+        .unwrap();
+        fs::write(&second, "[4,5]:json\n").unwrap();
+        fs::write(&other, "0x2a:u64").unwrap();
+
+        let indexed_witness = read_indexed_witness(&[
+            format!("3:{}", first.display()),
+            format!("0x10:{}", other.display()),
+            format!("0x3:{}", second.display()),
+        ])
+        .unwrap();
+
+        assert_eq!(indexed_witness.len(), 2);
+        assert_eq!(indexed_witness[&16], vec![42]);
+
+        // The files of the same index are concatenated and popped in order.
+        let mut witness = indexed_witness[&3].clone();
+        let popped = std::iter::from_fn(|| witness.pop()).collect::<Vec<_>>();
+        assert_eq!(popped, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_read_indexed_witness_invalid() {
+        assert!(read_indexed_witness(&["witness.txt".to_string()]).is_err());
+        assert!(read_indexed_witness(&["0xg:witness.txt".to_string()]).is_err());
+    }
+}
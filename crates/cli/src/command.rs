@@ -1,18 +1,31 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fs::File;
 use std::fs::OpenOptions;
 use std::fs::{self};
 use std::io::Read;
+use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
+use std::rc::Rc;
 
 use crate::config::CircuitDataConfig;
-use crate::config::CircuitDataMd5;
+use crate::config::CircuitDataDigest;
 use crate::TRIVIAL_WASM;
 use clap::Args;
 use console::style;
+use delphinus_host::host::merkle_helper::local_db::open_local_tree_db;
+use delphinus_host::HostEnvConfig;
+use delphinus_host::StandardHostEnvBuilder;
 use delphinus_zkwasm::checksum::ImageCheckSum;
+use delphinus_zkwasm::circuits::estimate::RowEstimate;
+use delphinus_zkwasm::circuits::image_table::compute_maximal_pages;
 use delphinus_zkwasm::circuits::ZkWasmCircuit;
+use delphinus_zkwasm::loader::Module;
 use delphinus_zkwasm::loader::ZkWasmLoader;
+use delphinus_zkwasm::runtime::host::default_env::DefaultHostEnvBuilder;
+use delphinus_zkwasm::runtime::host::default_env::ExecutionArg;
+use delphinus_zkwasm::runtime::host::host_env::HostEnv;
 use delphinus_zkwasm::runtime::host::HostEnvBuilder;
 use delphinus_zkwasm::runtime::monitor::table_monitor::TableMonitor;
 use halo2_proofs::arithmetic::CurveAffine;
@@ -22,20 +35,37 @@ use halo2_proofs::plonk::keygen_vk;
 use halo2_proofs::plonk::Circuit;
 use halo2_proofs::plonk::CircuitData;
 use halo2_proofs::poly::commitment::Params;
+use parity_wasm::elements::External;
+use parity_wasm::elements::Type;
+use specs::mtable::LocationType;
 use specs::slice::Slice;
 use specs::CompilationTable;
 use specs::TraceBackend;
+use wasmi::ModuleImportResolver;
+use wasmi::Signature;
 
+use crate::args::open_private_inputs;
+use crate::args::read_host_config;
+use crate::args::read_indexed_witness;
+use crate::args::read_inputs;
+use crate::args::read_output_channels;
 use crate::args::HostMode;
+use crate::config::sha256;
 use crate::config::Config;
+use crate::config::Features;
+use crate::config::LegacyCircuitDataMd5;
+use crate::config::VersionedConfig;
+use crate::config::CONFIG_VERSION;
 use crate::names::name_of_circuit_data;
 use crate::names::name_of_config;
 use crate::names::name_of_params;
+use crate::report::Reporter;
 
 #[derive(Debug)]
 pub(crate) struct SetupArg {
     pub(crate) k: u32,
     pub(crate) host_mode: HostMode,
+    pub(crate) host_config: Option<PathBuf>,
     pub(crate) phantom_functions: Vec<String>,
     pub(crate) wasm_image: Option<PathBuf>,
 }
@@ -45,18 +75,18 @@ impl SetupArg {
         params: &Params<C>,
         circuit: &ConcreteCircuit,
         path_of_circuit_data: PathBuf,
-    ) -> anyhow::Result<CircuitDataMd5> {
+    ) -> anyhow::Result<CircuitDataDigest> {
         let vkey = keygen_vk(params, circuit)?;
         let circuit_data = CircuitData::new(params, vkey, circuit)?;
 
-        let verifying_key_md5 = {
+        let verifying_key_sha256 = {
             let mut buf = Vec::new();
             circuit_data.get_vkey().write(&mut buf)?;
 
-            format!("{:x}", md5::compute(&buf))
+            sha256(&buf)
         };
 
-        let circuit_data_md5 = {
+        let circuit_data_sha256 = {
             circuit_data.write(
                 &mut OpenOptions::new()
                     .read(true)
@@ -69,12 +99,12 @@ impl SetupArg {
             let mut buf = Vec::new();
             File::open(&path_of_circuit_data)?.read_to_end(&mut buf)?;
 
-            format!("{:x}", md5::compute(&buf))
+            sha256(&buf)
         };
 
-        Ok(CircuitDataMd5 {
-            circuit_data_md5,
-            verifying_key_md5,
+        Ok(CircuitDataDigest {
+            circuit_data_sha256,
+            verifying_key_sha256,
         })
     }
 
@@ -86,7 +116,7 @@ impl SetupArg {
         k: u32,
         compilation_tables: &CompilationTable,
     ) -> anyhow::Result<CircuitDataConfig> {
-        let setup_circuit = |is_last_slice| -> anyhow::Result<CircuitDataMd5> {
+        let setup_circuit = |is_last_slice| -> anyhow::Result<CircuitDataDigest> {
             let setup_circuit = ZkWasmCircuit::new(
                 k,
                 Slice::from_compilation_table(compilation_tables, is_last_slice),
@@ -103,6 +133,9 @@ impl SetupArg {
                     &circuit,
                     params_dir.join(name_of_circuit_data(name, is_last_slice)),
                 ),
+                ZkWasmCircuit::TrapSliceCircuit(_) => anyhow::bail!(
+                    "The circuit of an execution terminated by a trap is not supported by the cli."
+                ),
             }
         };
 
@@ -120,35 +153,42 @@ impl SetupArg {
 
     pub(crate) fn setup(
         &self,
-        env_builder: &dyn HostEnvBuilder,
         name: &str,
         params_dir: &PathBuf,
+        reporter: &mut Reporter,
     ) -> anyhow::Result<()> {
+        let host_config = match (&self.host_config, self.host_mode) {
+            (Some(host_config), HostMode::Standard) => Some(read_host_config(host_config)?),
+            (Some(_), HostMode::Default) => {
+                anyhow::bail!("The host config is only valid with `--host standard`.")
+            }
+            (None, _) => None,
+        };
+        let env_builder = self.host_mode.env_builder(self.k, host_config.as_ref());
+
         fs::create_dir_all(params_dir)?;
 
         let wasm_image = self.wasm_image.as_ref().map_or(
             wabt::wat2wasm(TRIVIAL_WASM).map_err(|err| anyhow::anyhow!(err)),
             |file| fs::read(file).map_err(|err| anyhow::anyhow!(err)),
         )?;
-        let module = ZkWasmLoader::parse_module(&wasm_image)?;
-        let wasm_image_md5 = md5::compute(&wasm_image);
+        let module = ZkWasmLoader::parse_module(&wasm_image, self.k)?;
+        let wasm_image_sha256 = sha256(&wasm_image);
 
         let params_path = params_dir.join(name_of_params(self.k));
         let params = {
             if params_path.exists() {
-                println!(
-                    "{} Found existing params at {:?}. Using it instead of building a new one...",
-                    style("[1/5]").bold().dim(),
-                    params_path.canonicalize()?
+                reporter.step(
+                    "[1/5]",
+                    format!(
+                        "Found existing params at {:?}. Using it instead of building a new one...",
+                        params_path.canonicalize()?
+                    ),
                 );
 
                 Params::<G1Affine>::read(&mut File::open(&params_path)?)?
             } else {
-                println!(
-                    "{} Building params for K = {}...",
-                    style("[1/5]").bold().dim(),
-                    self.k
-                );
+                reporter.step("[1/5]", format!("Building params for K = {}...", self.k));
                 let params = Params::<G1Affine>::unsafe_setup::<Bn256>(self.k);
 
                 params.write(&mut File::create(&params_path)?)?;
@@ -167,15 +207,15 @@ impl SetupArg {
 
         let loader = ZkWasmLoader::new(self.k, env)?;
 
-        println!("{} Compiling...", style("[2/5]").bold().dim());
+        reporter.step("[2/5]", "Compiling...");
         loader.compile(&module, &mut monitor)?;
         let compilation_table = monitor.into_compilation_table();
 
-        println!("{} Building circuit data...", style("[3/5]").bold().dim(),);
+        reporter.step("[3/5]", "Building circuit data...");
         let circuit_datas =
             self.setup_circuit_data(name, params_dir, &params, self.k, &compilation_table)?;
 
-        println!("{} Computing checksum...", style("[4/5]").bold().dim(),);
+        reporter.step("[4/5]", "Computing checksum...");
         let checksum = {
             let checksum = compilation_table.checksum(self.k, &params);
             assert_eq!(checksum.len(), 1);
@@ -184,46 +224,695 @@ impl SetupArg {
         };
 
         {
-            println!("{} Writing config...", style("[5/5]").bold().dim(),);
+            reporter.step("[5/5]", "Writing config...");
 
-            let params_md5 = {
+            let params_sha256 = {
                 let mut buf = Vec::new();
 
                 params.write(&mut buf)?;
-                let md5 = md5::compute(&buf);
 
-                format!("{:x}", md5)
+                sha256(&buf)
             };
 
             let config_path = params_dir.join(name_of_config(name));
 
             let config = Config {
                 name: name.to_string(),
-
-                k: self.k,
                 params: params_path,
-                is_uniform_circuit: cfg!(feature = "uniform-circuit"),
 
-                params_md5,
-                wasm_image_md5: if cfg!(feature = "uniform-circuit") {
+                features: Features::current(),
+                k: self.k,
+                host_mode: self.host_mode,
+                host_config,
+                phantom_functions: self.phantom_functions.clone(),
+                params_sha256,
+                wasm_image_sha256: if cfg!(feature = "uniform-circuit") {
                     None
                 } else {
-                    Some(format!("{:x}", wasm_image_md5))
+                    Some(wasm_image_sha256)
                 },
                 circuit_datas,
-
                 checksum,
-                phantom_functions: self.phantom_functions.clone(),
-                host_mode: self.host_mode,
             };
             config.write(&mut File::create(&config_path)?)?;
 
-            println!(
+            reporter.info(format!(
                 "{} {:?}",
                 style("The configuration is saved at").green().bold().dim(),
                 config_path.canonicalize()?
+            ));
+            reporter.set("config", config_path.canonicalize()?);
+            reporter.set("params", &config.params);
+            reporter.set("checksum", &config.checksum);
+            reporter.set("manifest_digest", config.manifest_digest());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct InspectArg {
+    pub(crate) k: Option<u32>,
+    pub(crate) host_mode: Option<HostMode>,
+    pub(crate) wasm_image: Option<PathBuf>,
+}
+
+fn into_wasmi_value_type(ty: &parity_wasm::elements::ValueType) -> wasmi::ValueType {
+    match ty {
+        parity_wasm::elements::ValueType::I32 => wasmi::ValueType::I32,
+        parity_wasm::elements::ValueType::I64 => wasmi::ValueType::I64,
+        parity_wasm::elements::ValueType::F32 => wasmi::ValueType::F32,
+        parity_wasm::elements::ValueType::F64 => wasmi::ValueType::F64,
+    }
+}
+
+impl InspectArg {
+    fn inspect_imports(module: &Module, k: u32, reporter: &mut Reporter) {
+        let envs: Vec<(&str, HostEnv)> = vec![
+            (
+                "default",
+                DefaultHostEnvBuilder::new(k).create_env_without_value(),
+            ),
+            (
+                "standard",
+                StandardHostEnvBuilder::new(k).create_env_without_value(),
+            ),
+        ];
+
+        let module = module.module();
+        let imports = module.import_section().map_or(&[][..], |s| s.entries());
+        let types = module.type_section().map_or(&[][..], |s| s.types());
+
+        reporter.info(style("Imported host functions:").bold());
+        reporter.set("imports", Vec::<serde_json::Value>::new());
+
+        for import in imports {
+            let type_ref = match import.external() {
+                External::Function(type_ref) => *type_ref,
+                _ => continue,
+            };
+            let Some(Type::Function(func_type)) = types.get(type_ref as usize) else {
+                continue;
+            };
+
+            let signature = Signature::new(
+                func_type
+                    .params()
+                    .iter()
+                    .map(into_wasmi_value_type)
+                    .collect::<Vec<_>>(),
+                func_type.results().first().map(into_wasmi_value_type),
+            );
+
+            let resolved = envs
+                .iter()
+                .map(|(mode, env)| {
+                    (
+                        *mode,
+                        import.module() == "env"
+                            && env.resolve_func(import.field(), &signature).is_ok(),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            reporter.info(format!(
+                "  {}.{} {:?} -> {:?}\t{}",
+                import.module(),
+                import.field(),
+                func_type.params(),
+                func_type.results(),
+                resolved
+                    .iter()
+                    .map(|(mode, resolved)| if *resolved {
+                        format!("{}: {}", mode, style("resolved").green())
+                    } else {
+                        format!("{}: {}", mode, style("missing").red())
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+            reporter.push(
+                "imports",
+                serde_json::json!({
+                    "module": import.module(),
+                    "field": import.field(),
+                    "params": format!("{:?}", func_type.params()),
+                    "results": format!("{:?}", func_type.results()),
+                    "resolved": resolved.into_iter().collect::<HashMap<_, _>>(),
+                }),
             );
         }
+    }
+
+    fn inspect_image(
+        wasm_image: &Path,
+        k: u32,
+        host_mode: HostMode,
+        host_config: Option<&HostEnvConfig>,
+        phantom_functions: &[String],
+        reporter: &mut Reporter,
+    ) -> anyhow::Result<()> {
+        let module = Module::from_buffer(fs::read(wasm_image)?)?;
+
+        reporter.info(format!(
+            "{} {:?} (K = {}, host = {:?})",
+            style("Image").bold(),
+            wasm_image,
+            k,
+            host_mode
+        ));
+        reporter.set("image", wasm_image);
+        reporter.set("k", k);
+        reporter.set("host_mode", host_mode);
+
+        let errs = ZkWasmLoader::precheck(&module, k);
+        if errs.is_empty() {
+            reporter.info(format!("  precheck: {}", style("passed").green()));
+        } else {
+            reporter.info(format!("  precheck: {}", style("failed").red()));
+            for err in &errs {
+                reporter.info(format!("    - {}", err));
+            }
+        }
+        reporter.set(
+            "precheck",
+            errs.iter().map(|err| err.to_string()).collect::<Vec<_>>(),
+        );
+
+        Self::inspect_imports(&module, k, reporter);
+
+        let env_builder = host_mode.env_builder(k, host_config);
+        let env = env_builder.create_env_without_value();
+        let mut monitor = TableMonitor::new(
+            k,
+            env_builder.create_flush_strategy(),
+            phantom_functions,
+            TraceBackend::Memory,
+            &env,
+        );
+
+        let loader = ZkWasmLoader::new(k, env)?;
+        loader.compile(&module, &mut monitor)?;
+        let compilation_table = monitor.into_compilation_table();
+
+        let init_memory_entries = |ltype: LocationType| {
+            compilation_table
+                .imtable
+                .0
+                .values()
+                .filter(|entry| entry.ltype == ltype)
+                .count()
+        };
+
+        reporter.info(style("Compilation tables:").bold());
+        reporter.info(format!(
+            "  instructions: {}",
+            compilation_table.itable.len()
+        ));
+        reporter.info(format!(
+            "  br table entries: {}",
+            compilation_table.br_table.entries().len()
+        ));
+        reporter.info(format!(
+            "  elem table entries: {}",
+            compilation_table.elem_table.entries().len()
+        ));
+        reporter.info(format!(
+            "  init memory entries: {} (heap: {}, global: {})",
+            compilation_table.imtable.0.len(),
+            init_memory_entries(LocationType::Heap),
+            init_memory_entries(LocationType::Global)
+        ));
+
+        let circuit_maximal_pages = compute_maximal_pages(k);
+        let configure_table = &compilation_table.configure_table;
+        let pages = format!(
+            "initial {}, maximal {}, supported by circuit {}",
+            configure_table.init_memory_pages,
+            configure_table.maximal_memory_pages,
+            circuit_maximal_pages
+        );
+        if configure_table.init_memory_pages > circuit_maximal_pages {
+            reporter.info(format!("  pages: {}", style(pages).red()));
+        } else {
+            reporter.info(format!("  pages: {}", pages));
+        }
+
+        reporter.set(
+            "compilation_tables",
+            serde_json::json!({
+                "instructions": compilation_table.itable.len(),
+                "br_table_entries": compilation_table.br_table.entries().len(),
+                "elem_table_entries": compilation_table.elem_table.entries().len(),
+                "init_memory_entries": {
+                    "heap": init_memory_entries(LocationType::Heap),
+                    "global": init_memory_entries(LocationType::Global),
+                },
+                "pages": {
+                    "initial": configure_table.init_memory_pages,
+                    "maximal": configure_table.maximal_memory_pages,
+                    "circuit_maximal": circuit_maximal_pages,
+                },
+            }),
+        );
+
+        Ok(())
+    }
+
+    pub(crate) fn inspect(
+        &self,
+        name: &str,
+        params_dir: &Path,
+        reporter: &mut Reporter,
+    ) -> anyhow::Result<()> {
+        let config_path = params_dir.join(name_of_config(name));
+
+        let config = if config_path.exists() {
+            let config = Config::read(&mut File::open(&config_path)?)?;
+
+            reporter.info(format!("{} {:?}", style("Config").bold(), config_path));
+            reporter.info(serde_json::to_string_pretty(&config)?);
+            reporter.info(format!(
+                "{} {}",
+                style("Manifest digest").bold(),
+                config.manifest_digest()
+            ));
+            reporter.set("config", &config);
+            reporter.set("manifest_digest", config.manifest_digest());
+
+            Some(config)
+        } else {
+            reporter.info(format!("No config found at {:?}", config_path));
+
+            None
+        };
+
+        if let Some(wasm_image) = &self.wasm_image {
+            let k = self
+                .k
+                .or(config.as_ref().map(|config| config.k))
+                .ok_or_else(|| anyhow::anyhow!("K is required if no config is found."))?;
+            let host_mode = self
+                .host_mode
+                .or(config.as_ref().map(|config| config.host_mode))
+                .unwrap_or_default();
+            let host_config = config
+                .as_ref()
+                .and_then(|config| config.host_config.as_ref());
+            let phantom_functions = config
+                .as_ref()
+                .map_or(vec![], |config| config.phantom_functions.clone());
+
+            Self::inspect_image(
+                wasm_image,
+                k,
+                host_mode,
+                host_config,
+                &phantom_functions,
+                reporter,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Estimate the rows of the tables and the minimal K by executing the Wasm image.
+#[derive(Debug)]
+pub(crate) struct EstimateArg {
+    pub(crate) host_mode: Option<HostMode>,
+    pub(crate) wasm_image: PathBuf,
+    pub(crate) public_inputs: Vec<String>,
+    pub(crate) private_inputs: Vec<String>,
+    pub(crate) context_inputs: Vec<String>,
+    pub(crate) input_manifest: Option<PathBuf>,
+    pub(crate) private_stream: Option<String>,
+    pub(crate) output_channels: Option<PathBuf>,
+    pub(crate) witness: Vec<String>,
+    pub(crate) tree_db: Option<PathBuf>,
+}
+
+impl EstimateArg {
+    // Candidate K supported by setup.
+    const CANDIDATES: Range<u32> = 18..23;
+
+    pub(crate) fn estimate(
+        &self,
+        name: &str,
+        params_dir: &Path,
+        reporter: &mut Reporter,
+    ) -> anyhow::Result<()> {
+        let config_path = params_dir.join(name_of_config(name));
+        let config = if config_path.exists() {
+            Some(Config::read(&mut File::open(&config_path)?)?)
+        } else {
+            None
+        };
+
+        let host_mode = self
+            .host_mode
+            .or(config.as_ref().map(|config| config.host_mode))
+            .unwrap_or_default();
+        let host_config = config
+            .as_ref()
+            .and_then(|config| config.host_config.as_ref());
+        let phantom_functions = config
+            .as_ref()
+            .map_or(vec![], |config| config.phantom_functions.clone());
+
+        // Execute with the largest K to get the least slices.
+        let k = Self::CANDIDATES.end - 1;
+
+        let module = Module::from_buffer(fs::read(&self.wasm_image)?)?;
+
+        let (public_inputs, private_inputs, context_inputs) = read_inputs(
+            self.input_manifest.as_deref(),
+            &self.public_inputs,
+            &self.private_inputs,
+            &self.context_inputs,
+        )?;
+
+        let env_builder = host_mode.env_builder(k, host_config);
+        let env = env_builder.create_env(ExecutionArg {
+            public_inputs,
+            private_inputs: open_private_inputs(private_inputs, self.private_stream.as_deref())?,
+            context_inputs,
+            indexed_witness: Rc::new(RefCell::new(read_indexed_witness(&self.witness)?)),
+            tree_db: self
+                .tree_db
+                .as_deref()
+                .map(open_local_tree_db)
+                .transpose()?,
+            output_channels: read_output_channels(self.output_channels.as_deref())?,
+        });
+        let plugins = env.external_env.plugins();
+        let mut monitor = TableMonitor::new(
+            k,
+            env_builder.create_flush_strategy(),
+            &phantom_functions,
+            TraceBackend::Memory,
+            &env,
+        );
+
+        reporter.step("[1/2]", "Executing...");
+        {
+            let loader = ZkWasmLoader::new(k, env)?;
+            let runner = loader.compile(&module, &mut monitor)?;
+            loader.run(runner, &mut monitor)?;
+        }
+
+        reporter.step("[2/2]", "Estimating...");
+        let estimate = RowEstimate::from_tables(&monitor.into_tables()?)?;
+        let host_statics = |k: u32| {
+            plugins
+                .iter()
+                .filter_map(|plugin| {
+                    plugin
+                        .get_statics(k)
+                        .map(|statics| (plugin.name.clone(), statics))
+                })
+                .collect::<HashMap<_, _>>()
+        };
+
+        reporter.info(style("Rows:").bold());
+        reporter.info(format!(
+            "  event table: {} entries, {} rows",
+            estimate.etable_entries,
+            estimate.etable_rows()
+        ));
+        reporter.info(format!(
+            "  memory table: {} entries, {} rows",
+            estimate.mtable_entries,
+            estimate.mtable_rows()
+        ));
+        reporter.info(format!(
+            "  frame table: {} entries, {} rows",
+            estimate.frame_table_entries,
+            estimate.frame_table_rows()
+        ));
+        reporter.info(format!(
+            "  image table: {} entries, {} rows ({} pages)",
+            estimate.image_entries,
+            estimate.image_table_rows(),
+            estimate.memory_pages
+        ));
+        reporter.set("estimate", &estimate);
+
+        let mut plugin_names = plugins
+            .iter()
+            .map(|plugin| plugin.name.clone())
+            .collect::<Vec<_>>();
+        plugin_names.sort();
+        let host_statics_of = Self::CANDIDATES
+            .map(|k| (k, host_statics(k)))
+            .collect::<Vec<_>>();
+        if !host_statics_of[0].1.is_empty() {
+            reporter.info(style("Host op rounds:").bold());
+        }
+        reporter.set("host_op_rounds", Vec::<serde_json::Value>::new());
+        for name in plugin_names {
+            let rounds = host_statics_of
+                .iter()
+                .filter_map(|(k, host_statics)| host_statics.get(&name).map(|statics| (k, statics)))
+                .collect::<Vec<_>>();
+            let Some((_, statics)) = rounds.first() else {
+                continue;
+            };
+
+            reporter.info(format!(
+                "  {}: {} used, maximal {}",
+                name,
+                statics.used_round,
+                rounds
+                    .iter()
+                    .map(|(k, statics)| format!("{} (K = {})", statics.max_round, k))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+            reporter.push(
+                "host_op_rounds",
+                serde_json::json!({
+                    "plugin": name,
+                    "used_round": statics.used_round,
+                    "max_round": rounds
+                        .iter()
+                        .map(|(k, statics)| (k.to_string(), statics.max_round.into()))
+                        .collect::<serde_json::Map<_, _>>(),
+                }),
+            );
+        }
+
+        reporter.info(style("Candidates:").bold());
+        let candidates = host_statics_of
+            .iter()
+            .map(|(k, host_statics)| estimate.candidate(*k, host_statics))
+            .collect::<Vec<_>>();
+        for candidate in &candidates {
+            let slices = candidate
+                .slices
+                .map_or("-".to_string(), |slices| slices.to_string());
+
+            if candidate.bottlenecks.is_empty() {
+                reporter.info(format!(
+                    "  K = {}: {}, {} slice(s) with continuation",
+                    candidate.k,
+                    style("fits").green(),
+                    slices
+                ));
+            } else {
+                reporter.info(format!(
+                    "  K = {}: {} {:?}, {} slice(s) with continuation",
+                    candidate.k,
+                    style("exceeds").red(),
+                    candidate.bottlenecks,
+                    slices
+                ));
+            }
+        }
+        reporter.set("candidates", &candidates);
+
+        let minimal_k = estimate.minimal_k(Self::CANDIDATES, host_statics);
+        match minimal_k {
+            Some(minimal_k) => reporter.info(format!(
+                "{} {}",
+                style("Minimal K without continuation:").bold(),
+                minimal_k
+            )),
+            None => reporter.info(format!(
+                "{} none of {:?}",
+                style("Minimal K without continuation:").bold(),
+                Self::CANDIDATES
+            )),
+        }
+        reporter.set("minimal_k", minimal_k);
+
+        Ok(())
+    }
+}
+
+/// Upgrade a legacy configuration to the current version.
+#[derive(Debug)]
+pub(crate) struct MigrateArg {
+    pub(crate) wasm_image: Option<PathBuf>,
+}
+
+impl MigrateArg {
+    fn migrate_circuit_data(
+        path: &Path,
+        legacy: &LegacyCircuitDataMd5,
+    ) -> anyhow::Result<CircuitDataDigest> {
+        let buf = fs::read(path)?;
+
+        if format!("{:x}", md5::compute(&buf)) != legacy.circuit_data_md5 {
+            anyhow::bail!(
+                "Circuit data {:?} is inconsistent with the configuration.",
+                path
+            );
+        }
+
+        let verifying_key = {
+            let circuit_data = CircuitData::<G1Affine>::read(&mut File::open(path)?)?;
+
+            let mut buf = Vec::new();
+            circuit_data.get_vkey().write(&mut buf)?;
+
+            buf
+        };
+
+        if format!("{:x}", md5::compute(&verifying_key)) != legacy.verifying_key_md5 {
+            anyhow::bail!(
+                "Verifying key of {:?} is inconsistent with the configuration.",
+                path
+            );
+        }
+
+        Ok(CircuitDataDigest {
+            circuit_data_sha256: sha256(&buf),
+            verifying_key_sha256: sha256(&verifying_key),
+        })
+    }
+
+    pub(crate) fn migrate(
+        &self,
+        name: &str,
+        params_dir: &Path,
+        reporter: &mut Reporter,
+    ) -> anyhow::Result<()> {
+        let config_path = params_dir.join(name_of_config(name));
+
+        reporter.step(
+            "[1/3]",
+            format!("Reading configuration {:?}...", config_path),
+        );
+        let legacy = match Config::read_versioned(&mut File::open(&config_path)?)? {
+            VersionedConfig::Current(_) => {
+                reporter.info(format!(
+                    "The configuration is already of version {}.",
+                    CONFIG_VERSION
+                ));
+                reporter.set("version", CONFIG_VERSION);
+
+                return Ok(());
+            }
+            VersionedConfig::Legacy(legacy) => legacy,
+        };
+
+        if legacy.is_uniform_circuit != cfg!(feature = "uniform-circuit") {
+            anyhow::bail!(
+                "The configuration is set up with uniform-circuit = {}, \
+                    please migrate it by the cli built with the same features.",
+                legacy.is_uniform_circuit
+            );
+        }
+
+        // Files are only trusted if they still match the md5 digests in the configuration.
+        reporter.step("[2/3]", "Verifying files of the configuration...");
+        let params_sha256 = {
+            let buf = fs::read(&legacy.params)?;
+
+            if format!("{:x}", md5::compute(&buf)) != legacy.params_md5 {
+                anyhow::bail!(
+                    "Params {:?} is inconsistent with the configuration.",
+                    legacy.params
+                );
+            }
+
+            sha256(&buf)
+        };
+
+        let wasm_image_sha256 = match &legacy.wasm_image_md5 {
+            Some(wasm_image_md5) => {
+                let wasm_image = self.wasm_image.as_ref().ok_or_else(|| {
+                    anyhow::anyhow!("The Wasm image is required to migrate the configuration.")
+                })?;
+                let buf = fs::read(wasm_image)?;
+
+                if &format!("{:x}", md5::compute(&buf)) != wasm_image_md5 {
+                    anyhow::bail!(
+                        "Wasm image {:?} is inconsistent with the configuration.",
+                        wasm_image
+                    );
+                }
+
+                Some(sha256(&buf))
+            }
+            None => None,
+        };
+
+        #[cfg(feature = "continuation")]
+        let circuit_datas = CircuitDataConfig {
+            on_going_circuit: Self::migrate_circuit_data(
+                &params_dir.join(name_of_circuit_data(name, false)),
+                &legacy.circuit_datas.on_going_circuit,
+            )?,
+            finalized_circuit: Self::migrate_circuit_data(
+                &params_dir.join(name_of_circuit_data(name, true)),
+                &legacy.circuit_datas.finalized_circuit,
+            )?,
+        };
+
+        #[cfg(not(feature = "continuation"))]
+        let circuit_datas = CircuitDataConfig {
+            finalized_circuit: Self::migrate_circuit_data(
+                &params_dir.join(name_of_circuit_data(name, true)),
+                &legacy.circuit_datas.finalized_circuit,
+            )?,
+        };
+
+        reporter.step("[3/3]", "Writing config...");
+        let backup_path = config_path.with_extension("config.v0");
+        fs::copy(&config_path, &backup_path)?;
+
+        let config = Config {
+            name: legacy.name,
+            params: legacy.params,
+
+            features: Features::current(),
+            k: legacy.k,
+            host_mode: legacy.host_mode,
+            host_config: None,
+            phantom_functions: legacy.phantom_functions,
+            params_sha256,
+            wasm_image_sha256,
+            circuit_datas,
+            checksum: legacy.checksum,
+        };
+        config.write(&mut File::create(&config_path)?)?;
+
+        reporter.info(format!(
+            "{} {:?}, the legacy one is kept at {:?}",
+            style("The configuration is migrated to version")
+                .green()
+                .bold()
+                .dim(),
+            CONFIG_VERSION,
+            backup_path
+        ));
+        reporter.set("version", CONFIG_VERSION);
+        reporter.set("config", &config_path);
+        reporter.set("backup", &backup_path);
+        reporter.set("manifest_digest", config.manifest_digest());
 
         Ok(())
     }
@@ -235,21 +924,42 @@ pub(crate) struct RunningArg {
     #[clap(short = 'o', long = "output")]
     pub(crate) output_dir: PathBuf,
 
-    /// Public inputs with format 'value:type' where type=i64|bytes|bytes-packed|file.
+    /// Public inputs with format 'value:type' where type=i64|u64|i32|bytes|bytes-packed|hex-words|base64|json|file-le|file-be|file.
     #[clap(long = "public")]
     pub(crate) public_inputs: Vec<String>,
 
-    /// Private inputs with format 'value:type' where type=i64|bytes|bytes-packed|file.
+    /// Private inputs with format 'value:type' where type=i64|u64|i32|bytes|bytes-packed|hex-words|base64|json|file-le|file-be|file.
     #[clap(long = "private")]
     pub(crate) private_inputs: Vec<String>,
 
-    /// Context inputs with format 'value:type' where type=i64|bytes|bytes-packed|file.
+    /// Context inputs with format 'value:type' where type=i64|u64|i32|bytes|bytes-packed|hex-words|base64|json|file-le|file-be|file.
     #[clap(long = "context-in")]
     pub(crate) context_inputs: Vec<String>,
 
+    /// Path to the JSON manifest listing the public, private and context inputs.
+    #[clap(long = "inputs")]
+    pub(crate) input_manifest: Option<PathBuf>,
+
+    /// Private inputs read lazily with format 'file:type' where type=file-le|file-be, the file
+    /// `-` is the stdin.
+    #[clap(long = "private-stream")]
+    pub(crate) private_stream: Option<String>,
+
     /// Filename to the file to write the context output.
     #[clap(long = "context-out")]
     pub(crate) context_output: Option<String>,
+
+    /// Path to the JSON list of the named output channels.
+    #[clap(long = "output-channels")]
+    pub(crate) output_channels: Option<PathBuf>,
+
+    /// Indexed witness with format 'index:file'.
+    #[clap(long = "witness")]
+    pub(crate) witness: Vec<String>,
+
+    /// Path to the directory of the local db for the merkle and data cache host functions.
+    #[clap(long = "tree-db")]
+    pub(crate) tree_db: Option<PathBuf>,
 }
 
 #[derive(Debug)]
@@ -257,6 +967,23 @@ pub(crate) struct DryRunArg {
     pub(crate) wasm_image: PathBuf,
     pub(crate) running_arg: RunningArg,
     pub(crate) instruction_limit: Option<usize>,
+    pub(crate) profile: bool,
+}
+
+/// Step through the execution of the Wasm image.
+#[derive(Debug)]
+pub(crate) struct DebugArg {
+    pub(crate) wasm_image: PathBuf,
+    pub(crate) public_inputs: Vec<String>,
+    pub(crate) private_inputs: Vec<String>,
+    pub(crate) context_inputs: Vec<String>,
+    pub(crate) input_manifest: Option<PathBuf>,
+    pub(crate) private_stream: Option<String>,
+    pub(crate) output_channels: Option<PathBuf>,
+    pub(crate) witness: Vec<String>,
+    pub(crate) tree_db: Option<PathBuf>,
+    // load the traces written by `prove` instead of executing the image.
+    pub(crate) trace_dir: Option<PathBuf>,
 }
 
 /// Execute the Wasm image and generate a proof.
@@ -267,10 +994,37 @@ pub(crate) struct ProveArg {
     pub(crate) running_arg: RunningArg,
     pub(crate) mock_test: bool,
     pub(crate) file_backend: bool,
+    // resume from the first slice without a proof instead of executing the image.
+    pub(crate) resume: bool,
     // skip first n slice(s) proving.
     pub(crate) skip: usize,
     // add trivial circuits to padding
     pub(crate) padding: Option<usize>,
+    // prove slices in n worker processes.
+    pub(crate) workers: Option<usize>,
+}
+
+/// Prove a single slice written by `prove --workers`.
+#[derive(Debug)]
+pub(crate) struct ProveSliceArg {
+    pub(crate) wasm_image: PathBuf,
+    pub(crate) output_dir: PathBuf,
+    pub(crate) index: usize,
+    pub(crate) mock_test: bool,
+}
+
+/// Prove from the traces written by `prove` without executing the image.
+#[derive(Debug)]
+pub(crate) struct ReplayArg {
+    pub(crate) trace_dir: PathBuf,
+    pub(crate) output_dir: PathBuf,
+    pub(crate) mock_test: bool,
+}
+
+/// Summarize the executed instructions of the traces written by `prove`.
+#[derive(Debug)]
+pub(crate) struct StatisticArg {
+    pub(crate) trace_dir: PathBuf,
 }
 
 /// Verify the proof.
@@ -285,6 +1039,13 @@ pub(crate) struct VerifyArg {
 pub(crate) enum Subcommands {
     Setup(SetupArg),
     DryRun(DryRunArg),
+    Debug(DebugArg),
     Prove(ProveArg),
+    ProveSlice(ProveSliceArg),
+    Replay(ReplayArg),
+    Statistic(StatisticArg),
     Verify(VerifyArg),
+    Inspect(InspectArg),
+    Estimate(EstimateArg),
+    Migrate(MigrateArg),
 }
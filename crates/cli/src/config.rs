@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Cursor;
 use std::io::Read;
@@ -13,53 +14,132 @@ use circuits_batcher::proof::ProofInfo;
 use circuits_batcher::proof::ProofPieceInfo;
 use circuits_batcher::proof::Prover;
 use console::style;
+use delphinus_host::HostEnvConfig;
 use delphinus_zkwasm::circuits::ZkWasmCircuit;
+use delphinus_zkwasm::debugger::function_names;
+use delphinus_zkwasm::debugger::Debugger;
+use delphinus_zkwasm::error::BuildingCircuitError;
+use delphinus_zkwasm::foreign::output_helper::OutputChannel;
 use delphinus_zkwasm::loader::slice::Slices;
 use delphinus_zkwasm::loader::Module;
 use delphinus_zkwasm::loader::ZkWasmLoader;
+use delphinus_zkwasm::profile::instruction_statistic::InstructionStatistics;
 use delphinus_zkwasm::runtime::host::default_env::ExecutionArg;
 use delphinus_zkwasm::runtime::host::HostEnvBuilder;
 use delphinus_zkwasm::runtime::monitor::statistic_monitor::StatisticMonitor;
 use delphinus_zkwasm::runtime::monitor::table_monitor::TableMonitor;
 use halo2_proofs::pairing::bn256::Bn256;
+use halo2_proofs::pairing::bn256::Fr;
 use halo2_proofs::pairing::bn256::G1Affine;
 use halo2_proofs::plonk::CircuitData;
 use halo2_proofs::poly::commitment::Params;
 use indicatif::ProgressBar;
 use serde::Deserialize;
 use serde::Serialize;
+use specs::slice::SliceState;
+use specs::trace::read_header;
+use specs::trace::TraceError;
+use specs::trace::TraceHeader;
+use specs::trace::TraceKind;
+use specs::Tables;
 use specs::TraceBackend;
+use specs::COMPILATION_TABLES;
 
 use crate::args::HostMode;
+use crate::debugger::run_debugger;
 use crate::names::name_of_circuit_data;
+use crate::names::name_of_etable_slice;
+use crate::names::name_of_external_host_call_table_slice;
 use crate::names::name_of_frame_table_slice;
 use crate::names::name_of_instance;
 use crate::names::name_of_loadinfo;
 use crate::names::name_of_params;
+use crate::names::name_of_profile;
+use crate::names::name_of_resume_info;
+use crate::names::name_of_slice_state;
 use crate::names::name_of_transcript;
 use crate::names::name_of_witness;
+use crate::report::Reporter;
+
+/// Version of the configuration format.
+///
+/// - 0: the bincode encoded `LegacyConfig` without any header, files are identified by md5.
+/// - 1: `ConfigHeader` followed by the bincode encoded `Config`, files are identified by SHA-256.
+pub(crate) const CONFIG_VERSION: u32 = 1;
+
+const CONFIG_MAGIC: [u8; 8] = *b"ZKWASMCF";
+
+pub(crate) fn sha256(buf: &[u8]) -> String {
+    use sha2::Digest;
+
+    hex::encode(sha2::Sha256::digest(buf))
+}
+
+/// Features of the circuit, a configuration can only be used by the cli built with the same
+/// features.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Features {
+    pub(crate) uniform_circuit: bool,
+    pub(crate) continuation: bool,
+}
+
+impl Features {
+    pub(crate) fn current() -> Self {
+        Features {
+            uniform_circuit: cfg!(feature = "uniform-circuit"),
+            continuation: cfg!(feature = "continuation"),
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize)]
-pub(crate) struct CircuitDataMd5 {
-    pub(crate) circuit_data_md5: String,
-    pub(crate) verifying_key_md5: String,
+struct ConfigHeader {
+    magic: [u8; 8],
+    version: u32,
+    features: Features,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct CircuitDataDigest {
+    pub(crate) circuit_data_sha256: String,
+    pub(crate) verifying_key_sha256: String,
 }
 
 #[cfg(not(feature = "continuation"))]
 #[derive(Serialize, Deserialize)]
 pub(crate) struct CircuitDataConfig {
-    pub(crate) finalized_circuit: CircuitDataMd5,
+    pub(crate) finalized_circuit: CircuitDataDigest,
 }
 
 #[cfg(feature = "continuation")]
 #[derive(Serialize, Deserialize)]
 pub(crate) struct CircuitDataConfig {
-    pub(crate) on_going_circuit: CircuitDataMd5,
-    pub(crate) finalized_circuit: CircuitDataMd5,
+    pub(crate) on_going_circuit: CircuitDataDigest,
+    pub(crate) finalized_circuit: CircuitDataDigest,
 }
 
 #[derive(Serialize, Deserialize)]
-pub(crate) struct Config {
+pub(crate) struct LegacyCircuitDataMd5 {
+    pub(crate) circuit_data_md5: String,
+    pub(crate) verifying_key_md5: String,
+}
+
+#[cfg(not(feature = "continuation"))]
+#[derive(Serialize, Deserialize)]
+pub(crate) struct LegacyCircuitDataConfig {
+    pub(crate) finalized_circuit: LegacyCircuitDataMd5,
+}
+
+#[cfg(feature = "continuation")]
+#[derive(Serialize, Deserialize)]
+pub(crate) struct LegacyCircuitDataConfig {
+    pub(crate) on_going_circuit: LegacyCircuitDataMd5,
+    pub(crate) finalized_circuit: LegacyCircuitDataMd5,
+}
+
+/// The configuration of version 0, it's only kept to migrate existing configurations.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct LegacyConfig {
     pub(crate) name: String,
 
     pub(crate) is_uniform_circuit: bool,
@@ -67,19 +147,219 @@ pub(crate) struct Config {
     pub(crate) params: PathBuf,
     pub(crate) params_md5: String,
     pub(crate) wasm_image_md5: Option<String>,
-    pub(crate) circuit_datas: CircuitDataConfig,
+    pub(crate) circuit_datas: LegacyCircuitDataConfig,
 
     pub(crate) checksum: (String, String),
     pub(crate) phantom_functions: Vec<String>,
     pub(crate) host_mode: HostMode,
 }
 
+pub(crate) enum VersionedConfig {
+    Legacy(LegacyConfig),
+    Current(Config),
+}
+
+/// Results of the execution which are not in the traces, they are required to resume proving
+/// without executing the image again.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ResumeInfo {
+    pub(crate) slices: usize,
+    pub(crate) padding: Option<usize>,
+    pub(crate) instances: Vec<u64>,
+}
+
+impl ResumeInfo {
+    // Trivial slices are inserted so that the number of proofs is at least `padding`.
+    fn total_slices(&self) -> usize {
+        usize::max(self.slices, self.padding.unwrap_or(0))
+    }
+
+    fn instances<F: From<u64>>(&self) -> Vec<F> {
+        self.instances.iter().map(|v| (*v).into()).collect()
+    }
+
+    fn write(&self, path: &Path) -> anyhow::Result<()> {
+        let mut fd = File::create(path)?;
+        fd.write_all(serde_json::to_string_pretty(self)?.as_bytes())?;
+
+        Ok(())
+    }
+
+    fn read(path: &Path) -> anyhow::Result<Self> {
+        let mut buf = Vec::new();
+        File::open(path)?.read_to_end(&mut buf)?;
+
+        Ok(serde_json::from_slice(&buf)?)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Config {
+    pub(crate) name: String,
+    pub(crate) params: PathBuf,
+
+    // The manifest, all the fields below are covered by `manifest_digest`.
+    pub(crate) features: Features,
+    pub(crate) k: u32,
+    pub(crate) host_mode: HostMode,
+    // The ops of the standard host env, the standard ones are used if it's absent.
+    pub(crate) host_config: Option<HostEnvConfig>,
+    pub(crate) phantom_functions: Vec<String>,
+    pub(crate) params_sha256: String,
+    pub(crate) wasm_image_sha256: Option<String>,
+    pub(crate) circuit_datas: CircuitDataConfig,
+    pub(crate) checksum: (String, String),
+}
+
+fn report_proof(reporter: &mut Reporter, output_dir: &Path, proof_piece_info: &ProofPieceInfo) {
+    reporter.push(
+        "proofs",
+        serde_json::json!({
+            "circuit": proof_piece_info.circuit,
+            "witness": output_dir.join(&proof_piece_info.witness),
+            "instance": output_dir.join(&proof_piece_info.instance),
+            "transcript": output_dir.join(&proof_piece_info.transcript),
+        }),
+    );
+}
+
+/// Key of the index of the slice of the first proof in the loadinfo. The proofs of the slices
+/// before it, e.g. skipped by `prove --skip`, are not in the loadinfo.
+const LOADINFO_FIRST_SLICE: &str = "first_slice";
+
+/// Save the loadinfo to `path` with the index of the slice of its first proof.
+fn save_proof_load_info(
+    proof_load_info: &ProofGenerationInfo,
+    first_slice: usize,
+    path: &Path,
+) -> Result<()> {
+    proof_load_info.save(path.parent().unwrap());
+
+    // The loadinfo is written by the batcher, the index is appended to its JSON object.
+    let mut loadinfo: serde_json::Value = serde_json::from_slice(&std::fs::read(path)?)?;
+    loadinfo
+        .as_object_mut()
+        .ok_or_else(|| anyhow::anyhow!("The loadinfo {:?} is not a JSON object.", path))?
+        .insert(LOADINFO_FIRST_SLICE.to_string(), first_slice.into());
+    std::fs::write(path, serde_json::to_string_pretty(&loadinfo)?)?;
+
+    Ok(())
+}
+
+/// Read the index of the slice of the first proof in the loadinfo, see `save_proof_load_info`.
+fn read_first_slice_of_loadinfo(path: &Path) -> Result<usize> {
+    let loadinfo: serde_json::Value = serde_json::from_slice(&std::fs::read(path)?)?;
+
+    match loadinfo.get(LOADINFO_FIRST_SLICE) {
+        // The loadinfo is written without the index by a previous version, it starts from the
+        // first slice.
+        None => Ok(0),
+        Some(first_slice) => first_slice
+            .as_u64()
+            .map(|first_slice| first_slice as usize)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Malformed {} of the loadinfo {:?}.",
+                    LOADINFO_FIRST_SLICE,
+                    path
+                )
+            }),
+    }
+}
+
+/// Write the output channels to `<output_dir>/outputs`, the file of a channel is named after it.
+fn write_output_channels(
+    output_dir: &Path,
+    channels: &[OutputChannel],
+    reporter: &mut Reporter,
+) -> Result<()> {
+    if channels.is_empty() {
+        return Ok(());
+    }
+
+    let outputs_dir = output_dir.join("outputs");
+    std::fs::create_dir_all(&outputs_dir)?;
+
+    for channel in channels {
+        let path = outputs_dir.join(channel.file_name());
+        channel.write(&path)?;
+
+        reporter.info(format!(
+            "Write output channel {} to file {:?}",
+            channel.config.name, path
+        ));
+        reporter.push(
+            "output_channels",
+            serde_json::json!({
+                "name": channel.config.name,
+                "committed": channel.config.committed,
+                "path": path,
+            }),
+        );
+    }
+
+    Ok(())
+}
+
+fn check_trace_header(path: &Path, found: &TraceHeader, expected: &TraceHeader) -> Result<()> {
+    if found.k != expected.k {
+        anyhow::bail!(
+            "Trace {:?} is generated with K = {}, but K of the circuit is {}.",
+            path,
+            found.k,
+            expected.k
+        );
+    }
+
+    if found.image_checksum != expected.image_checksum {
+        anyhow::bail!(
+            "Trace {:?} is generated from another image, image checksum {} is expected but found {}.",
+            path,
+            expected.image_checksum,
+            found.image_checksum
+        );
+    }
+
+    if found.slice != expected.slice {
+        anyhow::bail!(
+            "Trace {:?} belongs to slice {} rather than slice {}.",
+            path,
+            found.slice,
+            expected.slice
+        );
+    }
+
+    Ok(())
+}
+
 impl Config {
-    fn image_consistent_check(&self, wasm_image: &[u8]) -> anyhow::Result<()> {
-        if let Some(expected_wasm_image_md5) = &self.wasm_image_md5 {
-            let wasm_image_md5 = format!("{:x}", md5::compute(wasm_image));
+    /// SHA-256 digest of the manifest, it identifies the circuit and could be signed.
+    pub(crate) fn manifest_digest(&self) -> String {
+        let manifest = bincode::serialize(&(
+            CONFIG_VERSION,
+            &self.features,
+            self.k,
+            &self.host_mode,
+            &self.host_config,
+            &self.phantom_functions,
+            &self.params_sha256,
+            &self.wasm_image_sha256,
+            &self.circuit_datas,
+            &self.checksum,
+        ))
+        .unwrap();
+
+        sha256(&manifest)
+    }
+
+    pub(crate) fn env_builder(&self) -> Box<dyn HostEnvBuilder> {
+        self.host_mode
+            .env_builder(self.k, self.host_config.as_ref())
+    }
 
-            if expected_wasm_image_md5 != &wasm_image_md5 {
+    fn image_consistent_check(&self, wasm_image: &[u8]) -> anyhow::Result<()> {
+        if let Some(expected_wasm_image_sha256) = &self.wasm_image_sha256 {
+            if expected_wasm_image_sha256 != &sha256(wasm_image) {
                 anyhow::bail!(
                     "Wasm image is inconsistent with the one used to build the circuit. \
                         Maybe you have changed the Wasm image after setup the circuit?",
@@ -90,10 +370,23 @@ impl Config {
         Ok(())
     }
 
-    fn params_consistent_check(&self, params: &[u8]) -> anyhow::Result<()> {
-        let params_md5 = format!("{:x}", md5::compute(params));
+    /// Header of the traces generated from `wasm_image`, indexed by slice.
+    pub(crate) fn trace_header(
+        &self,
+        wasm_image: &Path,
+    ) -> anyhow::Result<impl Fn(usize) -> TraceHeader + Clone + 'static> {
+        let k = self.k;
+        let image_checksum = sha256(&std::fs::read(wasm_image)?);
+
+        Ok(move |slice| TraceHeader {
+            k,
+            image_checksum: image_checksum.clone(),
+            slice,
+        })
+    }
 
-        if params_md5 != self.params_md5 {
+    fn params_consistent_check(&self, params: &[u8]) -> anyhow::Result<()> {
+        if sha256(params) != self.params_sha256 {
             anyhow::bail!(
                 "Params is inconsistent with the one used to build the circuit. \
                     Maybe you have changed the params after setup the circuit?",
@@ -106,11 +399,9 @@ impl Config {
     fn veryfying_key_consistent_check(
         &self,
         verifying_key: &[u8],
-        expected_md5: &str,
+        expected_sha256: &str,
     ) -> anyhow::Result<()> {
-        let verifying_key_md5 = format!("{:x}", md5::compute(verifying_key));
-
-        if verifying_key_md5 != expected_md5 {
+        if sha256(verifying_key) != expected_sha256 {
             anyhow::bail!(
                 "Verifying key is inconsistent with the one used to build the circuit. \
                     Maybe you have changed the circuit data after setup the circuit?",
@@ -119,21 +410,112 @@ impl Config {
 
         Ok(())
     }
+
+    #[cfg(feature = "uniform-circuit")]
+    fn image_checksum_check(&self, proof: &ProofInfo<Bn256>) -> anyhow::Result<()> {
+        use delphinus_zkwasm::circuits::image_table::IMAGE_COL_NAME;
+        use halo2_proofs::plonk::get_advice_commitments_from_transcript;
+        use halo2aggregator_s::transcript::poseidon::PoseidonRead;
+
+        let img_col_idx = proof
+            .vkey
+            .cs
+            .named_advices
+            .iter()
+            .find(|(k, _)| k == IMAGE_COL_NAME)
+            .ok_or_else(|| {
+                anyhow::anyhow!("Column {} is not found in the circuit.", IMAGE_COL_NAME)
+            })?
+            .1;
+
+        let advice_commitments: Vec<G1Affine> =
+            get_advice_commitments_from_transcript::<Bn256, _, _>(
+                &proof.vkey,
+                &mut PoseidonRead::init(&proof.transcripts[..]),
+            )
+            .map_err(|err| {
+                anyhow::anyhow!("Failed to read commitments from transcript: {:?}", err)
+            })?;
+
+        let img_col_commitment = advice_commitments
+            .get(img_col_idx as usize)
+            .ok_or_else(|| {
+                anyhow::anyhow!("Commitment of column {} is missing.", IMAGE_COL_NAME)
+            })?;
+
+        if img_col_commitment.x.to_string() != self.checksum.0
+            || img_col_commitment.y.to_string() != self.checksum.1
+        {
+            anyhow::bail!(
+                "Image checksum mismatch: the proof commits to ({}, {}) but the configuration expects ({}, {}). \
+                    Maybe the proof was generated for another Wasm image?",
+                img_col_commitment.x,
+                img_col_commitment.y,
+                self.checksum.0,
+                self.checksum.1,
+            );
+        }
+
+        Ok(())
+    }
 }
 
 impl Config {
     pub(crate) fn write(&self, fd: &mut File) -> anyhow::Result<()> {
+        let header = ConfigHeader {
+            magic: CONFIG_MAGIC,
+            version: CONFIG_VERSION,
+            features: self.features,
+        };
+
+        fd.write_all(&bincode::serialize(&header)?)?;
         fd.write_all(&bincode::serialize(self)?)?;
 
         Ok(())
     }
 
-    pub(crate) fn read(fd: &mut File) -> anyhow::Result<Self> {
+    pub(crate) fn read_versioned(fd: &mut File) -> anyhow::Result<VersionedConfig> {
         let mut buf = Vec::new();
         fd.read_to_end(&mut buf)?;
-        let config = bincode::deserialize(&buf)?;
 
-        Ok(config)
+        if !buf.starts_with(&CONFIG_MAGIC) {
+            return Ok(VersionedConfig::Legacy(bincode::deserialize(&buf)?));
+        }
+
+        let mut cursor = Cursor::new(&buf);
+        let header: ConfigHeader = bincode::deserialize_from(&mut cursor)?;
+
+        if header.version != CONFIG_VERSION {
+            anyhow::bail!(
+                "The configuration is of version {} but version {} is expected. \
+                    Please use the matching zkwasm-cli.",
+                header.version,
+                CONFIG_VERSION
+            );
+        }
+
+        if header.features != Features::current() {
+            anyhow::bail!(
+                "The configuration is built with features {:?} but the cli is built with {:?}.",
+                header.features,
+                Features::current()
+            );
+        }
+
+        Ok(VersionedConfig::Current(bincode::deserialize_from(
+            &mut cursor,
+        )?))
+    }
+
+    pub(crate) fn read(fd: &mut File) -> anyhow::Result<Self> {
+        match Config::read_versioned(fd)? {
+            VersionedConfig::Current(config) => Ok(config),
+            VersionedConfig::Legacy(_) => anyhow::bail!(
+                "The configuration is of the legacy version 0. \
+                    Please upgrade it to version {} by the `migrate` subcommand.",
+                CONFIG_VERSION
+            ),
+        }
     }
 }
 
@@ -144,7 +526,28 @@ impl Config {
 
         self.image_consistent_check(&buf)?;
 
-        ZkWasmLoader::parse_module(&buf)
+        ZkWasmLoader::parse_module(&buf, self.k)
+    }
+
+    // Function names are only used to diagnose the failures of the mock test.
+    fn read_function_names(
+        &self,
+        wasm_image: &Path,
+        mock_test: bool,
+    ) -> anyhow::Result<HashMap<u32, String>> {
+        if mock_test {
+            Ok(function_names(&self.read_wasm_image(wasm_image)?))
+        } else {
+            Ok(HashMap::default())
+        }
+    }
+
+    fn read_image_checksum(&self, wasm_image: &Path) -> anyhow::Result<String> {
+        let buf = std::fs::read(wasm_image)?;
+
+        self.image_consistent_check(&buf)?;
+
+        Ok(sha256(&buf))
     }
 
     fn read_params(&self, params_dir: &Path) -> anyhow::Result<Params<G1Affine>> {
@@ -163,14 +566,12 @@ impl Config {
     fn read_circuit_data(
         &self,
         path: &PathBuf,
-        expected_md5: &str,
+        expected_sha256: &str,
     ) -> anyhow::Result<CircuitData<G1Affine>> {
         let mut buf = Vec::new();
         File::open(path)?.read_to_end(&mut buf)?;
 
-        let circuit_data_md5 = format!("{:x}", md5::compute(&buf));
-
-        if circuit_data_md5 != expected_md5 {
+        if sha256(&buf) != expected_sha256 {
             anyhow::bail!(
                 "Circuit data is inconsistent with the one used to build the circuit. \
                     Maybe you have changed the circuit data after setup the circuit?",
@@ -190,23 +591,27 @@ impl Config {
         arg: ExecutionArg,
         context_output_filename: Option<String>,
         instruction_limit: Option<usize>,
+        profile: bool,
+        reporter: &mut Reporter,
     ) -> Result<()> {
         let module = self.read_wasm_image(wasm_image)?;
 
         let env = env_builder.create_env(arg);
 
         let mut monitor = StatisticMonitor::new(&self.phantom_functions, &env, instruction_limit);
+        if profile {
+            monitor = monitor.with_profiler();
+        }
 
         let result = {
             let loader = ZkWasmLoader::new(self.k, env)?;
 
             let runner = loader.compile(&module, &mut monitor)?;
 
-            println!("{} Executing...", style("[1/2]").bold().dim(),);
+            reporter.step("[1/3]", "Executing...");
             let result = loader.run(runner, &mut monitor)?;
 
-            println!("total guest instructions used {:?}", result.guest_statics);
-            println!("total host api used {:?}", result.host_statics);
+            reporter.execution_statics(result.guest_statics, &result.host_statics);
 
             result
         };
@@ -215,26 +620,85 @@ impl Config {
             if let Some(context_output_filename) = context_output_filename {
                 let context_output_path = output_dir.join(context_output_filename);
 
-                println!(
-                    "{} Write context output to file {:?}...",
-                    style("[2/2]").bold().dim(),
-                    context_output_path
+                reporter.step(
+                    "[2/3]",
+                    format!("Write context output to file {:?}...", context_output_path),
                 );
 
                 result
                     .context_outputs
                     .write(&mut File::create(&context_output_path)?)?;
+                reporter.set("context_output", &context_output_path);
             } else {
-                println!(
-                    "{} Context output is not specified. Skip writing context output...",
-                    style("[2/2]").bold().dim()
+                reporter.step(
+                    "[2/3]",
+                    "Context output is not specified. Skip writing context output...",
                 );
             }
+
+            write_output_channels(output_dir, &result.output_channels, reporter)?;
+        }
+
+        if let Some(profiler) = monitor.profiler() {
+            let profile_path = output_dir.join(name_of_profile(&self.name));
+
+            reporter.step(
+                "[3/3]",
+                format!("Write profile to file {:?}...", profile_path),
+            );
+
+            profiler.write_folded(&mut File::create(&profile_path)?)?;
+            reporter.set("profile", &profile_path);
+
+            reporter.profile(&profiler.functions());
+        } else {
+            reporter.step("[3/3]", "Profile is not enabled. Skip profiling...");
         }
 
         Ok(())
     }
 
+    /// Step through the execution of the image, or the traces in `trace_dir` if it's given.
+    ///
+    /// The debugger is still started if the execution fails, so the steps before the failure
+    /// could be inspected.
+    pub(crate) fn debug(
+        self,
+        env_builder: &dyn HostEnvBuilder,
+        wasm_image: &Path,
+        arg: ExecutionArg,
+        trace_dir: Option<&Path>,
+    ) -> Result<()> {
+        let module = self.read_wasm_image(wasm_image)?;
+
+        let tables = match trace_dir {
+            Some(trace_dir) => {
+                let image_checksum = self.read_image_checksum(wasm_image)?;
+                self.load_traces(trace_dir, Some(image_checksum))?.0
+            }
+            None => {
+                let env = env_builder.create_env(arg);
+                let mut monitor = TableMonitor::new(
+                    self.k,
+                    env_builder.create_flush_strategy(),
+                    &self.phantom_functions,
+                    TraceBackend::Memory,
+                    &env,
+                );
+
+                let loader = ZkWasmLoader::new(self.k, env)?;
+                let runner = loader.compile(&module, &mut monitor)?;
+                if let Err(err) = loader.run(runner, &mut monitor) {
+                    eprintln!("{} {:?}", style("Execution failed:").red(), err);
+                }
+
+                monitor.into_tables()?
+            }
+        };
+
+        run_debugger(Debugger::new(&tables, function_names(&module))?)
+    }
+
     pub(crate) fn prove(
         self,
         env_builder: &dyn HostEnvBuilder,
@@ -247,13 +711,13 @@ impl Config {
         table_backend: TraceBackend,
         skip: usize,
         padding: Option<usize>,
+        workers: Option<usize>,
+        reporter: &mut Reporter,
     ) -> anyhow::Result<()> {
-        let mut cached_proving_key = None;
-
-        println!("{} Load image...", style("[1/8]").bold().dim(),);
+        reporter.step("[1/8]", "Load image...");
         let module = self.read_wasm_image(wasm_image)?;
 
-        println!("{} Load params...", style("[2/8]").bold().dim(),);
+        reporter.step("[2/8]", "Load params...");
         let params = self.read_params(params_dir)?;
 
         let env = env_builder.create_env(arg);
@@ -267,89 +731,563 @@ impl Config {
         );
 
         let (result, tables) = {
-            println!("{} Executing...", style("[3/8]").bold().dim(),);
+            reporter.step("[3/8]", "Executing...");
 
             let loader = ZkWasmLoader::new(self.k, env)?;
             let runner = loader.compile(&module, &mut monitor)?;
             let result = loader.run(runner, &mut monitor)?;
 
-            println!("total guest instructions used {:?}", result.guest_statics);
-            println!("total host api used {:?}", result.host_statics);
+            reporter.execution_statics(result.guest_statics, &result.host_statics);
 
-            (result, monitor.into_tables())
+            (result, monitor.into_tables()?)
         };
 
         {
             if let Some(context_output_filename) = context_output_filename {
                 let context_output_path = output_dir.join(context_output_filename);
 
-                println!(
-                    "{} Write context output to file {:?}...",
-                    style("[4/8]").bold().dim(),
-                    context_output_path
+                reporter.step(
+                    "[4/8]",
+                    format!("Write context output to file {:?}...", context_output_path),
                 );
 
                 result
                     .context_outputs
                     .write(&mut File::create(&context_output_path)?)?;
+                reporter.set("context_output", &context_output_path);
             } else {
-                println!(
-                    "{} Context output is not specified. Skip writing context output...",
-                    style("[4/8]").bold().dim()
+                reporter.step(
+                    "[4/8]",
+                    "Context output is not specified. Skip writing context output...",
                 );
             }
+
+            write_output_channels(output_dir, &result.output_channels, reporter)?;
         }
 
+        let trace_dir = output_dir.join("traces");
+
         {
-            let dir = output_dir.join("traces");
+            reporter.step("[5/8]", format!("Writing traces to {:?}...", trace_dir));
+            tables.write(
+                &trace_dir,
+                self.trace_header(wasm_image)?,
+                |slice| name_of_etable_slice(&self.name, slice),
+                |slice| name_of_frame_table_slice(&self.name, slice),
+                name_of_external_host_call_table_slice,
+            )?;
 
-            println!(
-                "{} Writing traces to {:?}...",
-                style("[5/8]").bold().dim(),
-                dir
-            );
-            tables.write(&dir, |slice| name_of_frame_table_slice(&self.name, slice));
+            ResumeInfo {
+                slices: tables.execution_tables.etable.len(),
+                padding,
+                instances: result.public_inputs_and_outputs.clone(),
+            }
+            .write(&trace_dir.join(name_of_resume_info(&self.name)))?;
         }
 
-        println!("{} Build circuit(s)...", style("[6/8]").bold().dim(),);
+        reporter.step("[6/8]", "Build circuit(s)...");
         let instances = result
             .public_inputs_and_outputs
             .iter()
             .map(|v| (*v).into())
-            .collect::<Vec<_>>();
+            .collect::<Vec<Fr>>();
 
-        println!("{} Creating proof(s)...", style("[7/8]").bold().dim(),);
+        let total_slices = usize::max(tables.execution_tables.etable.len(), padding.unwrap_or(0));
 
         let mut proof_load_info =
             ProofGenerationInfo::new(&self.name, self.k as usize, HashType::Poseidon);
 
-        let progress_bar = ProgressBar::new(if let Some(padding) = padding {
-            usize::max(tables.execution_tables.etable.len(), padding) as u64
+        reporter.set("slices", total_slices);
+        if skip != 0 {
+            reporter.info(format!("skip first {} slice(s)", skip));
+        }
+
+        if let Some(workers) = workers {
+            reporter.step(
+                "[7/8]",
+                format!("Creating proof(s) with {} worker(s)...", workers),
+            );
+
+            // Materialize the state of every slice, so that each slice could be proved on its own.
+            Slices::<Fr>::new(self.k, tables, padding)?
+                .with_slice_state_writer(self.slice_state_writer(&trace_dir))
+                .write_slice_states()?;
+
+            let progress_bar = reporter.progress_bar(total_slices as u64);
+            progress_bar.inc(skip as u64);
+
+            self.prove_by_workers(
+                wasm_image,
+                params_dir,
+                output_dir,
+                (skip..total_slices).collect(),
+                workers,
+                mock_test,
+                &progress_bar,
+            )?;
+            progress_bar.finish_and_clear();
+
+            for index in skip..total_slices {
+                let proof_piece_info =
+                    self.proof_piece_info(index, index + 1 == total_slices, instances.len());
+
+                report_proof(reporter, output_dir, &proof_piece_info);
+                proof_load_info.append_single_proof(proof_piece_info);
+            }
         } else {
-            tables.execution_tables.etable.len() as u64
-        });
+            reporter.step("[7/8]", "Creating proof(s)...");
 
-        if skip != 0 {
+            let progress_bar = reporter.progress_bar(total_slices as u64);
             progress_bar.inc(skip as u64);
-            println!("skip first {} slice(s)", skip);
+
+            let slices = Slices::new(self.k, tables, padding)?
+                .with_slice_state_writer(self.slice_state_writer(&trace_dir))
+                .enumerate()
+                .skip(skip);
+
+            self.prove_slices(
+                slices,
+                total_slices,
+                &instances,
+                &params,
+                params_dir,
+                output_dir,
+                mock_test,
+                &function_names(&module),
+                &mut proof_load_info,
+                &progress_bar,
+                reporter,
+            )?;
+            progress_bar.finish_and_clear();
+        }
+
+        {
+            let proof_load_info_path = output_dir.join(name_of_loadinfo(&self.name));
+            reporter.step(
+                "[8/8]",
+                format!("Saving proof load info to {:?}...", proof_load_info_path),
+            );
+            save_proof_load_info(&proof_load_info, skip, &proof_load_info_path)?;
+            reporter.set("loadinfo", &proof_load_info_path);
+        }
+
+        Ok(())
+    }
+
+    /// Resume proving from the first slice without a proof.
+    ///
+    /// The image is not executed, slices are rebuilt from the traces and the slice states
+    /// persisted by the previous `prove`.
+    pub(crate) fn resume(
+        self,
+        wasm_image: &Path,
+        params_dir: &Path,
+        output_dir: &Path,
+        mock_test: bool,
+        reporter: &mut Reporter,
+    ) -> anyhow::Result<()> {
+        let trace_dir = output_dir.join("traces");
+
+        reporter.step("[1/5]", "Load image...");
+        let image_checksum = self.read_image_checksum(wasm_image)?;
+
+        reporter.step("[2/5]", "Load params...");
+        let params = self.read_params(params_dir)?;
+
+        reporter.step("[3/5]", format!("Loading traces from {:?}...", trace_dir));
+        let (tables, resume_info) = self.load_traces(&trace_dir, Some(image_checksum))?;
+
+        let total_slices = resume_info.total_slices();
+        let proved_slices = (0..total_slices)
+            .take_while(|index| {
+                output_dir
+                    .join(name_of_instance(&self.name, *index))
+                    .exists()
+                    && output_dir
+                        .join(name_of_transcript(&self.name, *index))
+                        .exists()
+            })
+            .count();
+
+        let instances = resume_info.instances::<Fr>();
+
+        let mut proof_load_info =
+            ProofGenerationInfo::new(&self.name, self.k as usize, HashType::Poseidon);
+        for index in 0..proved_slices {
+            let proof_piece_info =
+                self.proof_piece_info(index, index + 1 == total_slices, instances.len());
+
+            report_proof(reporter, output_dir, &proof_piece_info);
+            proof_load_info.append_single_proof(proof_piece_info);
+        }
+
+        reporter.step(
+            "[4/5]",
+            format!(
+                "Found {} proved slice(s), creating proof(s) from slice {}...",
+                proved_slices, proved_slices
+            ),
+        );
+
+        let progress_bar = reporter.progress_bar(total_slices as u64);
+        progress_bar.inc(proved_slices as u64);
+
+        let slices = Slices::new(self.k, tables, resume_info.padding)?
+            .with_slice_state_writer(self.slice_state_writer(&trace_dir))
+            .resume_from(
+                proved_slices,
+                self.read_slice_state(&trace_dir, proved_slices)?,
+            )?;
+
+        self.prove_slices(
+            (proved_slices..).zip(slices),
+            total_slices,
+            &instances,
+            &params,
+            params_dir,
+            output_dir,
+            mock_test,
+            &self.read_function_names(wasm_image, mock_test)?,
+            &mut proof_load_info,
+            &progress_bar,
+            reporter,
+        )?;
+        progress_bar.finish_and_clear();
+
+        {
+            let proof_load_info_path = output_dir.join(name_of_loadinfo(&self.name));
+            reporter.step(
+                "[5/5]",
+                format!("Saving proof load info to {:?}...", proof_load_info_path),
+            );
+            save_proof_load_info(&proof_load_info, 0, &proof_load_info_path)?;
+            reporter.set("loadinfo", &proof_load_info_path);
+        }
+
+        Ok(())
+    }
+
+    /// Prove all the slices from the traces in `trace_dir` which are written by `prove` on
+    /// another machine. Neither the image nor the host environment is required.
+    pub(crate) fn replay(
+        self,
+        params_dir: &Path,
+        trace_dir: &Path,
+        output_dir: &Path,
+        mock_test: bool,
+        reporter: &mut Reporter,
+    ) -> anyhow::Result<()> {
+        reporter.step("[1/4]", "Load params...");
+        let params = self.read_params(params_dir)?;
+
+        reporter.step("[2/4]", format!("Loading traces from {:?}...", trace_dir));
+        let (tables, resume_info) = self.load_traces(trace_dir, None)?;
+
+        let total_slices = resume_info.total_slices();
+        let instances = resume_info.instances::<Fr>();
+
+        reporter.set("slices", total_slices);
+        reporter.step("[3/4]", "Creating proof(s)...");
+
+        let mut proof_load_info =
+            ProofGenerationInfo::new(&self.name, self.k as usize, HashType::Poseidon);
+
+        let progress_bar = reporter.progress_bar(total_slices as u64);
+
+        self.prove_slices(
+            Slices::new(self.k, tables, resume_info.padding)?.enumerate(),
+            total_slices,
+            &instances,
+            &params,
+            params_dir,
+            output_dir,
+            mock_test,
+            // The image is not available to name the functions.
+            &HashMap::default(),
+            &mut proof_load_info,
+            &progress_bar,
+            reporter,
+        )?;
+        progress_bar.finish_and_clear();
+
+        {
+            let proof_load_info_path = output_dir.join(name_of_loadinfo(&self.name));
+            reporter.step(
+                "[4/4]",
+                format!("Saving proof load info to {:?}...", proof_load_info_path),
+            );
+            save_proof_load_info(&proof_load_info, 0, &proof_load_info_path)?;
+            reporter.set("loadinfo", &proof_load_info_path);
+        }
+
+        Ok(())
+    }
+
+    /// Summarize the executed instructions of all the slices in `trace_dir`, the event
+    /// tables are streamed from the files.
+    pub(crate) fn statistic(self, trace_dir: &Path, reporter: &mut Reporter) -> anyhow::Result<()> {
+        reporter.step("[1/2]", format!("Loading traces from {:?}...", trace_dir));
+        let (tables, _) = self.load_traces(trace_dir, None)?;
+
+        reporter.step("[2/2]", "Collecting instruction statistic...");
+        let statistic = tables.instruction_statistic()?;
+        reporter.instruction_statistic(&statistic);
+
+        Ok(())
+    }
+
+    /// Prove the slice `index` from the traces and slice states written by `prove --workers`.
+    ///
+    /// Only the proof data is written, the loadinfo is stitched by the `prove` process.
+    pub(crate) fn prove_slice(
+        self,
+        wasm_image: &Path,
+        params_dir: &Path,
+        output_dir: &Path,
+        index: usize,
+        mock_test: bool,
+        reporter: &mut Reporter,
+    ) -> anyhow::Result<()> {
+        let trace_dir = output_dir.join("traces");
+
+        let image_checksum = self.read_image_checksum(wasm_image)?;
+        let params = self.read_params(params_dir)?;
+        let (tables, resume_info) = self.load_traces(&trace_dir, Some(image_checksum))?;
+
+        let total_slices = resume_info.total_slices();
+        let instances = resume_info.instances::<Fr>();
+
+        let circuit = Slices::new(self.k, tables, resume_info.padding)?
+            .resume_from(index, self.read_slice_state(&trace_dir, index)?)?
+            .next()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Slice {} doesn't exist, there are only {} slice(s).",
+                    index,
+                    total_slices
+                )
+            })?;
+
+        let mut proof_load_info =
+            ProofGenerationInfo::new(&self.name, self.k as usize, HashType::Poseidon);
+
+        self.prove_slices(
+            std::iter::once((index, circuit)),
+            total_slices,
+            &instances,
+            &params,
+            params_dir,
+            output_dir,
+            mock_test,
+            &self.read_function_names(wasm_image, mock_test)?,
+            &mut proof_load_info,
+            &ProgressBar::hidden(),
+            reporter,
+        )
+    }
+
+    /// Rebuild the tables from the traces written by `prove`.
+    ///
+    /// The traces are checked against K of the circuit and `image_checksum`. If the checksum is
+    /// not given, the image used to setup the circuit is expected, any image is accepted if the
+    /// circuit is not bound to an image.
+    fn load_traces(
+        &self,
+        trace_dir: &Path,
+        image_checksum: Option<String>,
+    ) -> anyhow::Result<(Tables, ResumeInfo)> {
+        let resume_info = ResumeInfo::read(&trace_dir.join(name_of_resume_info(&self.name)))?;
+
+        let compilation_tables_path = trace_dir.join(COMPILATION_TABLES);
+        let compilation_tables_header =
+            read_header(&compilation_tables_path, TraceKind::CompilationTable)?;
+
+        let image_checksum = image_checksum
+            .or_else(|| self.wasm_image_sha256.clone())
+            .unwrap_or_else(|| compilation_tables_header.image_checksum.clone());
+        let expected = |slice| TraceHeader {
+            k: self.k,
+            image_checksum: image_checksum.clone(),
+            slice,
+        };
+
+        check_trace_header(
+            &compilation_tables_path,
+            &compilation_tables_header,
+            &expected(0),
+        )?;
+
+        for slice in 0..resume_info.slices {
+            let etable_path = trace_dir.join(name_of_etable_slice(&self.name, slice));
+            check_trace_header(
+                &etable_path,
+                &read_header(&etable_path, TraceKind::EventTable)?,
+                &expected(slice),
+            )?;
+
+            let frame_table_path = trace_dir.join(name_of_frame_table_slice(&self.name, slice));
+            check_trace_header(
+                &frame_table_path,
+                &read_header(&frame_table_path, TraceKind::FrameTable)?,
+                &expected(slice),
+            )?;
+        }
+
+        let tables = Tables::read(
+            trace_dir,
+            |slice| name_of_etable_slice(&self.name, slice),
+            |slice| name_of_frame_table_slice(&self.name, slice),
+            name_of_external_host_call_table_slice,
+        )?;
+
+        Ok((tables, resume_info))
+    }
+
+    // Read the state which the slice `index` starts from.
+    fn read_slice_state(
+        &self,
+        trace_dir: &Path,
+        index: usize,
+    ) -> anyhow::Result<Option<SliceState>> {
+        if index == 0 {
+            return Ok(None);
+        }
+
+        let path = trace_dir.join(name_of_slice_state(&self.name, index - 1));
+
+        if path.exists() {
+            Ok(Some(SliceState::read(&path)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn prove_by_workers(
+        &self,
+        wasm_image: &Path,
+        params_dir: &Path,
+        output_dir: &Path,
+        indexes: Vec<usize>,
+        workers: usize,
+        mock_test: bool,
+        progress_bar: &ProgressBar,
+    ) -> anyhow::Result<()> {
+        use std::process::Command;
+        use std::process::Stdio;
+        use std::sync::Mutex;
+
+        if workers == 0 {
+            anyhow::bail!("At least one worker is required to prove slices.");
         }
 
-        let mut slices = Slices::new(self.k, tables, padding)?
-            .enumerate()
-            .skip(skip)
-            .peekable();
-        while let Some((index, circuit)) = slices.next() {
+        let exe = std::env::current_exe()?;
+        let indexes = Mutex::new(indexes.into_iter());
+
+        let prove_slices = || -> anyhow::Result<()> {
+            loop {
+                let index = match indexes.lock().unwrap().next() {
+                    Some(index) => index,
+                    None => return Ok(()),
+                };
+
+                let mut command = Command::new(&exe);
+                command
+                    .arg(&self.name)
+                    .arg("--params")
+                    .arg(params_dir)
+                    .arg("prove-slice")
+                    .arg("--wasm")
+                    .arg(wasm_image)
+                    .arg("--output")
+                    .arg(output_dir)
+                    .arg("--index")
+                    .arg(index.to_string())
+                    .stdout(Stdio::null());
+                if mock_test {
+                    command.arg("--mock");
+                }
+
+                let status = command.status()?;
+                if !status.success() {
+                    anyhow::bail!("Worker proving slice {} failed with {}.", index, status);
+                }
+
+                progress_bar.inc(1);
+            }
+        };
+
+        std::thread::scope(|scope| {
+            let workers = (0..workers)
+                .map(|_| scope.spawn(prove_slices))
+                .collect::<Vec<_>>();
+
+            workers
+                .into_iter()
+                .try_for_each(|worker| worker.join().unwrap())
+        })?;
+
+        Ok(())
+    }
+
+    fn slice_state_writer(
+        &self,
+        trace_dir: &Path,
+    ) -> impl Fn(usize, &SliceState) -> Result<(), TraceError> + 'static {
+        let name = self.name.clone();
+        let trace_dir = trace_dir.to_path_buf();
+
+        move |index, state: &SliceState| {
+            state
+                .write(&trace_dir.join(name_of_slice_state(&name, index)))
+                .map_err(TraceError::Io)
+        }
+    }
+
+    fn proof_piece_info(
+        &self,
+        index: usize,
+        is_finalized_circuit: bool,
+        instance_size: usize,
+    ) -> ProofPieceInfo {
+        ProofPieceInfo {
+            circuit: name_of_circuit_data(&self.name, is_finalized_circuit),
+            instance_size: instance_size as u32,
+            witness: name_of_witness(&self.name, index),
+            instance: name_of_instance(&self.name, index),
+            transcript: name_of_transcript(&self.name, index),
+        }
+    }
+
+    fn prove_slices(
+        &self,
+        slices: impl Iterator<Item = (usize, Result<ZkWasmCircuit<Fr>, BuildingCircuitError>)>,
+        total_slices: usize,
+        instances: &[Fr],
+        params: &Params<G1Affine>,
+        params_dir: &Path,
+        output_dir: &Path,
+        mock_test: bool,
+        function_names: &HashMap<u32, String>,
+        proof_load_info: &mut ProofGenerationInfo,
+        progress_bar: &ProgressBar,
+        reporter: &mut Reporter,
+    ) -> anyhow::Result<()> {
+        let mut cached_proving_key = None;
+
+        for (index, circuit) in slices {
             let circuit = circuit?;
 
-            let _is_finalized_circuit = slices.peek().is_none();
+            let _is_finalized_circuit = index + 1 == total_slices;
 
             if mock_test {
-                println!("mock test for slice {}...", index);
-                circuit.mock_test(instances.clone())?;
+                reporter.info(format!("mock test for slice {}...", index));
+                circuit
+                    .mock_test(instances.to_vec())
+                    .map_err(|err| err.with_function_names(function_names))?;
             }
 
             let mut cached_proving_key_or_read =
-                |file_name: &str, is_last_circuit, expected_md5| -> anyhow::Result<()> {
+                |file_name: &str, is_last_circuit, expected_sha256| -> anyhow::Result<()> {
                     if let Some((name, _)) = cached_proving_key.as_ref() {
                         if name == file_name {
                             return Ok(());
@@ -359,9 +1297,9 @@ impl Config {
                     let pk = self
                         .read_circuit_data(
                             &params_dir.join(name_of_circuit_data(&self.name, is_last_circuit)),
-                            expected_md5,
+                            expected_sha256,
                         )?
-                        .into_proving_key(&params);
+                        .into_proving_key(params);
 
                     cached_proving_key = Some((file_name.to_string(), pk));
 
@@ -371,40 +1309,33 @@ impl Config {
             #[cfg(feature = "continuation")]
             if _is_finalized_circuit {
                 cached_proving_key_or_read(
-                    &self.circuit_datas.finalized_circuit.circuit_data_md5,
+                    &self.circuit_datas.finalized_circuit.circuit_data_sha256,
                     true,
-                    &self.circuit_datas.finalized_circuit.circuit_data_md5,
+                    &self.circuit_datas.finalized_circuit.circuit_data_sha256,
                 )?
             } else {
                 cached_proving_key_or_read(
-                    &self.circuit_datas.on_going_circuit.circuit_data_md5,
+                    &self.circuit_datas.on_going_circuit.circuit_data_sha256,
                     false,
-                    &self.circuit_datas.on_going_circuit.circuit_data_md5,
+                    &self.circuit_datas.on_going_circuit.circuit_data_sha256,
                 )?
             };
 
             #[cfg(not(feature = "continuation"))]
             cached_proving_key_or_read(
-                &self.circuit_datas.finalized_circuit.circuit_data_md5,
+                &self.circuit_datas.finalized_circuit.circuit_data_sha256,
                 true,
-                &self.circuit_datas.finalized_circuit.circuit_data_md5,
+                &self.circuit_datas.finalized_circuit.circuit_data_sha256,
             )?;
 
-            let circuit_data_name = name_of_circuit_data(&self.name, _is_finalized_circuit);
-
-            let proof_piece_info = ProofPieceInfo {
-                circuit: circuit_data_name,
-                instance_size: instances.len() as u32,
-                witness: name_of_witness(&self.name, index),
-                instance: name_of_instance(&self.name, index),
-                transcript: name_of_transcript(&self.name, index),
-            };
+            let proof_piece_info =
+                self.proof_piece_info(index, _is_finalized_circuit, instances.len());
 
             let proof = match circuit {
                 ZkWasmCircuit::Ongoing(circuit) => proof_piece_info.create_proof::<Bn256, _>(
                     &circuit,
-                    &vec![instances.clone()],
-                    &params,
+                    &vec![instances.to_vec()],
+                    params,
                     &cached_proving_key.as_ref().unwrap().1,
                     proof_load_info.hashtype,
                     OpenSchema::Shplonk,
@@ -412,42 +1343,40 @@ impl Config {
                 ZkWasmCircuit::LastSliceCircuit(circuit) => proof_piece_info
                     .create_proof::<Bn256, _>(
                         &circuit,
-                        &vec![instances.clone()],
-                        &params,
+                        &vec![instances.to_vec()],
+                        params,
                         &cached_proving_key.as_ref().unwrap().1,
                         proof_load_info.hashtype,
                         OpenSchema::Shplonk,
                     ),
+                // The cli doesn't run the image in the trap mode, so the slices never trap
+                // unless the traces are produced elsewhere.
+                ZkWasmCircuit::TrapSliceCircuit(_) => anyhow::bail!(
+                    "Slice {} is terminated by a trap, proving a trapped execution is not \
+                        supported by the cli.",
+                    index
+                ),
             };
 
-            proof_piece_info.save_proof_data(&vec![instances.clone()], &proof, output_dir);
+            proof_piece_info.save_proof_data(&vec![instances.to_vec()], &proof, output_dir);
 
+            report_proof(reporter, output_dir, &proof_piece_info);
             proof_load_info.append_single_proof(proof_piece_info);
 
             progress_bar.inc(1);
         }
-        progress_bar.finish_and_clear();
-
-        {
-            let proof_load_info_path = output_dir.join(name_of_loadinfo(&self.name));
-            println!(
-                "{} Saving proof load info to {:?}...",
-                style("[8/8]").bold().dim(),
-                proof_load_info_path
-            );
-            proof_load_info.save(proof_load_info_path.parent().unwrap());
-        }
 
         Ok(())
     }
 
-    pub(crate) fn verify(self, params_dir: &Path, output_dir: &PathBuf) -> anyhow::Result<()> {
+    pub(crate) fn verify(
+        self,
+        params_dir: &Path,
+        output_dir: &PathBuf,
+        reporter: &mut Reporter,
+    ) -> anyhow::Result<()> {
         let mut proofs = {
-            println!(
-                "{} Reading proofs from {:?}",
-                style("[1/2]").bold().dim(),
-                output_dir
-            );
+            reporter.step("[1/2]", format!("Reading proofs from {:?}", output_dir));
 
             let proof_load_info =
                 ProofGenerationInfo::load(&output_dir.join(&name_of_loadinfo(&self.name)));
@@ -459,14 +1388,26 @@ impl Config {
         }
         .into_iter()
         .peekable();
+        let first_slice =
+            read_first_slice_of_loadinfo(&output_dir.join(name_of_loadinfo(&self.name)))?;
 
-        println!(
-            "{} Found {} proofs, verifying..",
-            style("[2/2]").bold().dim(),
-            proofs.len()
+        reporter.step(
+            "[2/2]",
+            format!("Found {} proofs, verifying..", proofs.len()),
         );
+        reporter.set("proofs", proofs.len());
+
+        // The image table of the first slice is the image committed in setup, the image tables
+        // of the following slices are not checked if the first slice is not proved.
+        #[cfg(feature = "uniform-circuit")]
+        if first_slice == 0 {
+            if let Some(proof) = proofs.peek() {
+                self.image_checksum_check(proof)?;
+            }
+        }
 
-        let progress_bar = ProgressBar::new(proofs.len() as u64);
+        let progress_bar = reporter.progress_bar(proofs.len() as u64);
+        let mut index = first_slice;
         while let Some(proof) = proofs.next() {
             let params_verifier = {
                 let public_inputs_size = proof
@@ -486,68 +1427,252 @@ impl Config {
                 if proofs.peek().is_none() {
                     self.veryfying_key_consistent_check(
                         &buf,
-                        &self.circuit_datas.finalized_circuit.verifying_key_md5,
+                        &self.circuit_datas.finalized_circuit.verifying_key_sha256,
                     )?;
                 } else {
                     self.veryfying_key_consistent_check(
                         &buf,
-                        &self.circuit_datas.on_going_circuit.verifying_key_md5,
+                        &self.circuit_datas.on_going_circuit.verifying_key_sha256,
                     )?;
                 }
 
                 #[cfg(not(feature = "continuation"))]
                 self.veryfying_key_consistent_check(
                     &buf,
-                    &self.circuit_datas.finalized_circuit.verifying_key_md5,
+                    &self.circuit_datas.finalized_circuit.verifying_key_sha256,
                 )?;
             };
 
-            proof
-                .verify_proof(&params_verifier, OpenSchema::Shplonk)
-                .unwrap();
-
-            // TODO: handle checksum sanity check
-            // #[cfg(feature = "uniform-circuit")]
-            // {
-            //     use delphinus_zkwasm::circuits::image_table::IMAGE_COL_NAME;
-            //     use halo2_proofs::plonk::get_advice_commitments_from_transcript;
-            //     use halo2aggregator_s::transcript::poseidon::PoseidonRead;
-
-            //     let _img_col_idx = proof
-            //         .vkey
-            //         .cs
-            //         .named_advices
-            //         .iter()
-            //         .find(|(k, _)| k == IMAGE_COL_NAME)
-            //         .unwrap()
-            //         .1;
-            //     let _img_col_commitment: Vec<G1Affine> =
-            //         get_advice_commitments_from_transcript::<Bn256, _, _>(
-            //             &proof.vkey,
-            //             &mut PoseidonRead::init(&proof.transcripts[..]),
-            //         )
-            //         .unwrap();
-
-            //     assert!(
-            //         vec![_img_col_commitment[_img_col_idx as usize]][0]
-            //             .x
-            //             .to_string()
-            //             == self.checksum.0
-            //     );
-            //     assert!(
-            //         vec![_img_col_commitment[_img_col_idx as usize]][0]
-            //             .y
-            //             .to_string()
-            //             == self.checksum.1
-            //     );
-            // }
+            if let Err(err) = proof.verify_proof(&params_verifier, OpenSchema::Shplonk) {
+                reporter.set("verified", false);
+                anyhow::bail!("Proof {} is invalid: {:?}", index, err);
+            }
 
+            index += 1;
             progress_bar.inc(1);
         }
         progress_bar.finish_and_clear();
 
-        println!("{}", style("Verification succeeded!").green().bold().dim(),);
+        reporter.set("verified", true);
+        reporter.info(style("Verification succeeded!").green().bold().dim());
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use delphinus_zkwasm::circuits::config::MIN_K;
+    use delphinus_zkwasm::runtime::host::default_env::DefaultHostEnvBuilder;
+    use specs::etable::EventTable;
+    use specs::jtable::FrameTable;
+    use specs::TableBackend;
+
+    fn digest() -> CircuitDataDigest {
+        CircuitDataDigest {
+            circuit_data_sha256: sha256(b"circuit data"),
+            verifying_key_sha256: sha256(b"verifying key"),
+        }
+    }
+
+    fn config(host_config: Option<HostEnvConfig>) -> Config {
+        Config {
+            name: "test".to_string(),
+            params: PathBuf::from("K18.params"),
+
+            features: Features::current(),
+            k: 18,
+            host_mode: HostMode::Standard,
+            host_config,
+            phantom_functions: vec!["phantom".to_string()],
+            params_sha256: sha256(b"params"),
+            wasm_image_sha256: None,
+            #[cfg(feature = "continuation")]
+            circuit_datas: CircuitDataConfig {
+                on_going_circuit: digest(),
+                finalized_circuit: digest(),
+            },
+            #[cfg(not(feature = "continuation"))]
+            circuit_datas: CircuitDataConfig {
+                finalized_circuit: digest(),
+            },
+            checksum: ("x".to_string(), "y".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_config_round_trip() {
+        let dir = std::env::temp_dir().join("zkwasm-cli-test-config");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        for (name, host_config) in [("none", None), ("some", Some(HostEnvConfig::default()))] {
+            let config = config(host_config);
+            let path = dir.join(name);
+            config.write(&mut File::create(&path).unwrap()).unwrap();
+
+            let loaded = Config::read(&mut File::open(&path).unwrap()).unwrap();
+            assert_eq!(loaded.host_config.is_some(), config.host_config.is_some());
+            assert_eq!(loaded.phantom_functions, config.phantom_functions);
+            assert_eq!(loaded.manifest_digest(), config.manifest_digest());
+        }
+
+        assert_ne!(
+            config(None).manifest_digest(),
+            config(Some(HostEnvConfig::default())).manifest_digest()
+        );
+    }
+
+    #[test]
+    fn test_loadinfo_first_slice() {
+        let dir = std::env::temp_dir().join("zkwasm-cli-test-loadinfo");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let path = dir.join(name_of_loadinfo("test"));
+        let proof_load_info = ProofGenerationInfo::new("test", 18, HashType::Poseidon);
+
+        proof_load_info.save(&dir);
+        assert_eq!(read_first_slice_of_loadinfo(&path).unwrap(), 0);
+
+        save_proof_load_info(&proof_load_info, 2, &path).unwrap();
+        assert_eq!(read_first_slice_of_loadinfo(&path).unwrap(), 2);
+        // The index doesn't break the loadinfo for the batcher.
+        assert!(matches!(
+            ProofGenerationInfo::load(&path).hashtype,
+            HashType::Poseidon
+        ));
+    }
+
+    fn json(value: &impl Serialize) -> serde_json::Value {
+        serde_json::to_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_traces_round_trip() {
+        let textual_repr = r#"
+        (module
+            (import "env" "wasm_input" (func $wasm_input (param i32) (result i64)))
+            (import "env" "wasm_output" (func $wasm_output (param i64)))
+            (import "env" "wasm_dbg" (func $wasm_dbg (param i64)))
+            (import "env" "wasm_read_context" (func $wasm_read_context (result i64)))
+            (import "env" "wasm_write_context" (func $wasm_write_context (param i64)))
+            (func (export "zkmain")
+                (call $wasm_dbg (call $wasm_input (i32.const 1)))
+                (call $wasm_write_context (call $wasm_read_context))
+                (call $wasm_output (i64.const 3))
+            )
+        )
+        "#;
+
+        let dir = std::env::temp_dir().join("zkwasm-cli-test-traces");
+        let trace_dir = dir.join("traces");
+        std::fs::create_dir_all(&trace_dir).unwrap();
+
+        let wasm_image = dir.join("test.wasm");
+        std::fs::write(&wasm_image, wabt::wat2wasm(textual_repr).unwrap()).unwrap();
+
+        let config = Config {
+            k: MIN_K,
+            ..config(None)
+        };
+
+        let env_builder = DefaultHostEnvBuilder::new(config.k);
+        let env = env_builder.create_env(ExecutionArg {
+            public_inputs: vec![2],
+            private_inputs: vec![].into(),
+            context_inputs: vec![5],
+            indexed_witness: Default::default(),
+            tree_db: None,
+            output_channels: vec![],
+        });
+        let mut monitor = TableMonitor::new(
+            config.k,
+            env_builder.create_flush_strategy(),
+            &config.phantom_functions,
+            TraceBackend::Memory,
+            &env,
+        );
+
+        let module = config.read_wasm_image(&wasm_image).unwrap();
+        let loader = ZkWasmLoader::new(config.k, env).unwrap();
+        let runner = loader.compile(&module, &mut monitor).unwrap();
+        let result = loader.run(runner, &mut monitor).unwrap();
+        let tables = monitor.into_tables().unwrap();
+
+        tables
+            .write(
+                &trace_dir,
+                config.trace_header(&wasm_image).unwrap(),
+                |slice| name_of_etable_slice(&config.name, slice),
+                |slice| name_of_frame_table_slice(&config.name, slice),
+                name_of_external_host_call_table_slice,
+            )
+            .unwrap();
+        ResumeInfo {
+            slices: tables.execution_tables.etable.len(),
+            padding: None,
+            instances: result.public_inputs_and_outputs.clone(),
+        }
+        .write(&trace_dir.join(name_of_resume_info(&config.name)))
+        .unwrap();
+
+        let image_checksum = config.read_image_checksum(&wasm_image).unwrap();
+        let (loaded, resume_info) = config
+            .load_traces(&trace_dir, Some(image_checksum))
+            .unwrap();
+
+        assert_eq!(
+            json(&loaded.compilation_tables.itable),
+            json(&tables.compilation_tables.itable)
+        );
+
+        let (expected, loaded_tables) = (&tables.execution_tables, &loaded.execution_tables);
+        for (expected, loaded) in expected.etable.iter().zip(loaded_tables.etable.iter()) {
+            match (expected, loaded) {
+                (TableBackend::Memory(expected), TableBackend::Json(path)) => {
+                    assert_eq!(json(&EventTable::read(path).unwrap()), json(expected))
+                }
+                _ => panic!("the event tables should be loaded from the traces"),
+            }
+        }
+        for (expected, loaded) in expected
+            .frame_table
+            .iter()
+            .zip(loaded_tables.frame_table.iter())
+        {
+            match (expected, loaded) {
+                (TableBackend::Memory(expected), TableBackend::Json(path)) => {
+                    assert_eq!(json(&FrameTable::read(path).unwrap()), json(expected))
+                }
+                _ => panic!("the frame tables should be loaded from the traces"),
+            }
+        }
+
+        // `wasm_dbg` is an external host call.
+        assert!(!expected.external_host_call_table[0].entries().is_empty());
+        assert_eq!(
+            json(&loaded_tables.external_host_call_table),
+            json(&expected.external_host_call_table)
+        );
+        assert_eq!(expected.context_input_table, vec![5]);
+        assert_eq!(expected.context_output_table, vec![5]);
+        assert_eq!(
+            loaded_tables.context_input_table,
+            expected.context_input_table
+        );
+        assert_eq!(
+            loaded_tables.context_output_table,
+            expected.context_output_table
+        );
+
+        let instances = result.public_inputs_and_outputs::<Fr>();
+        assert_eq!(resume_info.instances::<Fr>(), instances);
+
+        // The slices rebuilt from the traces are accepted with the instances of the execution.
+        Slices::new(config.k, loaded, resume_info.padding)
+            .unwrap()
+            .mock_test_all(instances)
+            .unwrap();
+    }
+}
@@ -0,0 +1,207 @@
+use std::io::BufRead;
+use std::io::Write;
+
+use delphinus_zkwasm::debugger::Breakpoint;
+use delphinus_zkwasm::debugger::Debugger;
+
+const HELP: &str = r#"Commands:
+    n, next [N]                      Step forward N step(s)
+    p, prev [N]                      Step backward N step(s)
+    g, goto <EID>                    Move to the step EID
+    c, continue                      Step forward until a breakpoint is hit
+    rc, rcontinue                    Step backward until a breakpoint is hit
+    b, break func <NAME>             Break on the function NAME
+    b, break iid <IID>               Break on the instruction IID
+    b, break mem <ADDRESS>           Break on any access to the memory ADDRESS
+    bl, breakpoints                  List breakpoints
+    d, delete <INDEX>                Delete the breakpoint INDEX
+    i, info                          Show the current step and its memory events
+    s, stack                         Show the value stack, the top comes first
+    m, memory <ADDRESS> [N]          Show N 8-byte block(s) of memory from ADDRESS
+    bt, backtrace                    Show the call stack
+    h, help                          Show this message
+    q, quit                          Exit the debugger"#;
+
+fn parse_number(arg: Option<&str>) -> anyhow::Result<u32> {
+    let arg = arg.ok_or_else(|| anyhow::anyhow!("Missing argument."))?;
+
+    let number = match arg.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16)?,
+        None => arg.parse()?,
+    };
+
+    Ok(number)
+}
+
+// The optional count of a command, defaults to 1.
+fn parse_count(arg: Option<&str>) -> anyhow::Result<u32> {
+    arg.map_or(Ok(1), |arg| parse_number(Some(arg)))
+}
+
+fn function(debugger: &Debugger, fid: u32) -> String {
+    match debugger.function_name(fid) {
+        Some(name) => format!("{}({})", name, fid),
+        None => format!("func[{}]", fid),
+    }
+}
+
+fn print_current(debugger: &Debugger) {
+    match (debugger.current(), debugger.instruction()) {
+        (Some(entry), Some(instruction)) => println!(
+            "[{}/{}] eid {} at {}:{} {:?}",
+            debugger.position() + 1,
+            debugger.len(),
+            entry.eid,
+            function(debugger, entry.fid),
+            entry.iid,
+            instruction.opcode
+        ),
+        _ => println!("[{}/{}] finished", debugger.len(), debugger.len()),
+    }
+}
+
+fn execute(debugger: &mut Debugger, command: &str, args: &[&str]) -> anyhow::Result<()> {
+    match command {
+        "n" | "next" => {
+            for _ in 0..parse_count(args.first().copied())? {
+                if !debugger.step_forward() {
+                    break;
+                }
+            }
+            print_current(debugger);
+        }
+        "p" | "prev" => {
+            for _ in 0..parse_count(args.first().copied())? {
+                if !debugger.step_backward() {
+                    break;
+                }
+            }
+            print_current(debugger);
+        }
+        "g" | "goto" => {
+            let eid = parse_number(args.first().copied())?;
+            if !debugger.seek(eid) {
+                anyhow::bail!("Step {} doesn't exist.", eid);
+            }
+            print_current(debugger);
+        }
+        "c" | "continue" | "rc" | "rcontinue" => {
+            let hit = if command.starts_with('r') {
+                debugger.continue_backward().cloned()
+            } else {
+                debugger.continue_forward().cloned()
+            };
+            if let Some(breakpoint) = hit {
+                println!("Hit breakpoint: {}", breakpoint);
+            }
+            print_current(debugger);
+        }
+        "b" | "break" => {
+            let breakpoint = match args.first().copied() {
+                Some("func") => Breakpoint::Function(
+                    args.get(1)
+                        .ok_or_else(|| anyhow::anyhow!("Missing function name."))?
+                        .to_string(),
+                ),
+                Some("iid") => Breakpoint::Iid(parse_number(args.get(1).copied())?),
+                Some("mem") => Breakpoint::Memory(parse_number(args.get(1).copied())?),
+                _ => anyhow::bail!("Usage: break func <NAME> | iid <IID> | mem <ADDRESS>"),
+            };
+            println!(
+                "Breakpoint {}: {}",
+                debugger.breakpoints().len(),
+                breakpoint
+            );
+            debugger.add_breakpoint(breakpoint);
+        }
+        "bl" | "breakpoints" => {
+            for (index, breakpoint) in debugger.breakpoints().iter().enumerate() {
+                println!("{}: {}", index, breakpoint);
+            }
+        }
+        "d" | "delete" => {
+            let index = parse_number(args.first().copied())?;
+            match debugger.remove_breakpoint(index as usize) {
+                Some(breakpoint) => println!("Deleted breakpoint {}: {}", index, breakpoint),
+                None => anyhow::bail!("Breakpoint {} doesn't exist.", index),
+            }
+        }
+        "i" | "info" => {
+            print_current(debugger);
+            if let Some(entry) = debugger.current() {
+                println!("sp: {}", entry.sp);
+                println!("allocated memory pages: {}", entry.allocated_memory_pages);
+                println!("step info: {:?}", entry.step_info);
+            }
+            for event in debugger.memory_events() {
+                println!(
+                    "  {:?} {:?} {:?}[{}] = {:#x}",
+                    event.atype, event.vtype, event.ltype, event.offset, event.value
+                );
+            }
+        }
+        "s" | "stack" => {
+            for (depth, value) in debugger.value_stack().iter().enumerate() {
+                println!("{:>4}: {:#018x}", depth, value);
+            }
+        }
+        "m" | "memory" => {
+            let address = parse_number(args.first().copied())? / 8 * 8;
+            let blocks = parse_count(args.get(1).copied())?;
+            for block in 0..blocks {
+                let address = address + block * 8;
+                match debugger.memory(address) {
+                    Some(value) => println!("{:#010x}: {:#018x}", address, value),
+                    None => println!("{:#010x}: <unknown>", address),
+                }
+            }
+        }
+        "bt" | "backtrace" => {
+            for (depth, frame) in debugger.call_stack().iter().enumerate() {
+                println!("#{} {}:{}", depth, function(debugger, frame.fid), frame.iid);
+            }
+        }
+        "h" | "help" => println!("{}", HELP),
+        _ => anyhow::bail!("Unknown command {}, type `help` for commands.", command),
+    }
+
+    Ok(())
+}
+
+/// Read commands from stdin until `quit` or EOF.
+pub(crate) fn run_debugger(mut debugger: Debugger) -> anyhow::Result<()> {
+    println!(
+        "{} step(s) loaded, type `help` for commands.",
+        debugger.len()
+    );
+    print_current(&debugger);
+
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    loop {
+        print!("(zkwasm-dbg) ");
+        std::io::stdout().flush()?;
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
+
+        let mut words = line.split_whitespace();
+        let command = match words.next() {
+            Some(command) => command,
+            None => continue,
+        };
+        if command == "q" || command == "quit" {
+            break;
+        }
+
+        let args = words.collect::<Vec<_>>();
+        if let Err(err) = execute(&mut debugger, command, &args) {
+            println!("{}", err);
+        }
+    }
+
+    Ok(())
+}
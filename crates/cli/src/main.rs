@@ -2,7 +2,6 @@
 #![allow(clippy::too_many_arguments, clippy::while_let_on_iterator)]
 
 use std::cell::RefCell;
-use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use std::rc::Rc;
@@ -10,26 +9,30 @@ use std::rc::Rc;
 use anyhow::Result;
 use app_builder::app;
 use command::Subcommands;
-use delphinus_host::StandardHostEnvBuilder;
-use delphinus_zkwasm::runtime::host::default_env::DefaultHostEnvBuilder;
+use delphinus_host::host::merkle_helper::local_db::open_local_tree_db;
 use delphinus_zkwasm::runtime::host::default_env::ExecutionArg;
 
-use args::HostMode;
+use args::open_private_inputs;
+use args::read_indexed_witness;
+use args::read_inputs;
+use args::read_output_channels;
 use config::Config;
-use delphinus_zkwasm::runtime::host::HostEnvBuilder;
 use names::name_of_config;
 use names::name_of_etable_slice;
 use names::name_of_frame_table_slice;
-use specs::args::parse_args;
+use report::Reporter;
 use specs::etable::EventTable;
 use specs::jtable::FrameTable;
+use specs::trace::TraceError;
 use specs::TraceBackend;
 
 mod app_builder;
 mod args;
 mod command;
 mod config;
+mod debugger;
 mod names;
+mod report;
 
 const TRIVIAL_WASM: &str = r#"
 (module
@@ -41,6 +44,7 @@ const TRIVIAL_WASM: &str = r#"
 struct ZkWasmCli {
     name: String,
     params_dir: PathBuf,
+    json: bool,
     subcommand: Subcommands,
 }
 
@@ -54,28 +58,34 @@ fn main() -> Result<()> {
 
     let cli: ZkWasmCli = app.get_matches().into();
 
+    let mut reporter = Reporter::new(cli.json);
+    let result = run(cli, &mut reporter);
+    reporter.finish(&result);
+
+    result
+}
+
+fn run(cli: ZkWasmCli, reporter: &mut Reporter) -> Result<()> {
     match cli.subcommand {
         Subcommands::Setup(arg) => {
-            let env_builder: Box<dyn HostEnvBuilder> = match arg.host_mode {
-                HostMode::Default => Box::new(DefaultHostEnvBuilder::new(arg.k)),
-                HostMode::Standard => Box::new(StandardHostEnvBuilder::new(arg.k)),
-            };
-
-            arg.setup(&*env_builder, &cli.name, &cli.params_dir)?;
+            arg.setup(&cli.name, &cli.params_dir, reporter)?;
         }
         Subcommands::DryRun(arg) => {
             let config = Config::read(&mut fs::File::open(
                 cli.params_dir.join(name_of_config(&cli.name)),
             )?)?;
 
-            let public_inputs = parse_args(&arg.running_arg.public_inputs);
-            let private_inputs = parse_args(&arg.running_arg.private_inputs);
-            let context_inputs = parse_args(&arg.running_arg.context_inputs);
+            let (public_inputs, private_inputs, context_inputs) = read_inputs(
+                arg.running_arg.input_manifest.as_deref(),
+                &arg.running_arg.public_inputs,
+                &arg.running_arg.private_inputs,
+                &arg.running_arg.context_inputs,
+            )?;
+            let indexed_witness = Rc::new(RefCell::new(read_indexed_witness(
+                &arg.running_arg.witness,
+            )?));
 
-            let env_builder: Box<dyn HostEnvBuilder> = match config.host_mode {
-                HostMode::Default => Box::new(DefaultHostEnvBuilder::new(config.k)),
-                HostMode::Standard => Box::new(StandardHostEnvBuilder::new(config.k)),
-            };
+            let env_builder = config.env_builder();
 
             config.dry_run(
                 &*env_builder,
@@ -83,13 +93,59 @@ fn main() -> Result<()> {
                 &arg.running_arg.output_dir,
                 ExecutionArg {
                     public_inputs,
-                    private_inputs,
+                    private_inputs: open_private_inputs(
+                        private_inputs,
+                        arg.running_arg.private_stream.as_deref(),
+                    )?,
                     context_inputs,
-                    indexed_witness: Rc::new(RefCell::new(HashMap::default())),
-                    tree_db: None,
+                    indexed_witness: indexed_witness.clone(),
+                    tree_db: arg
+                        .running_arg
+                        .tree_db
+                        .as_deref()
+                        .map(open_local_tree_db)
+                        .transpose()?,
+                    output_channels: read_output_channels(
+                        arg.running_arg.output_channels.as_deref(),
+                    )?,
                 },
                 arg.running_arg.context_output,
                 arg.instruction_limit,
+                arg.profile,
+                reporter,
+            )?;
+
+            reporter.unconsumed_witness(&indexed_witness.borrow());
+        }
+        Subcommands::Debug(arg) => {
+            let config = Config::read(&mut fs::File::open(
+                cli.params_dir.join(name_of_config(&cli.name)),
+            )?)?;
+
+            let (public_inputs, private_inputs, context_inputs) = read_inputs(
+                arg.input_manifest.as_deref(),
+                &arg.public_inputs,
+                &arg.private_inputs,
+                &arg.context_inputs,
+            )?;
+
+            let env_builder = config.env_builder();
+
+            config.debug(
+                &*env_builder,
+                &arg.wasm_image,
+                ExecutionArg {
+                    public_inputs,
+                    private_inputs: open_private_inputs(
+                        private_inputs,
+                        arg.private_stream.as_deref(),
+                    )?,
+                    context_inputs,
+                    indexed_witness: Rc::new(RefCell::new(read_indexed_witness(&arg.witness)?)),
+                    tree_db: arg.tree_db.as_deref().map(open_local_tree_db).transpose()?,
+                    output_channels: read_output_channels(arg.output_channels.as_deref())?,
+                },
+                arg.trace_dir.as_deref(),
             )?;
         }
         Subcommands::Prove(arg) => {
@@ -100,39 +156,63 @@ fn main() -> Result<()> {
                 cli.params_dir.join(name_of_config(&cli.name)),
             )?)?;
 
-            let public_inputs = parse_args(&arg.running_arg.public_inputs);
-            let private_inputs = parse_args(&arg.running_arg.private_inputs);
-            let context_inputs = parse_args(&arg.running_arg.context_inputs);
+            let env_builder = config.env_builder();
+
+            if arg.resume {
+                config.resume(
+                    &arg.wasm_image,
+                    &cli.params_dir,
+                    &arg.output_dir,
+                    arg.mock_test,
+                    reporter,
+                )?;
+
+                return Ok(());
+            }
+
+            let (public_inputs, private_inputs, context_inputs) = read_inputs(
+                arg.running_arg.input_manifest.as_deref(),
+                &arg.running_arg.public_inputs,
+                &arg.running_arg.private_inputs,
+                &arg.running_arg.context_inputs,
+            )?;
 
             let trace_backend: TraceBackend = if arg.file_backend {
+                let trace_header = config.trace_header(&arg.wasm_image)?;
+
                 let event_table_writer = {
                     let name = cli.name.clone();
                     let trace_dir = trace_dir.clone();
+                    let trace_header = trace_header.clone();
 
-                    Box::new(move |slice, etable: &EventTable| {
-                        let filename_of_etable_slice =
-                            PathBuf::from(name_of_etable_slice(&name, slice));
-                        let path = trace_dir.join(filename_of_etable_slice);
+                    Box::new(
+                        move |slice, etable: &EventTable| -> Result<PathBuf, TraceError> {
+                            let filename_of_etable_slice =
+                                PathBuf::from(name_of_etable_slice(&name, slice));
+                            let path = trace_dir.join(filename_of_etable_slice);
 
-                        etable.write(&path).unwrap();
+                            etable.write(&path, &trace_header(slice))?;
 
-                        path
-                    })
+                            Ok(path)
+                        },
+                    )
                 };
 
                 let frame_table_writer = {
                     let name = cli.name.clone();
                     let trace_dir = trace_dir;
 
-                    Box::new(move |slice, frame_table: &FrameTable| {
-                        let filename_of_frame_table_slice =
-                            PathBuf::from(name_of_frame_table_slice(&name, slice));
-                        let path = trace_dir.join(filename_of_frame_table_slice);
+                    Box::new(
+                        move |slice, frame_table: &FrameTable| -> Result<PathBuf, TraceError> {
+                            let filename_of_frame_table_slice =
+                                PathBuf::from(name_of_frame_table_slice(&name, slice));
+                            let path = trace_dir.join(filename_of_frame_table_slice);
 
-                        frame_table.write(&path).unwrap();
+                            frame_table.write(&path, &trace_header(slice))?;
 
-                        path
-                    })
+                            Ok(path)
+                        },
+                    )
                 };
 
                 TraceBackend::File {
@@ -143,11 +223,6 @@ fn main() -> Result<()> {
                 TraceBackend::Memory
             };
 
-            let env_builder: Box<dyn HostEnvBuilder> = match config.host_mode {
-                HostMode::Default => Box::new(DefaultHostEnvBuilder::new(config.k)),
-                HostMode::Standard => Box::new(StandardHostEnvBuilder::new(config.k)),
-            };
-
             config.prove(
                 &*env_builder,
                 &arg.wasm_image,
@@ -155,24 +230,84 @@ fn main() -> Result<()> {
                 &arg.output_dir,
                 ExecutionArg {
                     public_inputs,
-                    private_inputs,
+                    private_inputs: open_private_inputs(
+                        private_inputs,
+                        arg.running_arg.private_stream.as_deref(),
+                    )?,
                     context_inputs,
-                    indexed_witness: Rc::new(RefCell::new(HashMap::default())),
-                    tree_db: None,
+                    indexed_witness: Rc::new(RefCell::new(read_indexed_witness(
+                        &arg.running_arg.witness,
+                    )?)),
+                    tree_db: arg
+                        .running_arg
+                        .tree_db
+                        .as_deref()
+                        .map(open_local_tree_db)
+                        .transpose()?,
+                    output_channels: read_output_channels(
+                        arg.running_arg.output_channels.as_deref(),
+                    )?,
                 },
                 arg.running_arg.context_output,
                 arg.mock_test,
                 trace_backend,
                 arg.skip,
                 arg.padding,
+                arg.workers,
+                reporter,
             )?;
         }
+        Subcommands::ProveSlice(arg) => {
+            let config = Config::read(&mut fs::File::open(
+                cli.params_dir.join(name_of_config(&cli.name)),
+            )?)?;
+
+            config.prove_slice(
+                &arg.wasm_image,
+                &cli.params_dir,
+                &arg.output_dir,
+                arg.index,
+                arg.mock_test,
+                reporter,
+            )?;
+        }
+        Subcommands::Replay(arg) => {
+            fs::create_dir_all(&arg.output_dir)?;
+
+            let config = Config::read(&mut fs::File::open(
+                cli.params_dir.join(name_of_config(&cli.name)),
+            )?)?;
+
+            config.replay(
+                &cli.params_dir,
+                &arg.trace_dir,
+                &arg.output_dir,
+                arg.mock_test,
+                reporter,
+            )?;
+        }
+        Subcommands::Statistic(arg) => {
+            let config = Config::read(&mut fs::File::open(
+                cli.params_dir.join(name_of_config(&cli.name)),
+            )?)?;
+
+            config.statistic(&arg.trace_dir, reporter)?;
+        }
         Subcommands::Verify(arg) => {
             let config = Config::read(&mut fs::File::open(
                 cli.params_dir.join(name_of_config(&cli.name)),
             )?)?;
 
-            config.verify(&cli.params_dir, &arg.output_dir)?;
+            config.verify(&cli.params_dir, &arg.output_dir, reporter)?;
+        }
+        Subcommands::Inspect(arg) => {
+            arg.inspect(&cli.name, &cli.params_dir, reporter)?;
+        }
+        Subcommands::Estimate(arg) => {
+            arg.estimate(&cli.name, &cli.params_dir, reporter)?;
+        }
+        Subcommands::Migrate(arg) => {
+            arg.migrate(&cli.name, &cli.params_dir, reporter)?;
         }
     }
 
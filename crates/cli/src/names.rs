@@ -47,3 +47,23 @@ pub(crate) fn name_of_etable_slice(name: &str, index: usize) -> String {
 pub(crate) fn name_of_frame_table_slice(name: &str, index: usize) -> String {
     format!("{}.frame_table.{}.data", name, index)
 }
+
+#[inline(always)]
+pub(crate) fn name_of_external_host_call_table_slice(index: usize) -> String {
+    format!("external_host_table.{}.json", index)
+}
+
+#[inline(always)]
+pub(crate) fn name_of_slice_state(name: &str, index: usize) -> String {
+    format!("{}.slice_state.{}.data", name, index)
+}
+
+#[inline(always)]
+pub(crate) fn name_of_resume_info(name: &str) -> String {
+    format!("{}.resume.json", name)
+}
+
+#[inline(always)]
+pub(crate) fn name_of_profile(name: &str) -> String {
+    format!("{}.profile.folded", name)
+}
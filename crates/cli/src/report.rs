@@ -0,0 +1,247 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::time::Instant;
+
+use console::style;
+use delphinus_zkwasm::profile::instruction_statistic::InstructionStatistic;
+use delphinus_zkwasm::runtime::host::ForeignStatics;
+use delphinus_zkwasm::runtime::monitor::plugins::profiler::FunctionProfile;
+use indicatif::ProgressBar;
+use serde::Serialize;
+use serde_json::Map;
+use serde_json::Value;
+
+#[derive(Serialize)]
+struct StepReport {
+    step: String,
+    message: String,
+    elapsed_ms: u64,
+}
+
+/// Output of a command.
+///
+/// Messages are printed for human by default. With `--json`, nothing is printed until the
+/// command finishes, then a single JSON document with the timing of each step and the values
+/// recorded by `set` is emitted.
+pub(crate) struct Reporter {
+    json: bool,
+    start: Instant,
+    steps: Vec<StepReport>,
+    current_step: Option<(StepReport, Instant)>,
+    values: Map<String, Value>,
+}
+
+impl Reporter {
+    pub(crate) fn new(json: bool) -> Self {
+        Reporter {
+            json,
+            start: Instant::now(),
+            steps: vec![],
+            current_step: None,
+            values: Map::new(),
+        }
+    }
+
+    fn finish_step(&mut self) {
+        if let Some((mut step, start)) = self.current_step.take() {
+            step.elapsed_ms = start.elapsed().as_millis() as u64;
+            self.steps.push(step);
+        }
+    }
+
+    /// Start a new step, e.g. `step("[1/8]", "Load image...")`, the previous step is finished.
+    pub(crate) fn step(&mut self, marker: &str, message: impl Display) {
+        self.finish_step();
+
+        let message = message.to_string();
+
+        if !self.json {
+            println!("{} {}", style(marker).bold().dim(), message);
+        }
+
+        self.current_step = Some((
+            StepReport {
+                step: marker.trim_matches(|c| c == '[' || c == ']').to_string(),
+                message: message.trim_end_matches('.').to_string(),
+                elapsed_ms: 0,
+            },
+            Instant::now(),
+        ));
+    }
+
+    /// Print a message which is not part of the JSON document.
+    pub(crate) fn info(&self, message: impl Display) {
+        if !self.json {
+            println!("{}", message);
+        }
+    }
+
+    /// Record a value of the JSON document.
+    pub(crate) fn set(&mut self, key: &str, value: impl Serialize) {
+        if self.json {
+            self.values.insert(
+                key.to_string(),
+                serde_json::to_value(value).unwrap_or(Value::Null),
+            );
+        }
+    }
+
+    /// Append a value to the array `key` of the JSON document.
+    pub(crate) fn push(&mut self, key: &str, value: impl Serialize) {
+        if self.json {
+            let value = serde_json::to_value(value).unwrap_or(Value::Null);
+
+            match self
+                .values
+                .entry(key.to_string())
+                .or_insert_with(|| Value::Array(vec![]))
+            {
+                Value::Array(values) => values.push(value),
+                entry => *entry = Value::Array(vec![entry.take(), value]),
+            }
+        }
+    }
+
+    pub(crate) fn execution_statics(
+        &mut self,
+        guest_statics: usize,
+        host_statics: &HashMap<String, ForeignStatics>,
+    ) {
+        self.info(format!("total guest instructions used {:?}", guest_statics));
+        self.info(format!("total host api used {:?}", host_statics));
+
+        self.set("guest_instructions", guest_statics);
+        self.set("host_statics", host_statics);
+    }
+
+    /// Report the indexed witness left in the buffers after the execution.
+    pub(crate) fn unconsumed_witness(&mut self, indexed_witness: &HashMap<u64, Vec<u64>>) {
+        let unconsumed = indexed_witness
+            .iter()
+            .filter(|(_, witness)| !witness.is_empty())
+            .map(|(index, witness)| (*index, witness.len()))
+            .collect::<BTreeMap<_, _>>();
+
+        for (index, len) in &unconsumed {
+            self.info(format!(
+                "{} {} unconsumed entries in witness {}",
+                style("warning:").yellow().bold(),
+                len,
+                index
+            ));
+        }
+
+        self.set("unconsumed_witness", unconsumed);
+    }
+
+    /// Print the most expensive functions, all the functions are recorded.
+    pub(crate) fn profile(&mut self, functions: &[FunctionProfile]) {
+        const TOP_FUNCTIONS: usize = 20;
+
+        self.info(format!(
+            "{:>12} {:>12} {:>12} {:>12} {:>10} {:>8}  function",
+            "self instrs", "total instrs", "self mops", "total mops", "host calls", "calls"
+        ));
+        for function in functions.iter().take(TOP_FUNCTIONS) {
+            self.info(format!(
+                "{:>12} {:>12} {:>12} {:>12} {:>10} {:>8}  {}",
+                function.exclusive.instructions,
+                function.inclusive.instructions,
+                function.exclusive.memory_ops,
+                function.inclusive.memory_ops,
+                function.exclusive.host_calls,
+                function.calls,
+                function
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| format!("func[{}]", function.fid))
+            ));
+        }
+        if functions.len() > TOP_FUNCTIONS {
+            self.info(format!(
+                "... {} more function(s)",
+                functions.len() - TOP_FUNCTIONS
+            ));
+        }
+
+        self.set("functions", functions);
+    }
+
+    /// Print the statistic of each opcode class, ordered by the opcode class.
+    pub(crate) fn instruction_statistic(&mut self, statistic: &InstructionStatistic) {
+        self.info(format!(
+            "{:>12} {:>12} {:>10} {:>10} {:>10} {:>12}  opcode class",
+            "count", "mentries", "reads", "writes", "jops", "rows"
+        ));
+        for (opcode_class, opcode) in statistic.opcodes.iter() {
+            self.info(format!(
+                "{:>12} {:>12} {:>10} {:>10} {:>10} {:>12}  {:?}",
+                opcode.count,
+                opcode.memory_entries,
+                opcode.reads,
+                opcode.writes,
+                opcode.jops,
+                opcode.estimated_rows,
+                opcode_class
+            ));
+        }
+
+        let total = statistic.total();
+        self.info(format!(
+            "{:>12} {:>12} {:>10} {:>10} {:>10} {:>12}  total",
+            total.count,
+            total.memory_entries,
+            total.reads,
+            total.writes,
+            total.jops,
+            total.estimated_rows
+        ));
+
+        self.set(
+            "instructions",
+            statistic
+                .opcodes
+                .iter()
+                .map(|(opcode_class, opcode)| (format!("{:?}", opcode_class), opcode))
+                .collect::<BTreeMap<_, _>>(),
+        );
+        self.set("total", total);
+    }
+
+    pub(crate) fn progress_bar(&self, len: u64) -> ProgressBar {
+        if self.json {
+            ProgressBar::hidden()
+        } else {
+            ProgressBar::new(len)
+        }
+    }
+
+    /// Finish the command, the JSON document is emitted to stdout with `--json`.
+    pub(crate) fn finish(mut self, result: &anyhow::Result<()>) {
+        self.finish_step();
+
+        if self.json {
+            let mut document = Map::new();
+
+            document.insert("success".to_string(), Value::Bool(result.is_ok()));
+            if let Err(err) = result {
+                document.insert("error".to_string(), Value::String(format!("{:?}", err)));
+            }
+            document.insert(
+                "elapsed_ms".to_string(),
+                Value::from(self.start.elapsed().as_millis() as u64),
+            );
+            document.insert(
+                "steps".to_string(),
+                serde_json::to_value(&self.steps).unwrap(),
+            );
+            document.extend(self.values);
+
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&Value::Object(document)).unwrap()
+            );
+        }
+    }
+}
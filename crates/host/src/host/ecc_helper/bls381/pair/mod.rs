@@ -85,7 +85,7 @@ pub fn register_blspair_foreign(env: &mut HostEnv) {
                     context.limbs.push(args.nth(0));
                     context.input_cursor += 1;
                 }
-                None
+                Ok(None)
             },
         ),
     );
@@ -123,7 +123,7 @@ pub fn register_blspair_foreign(env: &mut HostEnv) {
                     context.limbs.push(args.nth(0));
                     context.input_cursor += 1;
                 };
-                None
+                Ok(None)
             },
         ),
     );
@@ -140,7 +140,7 @@ pub fn register_blspair_foreign(env: &mut HostEnv) {
                     context.result_limbs[context.result_cursor] as i64,
                 ));
                 context.result_cursor += 1;
-                ret
+                Ok(ret)
             },
         ),
     );
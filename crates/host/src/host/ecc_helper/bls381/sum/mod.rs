@@ -67,7 +67,7 @@ pub fn register_blssum_foreign(env: &mut HostEnv) {
                     context.limbs.push(args.nth(0));
                     context.input_cursor += 1;
                 }
-                None
+                Ok(None)
             },
         ),
     );
@@ -100,7 +100,7 @@ pub fn register_blssum_foreign(env: &mut HostEnv) {
                     limbs[context.result_cursor] as i64,
                 ));
                 context.result_cursor += 1;
-                ret
+                Ok(ret)
             },
         ),
     );
@@ -92,7 +92,7 @@ pub fn register_bn254pair_foreign(env: &mut HostEnv) {
                     context.limbs.push(args.nth(0));
                     context.input_cursor += 1;
                 }
-                None
+                Ok(None)
             },
         ),
     );
@@ -136,7 +136,7 @@ pub fn register_bn254pair_foreign(env: &mut HostEnv) {
                     context.limbs.push(args.nth(0));
                     context.input_cursor += 1;
                 };
-                None
+                Ok(None)
             },
         ),
     );
@@ -158,7 +158,7 @@ pub fn register_bn254pair_foreign(env: &mut HostEnv) {
                     context.result_limbs[context.result_cursor] as i64,
                 ));
                 context.result_cursor += 1;
-                ret
+                Ok(ret)
             },
         ),
     );
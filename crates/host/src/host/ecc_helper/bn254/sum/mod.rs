@@ -105,7 +105,7 @@ pub fn register_bn254sum_foreign(env: &mut HostEnv) {
             |_obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<BN254SumContext>().unwrap();
                 context.bn254_sum_new(args.nth::<u64>(0) as usize);
-                None
+                Ok(None)
             },
         ),
     );
@@ -119,7 +119,7 @@ pub fn register_bn254sum_foreign(env: &mut HostEnv) {
             |_obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<BN254SumContext>().unwrap();
                 context.bn254_sum_push_scalar(args.nth::<u64>(0));
-                None
+                Ok(None)
             },
         ),
     );
@@ -133,7 +133,7 @@ pub fn register_bn254sum_foreign(env: &mut HostEnv) {
             |_obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<BN254SumContext>().unwrap();
                 context.bn254_sum_push_limb(args.nth::<u64>(0));
-                None
+                Ok(None)
             },
         ),
     );
@@ -165,7 +165,7 @@ pub fn register_bn254sum_foreign(env: &mut HostEnv) {
                     limbs[context.result_cursor] as i64,
                 ));
                 context.result_cursor += 1;
-                ret
+                Ok(ret)
             },
         ),
     );
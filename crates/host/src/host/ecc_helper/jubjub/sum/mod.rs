@@ -134,7 +134,7 @@ pub fn register_babyjubjubsum_foreign(env: &mut HostEnv) {
             |_obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<BabyJubjubSumContext>().unwrap();
                 context.babyjubjub_sum_new(args.nth::<u64>(0) as usize);
-                None
+                Ok(None)
             },
         ),
     );
@@ -148,7 +148,7 @@ pub fn register_babyjubjubsum_foreign(env: &mut HostEnv) {
             |_obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<BabyJubjubSumContext>().unwrap();
                 context.babyjubjub_sum_push(args.nth(0));
-                None
+                Ok(None)
             },
         ),
     );
@@ -162,9 +162,9 @@ pub fn register_babyjubjubsum_foreign(env: &mut HostEnv) {
             |_obs, context: &mut dyn ForeignContext, _args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<BabyJubjubSumContext>().unwrap();
 
-                Some(wasmi::RuntimeValue::I64(
-                    context.babyjubjub_sum_finalize() as i64
-                ))
+                Ok(Some(wasmi::RuntimeValue::I64(
+                    context.babyjubjub_sum_finalize() as i64,
+                )))
             },
         ),
     );
@@ -85,7 +85,7 @@ pub fn register_keccak_foreign(env: &mut HostEnv) {
                 let context = context.downcast_mut::<Keccak256Context>().unwrap();
                 log::debug!("buf len is {}", context.buf.len());
                 context.keccak_new(args.nth::<u64>(0) as usize);
-                None
+                Ok(None)
             },
         ),
     );
@@ -99,7 +99,7 @@ pub fn register_keccak_foreign(env: &mut HostEnv) {
             |_obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<Keccak256Context>().unwrap();
                 context.keccak_push(args.nth::<u64>(0));
-                None
+                Ok(None)
             },
         ),
     );
@@ -112,7 +112,9 @@ pub fn register_keccak_foreign(env: &mut HostEnv) {
         Rc::new(
             |_obs, context: &mut dyn ForeignContext, _args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<Keccak256Context>().unwrap();
-                Some(wasmi::RuntimeValue::I64(context.keccak_finalize() as i64))
+                Ok(Some(wasmi::RuntimeValue::I64(
+                    context.keccak_finalize() as i64
+                )))
             },
         ),
     );
@@ -138,7 +138,7 @@ pub fn register_poseidon_foreign(env: &mut HostEnv) {
                 let context = context.downcast_mut::<PoseidonContext>().unwrap();
                 log::debug!("buf len is {}", context.buf.len());
                 context.poseidon_new(args.nth::<u64>(0) as usize);
-                None
+                Ok(None)
             },
         ),
     );
@@ -152,7 +152,7 @@ pub fn register_poseidon_foreign(env: &mut HostEnv) {
             |_obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<PoseidonContext>().unwrap();
                 context.poseidon_push(args.nth::<u64>(0));
-                None
+                Ok(None)
             },
         ),
     );
@@ -165,7 +165,9 @@ pub fn register_poseidon_foreign(env: &mut HostEnv) {
         Rc::new(
             |_obs, context: &mut dyn ForeignContext, _args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<PoseidonContext>().unwrap();
-                Some(wasmi::RuntimeValue::I64(context.poseidon_finalize() as i64))
+                Ok(Some(wasmi::RuntimeValue::I64(
+                    context.poseidon_finalize() as i64
+                )))
             },
         ),
     );
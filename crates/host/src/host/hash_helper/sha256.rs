@@ -1,3 +1,4 @@
+use delphinus_zkwasm::foreign::foreign_table_enable_lines;
 use delphinus_zkwasm::runtime::host::host_env::HostEnv;
 use delphinus_zkwasm::runtime::host::ForeignContext;
 use delphinus_zkwasm::runtime::host::ForeignStatics;
@@ -48,10 +49,30 @@ impl Generator {
     }
 }
 
+/// Bytes of a block compressed by a round of SHA-256.
+const SHA256_BLOCK_SIZE: usize = 64;
+/// Words pushed by `sha256_push` for a block.
+pub const SHA256_BLOCK_WORDS: usize = SHA256_BLOCK_SIZE / 8;
+/// Words of the digest returned by `sha256_finalize` one by one.
+pub const SHA256_DIGEST_WORDS: usize = 4;
+
+/// Rounds of the blocks with the message of `size` bytes, including the padding of at least
+/// 9 bytes (0x80 and the 64-bit length).
+pub fn sha256_rounds(size: usize) -> usize {
+    (size + 9 + SHA256_BLOCK_SIZE - 1) / SHA256_BLOCK_SIZE
+}
+
+/// There is no host circuit of SHA-256 yet, the rounds are bounded so that the pushed words
+/// fit in the foreign table.
+pub fn sha256_max_rounds(k: u32) -> usize {
+    foreign_table_enable_lines(k) / SHA256_BLOCK_WORDS
+}
+
 struct Sha256Context {
     pub hasher: Option<Sha256>,
     pub generator: Generator,
     pub size: usize,
+    pub used_round: usize,
 }
 
 impl Sha256Context {
@@ -63,14 +84,17 @@ impl Sha256Context {
                 values: vec![],
             },
             size: 0,
+            used_round: 0,
         }
     }
 }
 
 impl ForeignContext for Sha256Context {
-    fn get_statics(&self, _k: u32) -> Option<ForeignStatics> {
-        // we did not support full sha256 as host yet
-        None
+    fn get_statics(&self, k: u32) -> Option<ForeignStatics> {
+        Some(ForeignStatics {
+            used_round: self.used_round,
+            max_round: sha256_max_rounds(k),
+        })
     }
 }
 
@@ -95,8 +119,9 @@ pub fn register_sha256_foreign(env: &mut HostEnv) {
                 if let Some(s) = hasher {
                     context.hasher = Some(s);
                     context.size = args.nth::<u64>(0) as usize;
+                    context.used_round += sha256_rounds(context.size);
                 };
-                None
+                Ok(None)
             },
         ),
     );
@@ -122,7 +147,7 @@ pub fn register_sha256_foreign(env: &mut HostEnv) {
                     r.truncate(sz);
                     s.update(r);
                 };
-                None
+                Ok(None)
             },
         ),
     );
@@ -143,8 +168,25 @@ pub fn register_sha256_foreign(env: &mut HostEnv) {
                         .collect::<Vec<u64>>();
                 };
                 context.hasher = None;
-                Some(wasmi::RuntimeValue::I64(context.generator.gen() as i64))
+                Ok(Some(wasmi::RuntimeValue::I64(
+                    context.generator.gen() as i64
+                )))
             },
         ),
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::sha256_rounds;
+
+    #[test]
+    fn test_sha256_rounds_with_padding() {
+        assert_eq!(sha256_rounds(0), 1);
+        assert_eq!(sha256_rounds(55), 1);
+        assert_eq!(sha256_rounds(56), 2);
+        assert_eq!(sha256_rounds(64), 2);
+        assert_eq!(sha256_rounds(119), 2);
+        assert_eq!(sha256_rounds(120), 3);
+    }
+}
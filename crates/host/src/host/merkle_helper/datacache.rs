@@ -123,7 +123,7 @@ pub fn register_datacache_foreign(env: &mut HostEnv, tree_db: Option<Rc<RefCell<
             |_obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<CacheContext>().unwrap();
                 context.set_mode(args.nth(0));
-                None
+                Ok(None)
             },
         ),
     );
@@ -137,7 +137,7 @@ pub fn register_datacache_foreign(env: &mut HostEnv, tree_db: Option<Rc<RefCell<
             |_obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<CacheContext>().unwrap();
                 context.set_data_hash(args.nth(0));
-                None
+                Ok(None)
             },
         ),
     );
@@ -151,7 +151,7 @@ pub fn register_datacache_foreign(env: &mut HostEnv, tree_db: Option<Rc<RefCell<
             |_obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<CacheContext>().unwrap();
                 context.store_data(args.nth(0));
-                None
+                Ok(None)
             },
         ),
     );
@@ -165,7 +165,7 @@ pub fn register_datacache_foreign(env: &mut HostEnv, tree_db: Option<Rc<RefCell<
             |_obs, context: &mut dyn ForeignContext, _args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<CacheContext>().unwrap();
 
-                Some(wasmi::RuntimeValue::I64(context.fetch_data() as i64))
+                Ok(Some(wasmi::RuntimeValue::I64(context.fetch_data() as i64)))
             },
         ),
     );
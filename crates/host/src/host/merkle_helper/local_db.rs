@@ -0,0 +1,241 @@
+use anyhow::Context;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::rc::Rc;
+use zkwasm_host_circuits::host::datahash::DataHashRecord;
+use zkwasm_host_circuits::host::db::TreeDB;
+use zkwasm_host_circuits::host::mongomerkle::MerkleRecord;
+
+const MERKLE_RECORDS: &str = "merkle_records.jsonl";
+const DATA_RECORDS: &str = "data_records.jsonl";
+
+/// A `TreeDB` backed by append-only files in a directory, so the merkle and the data cache
+/// plugins can run without a MongoDB and keep their state between runs.
+///
+/// The records are addressed by their hash, a record is written once and never updated.
+pub struct LocalTreeDB {
+    merkle_records: HashMap<[u8; 32], MerkleRecord>,
+    data_records: HashMap<[u8; 32], DataHashRecord>,
+    merkle_log: File,
+    data_log: File,
+}
+
+/// Load the records of the log at `path`. The last line is dropped and truncated if it's not
+/// terminated by a newline, which happens if the process is interrupted while appending records,
+/// so that the following records are appended after the last complete line.
+fn load_records<T: DeserializeOwned>(
+    path: &Path,
+    hash_of: impl Fn(&T) -> [u8; 32],
+) -> anyhow::Result<HashMap<[u8; 32], T>> {
+    let mut records = HashMap::new();
+
+    if path.exists() {
+        let buf = fs::read(path)?;
+        let complete = buf
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map_or(0, |last_newline| last_newline + 1);
+
+        for (line_number, line) in buf[..complete].split(|byte| *byte == b'\n').enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+
+            let record: T = serde_json::from_slice(line).with_context(|| {
+                format!("Invalid record at {}:{}", path.display(), line_number + 1)
+            })?;
+            records.insert(hash_of(&record), record);
+        }
+
+        if complete < buf.len() {
+            OpenOptions::new()
+                .write(true)
+                .open(path)?
+                .set_len(complete as u64)?;
+        }
+    }
+
+    Ok(records)
+}
+
+fn append_records<'a, T: Serialize + 'a>(
+    log: &mut File,
+    records: impl Iterator<Item = &'a T>,
+) -> anyhow::Result<()> {
+    let mut buf = vec![];
+    for record in records {
+        serde_json::to_writer(&mut buf, record)?;
+        buf.push(b'\n');
+    }
+
+    if !buf.is_empty() {
+        log.write_all(&buf)?;
+    }
+
+    Ok(())
+}
+
+impl LocalTreeDB {
+    /// Opens the db in `dir`, the directory is created if it doesn't exist.
+    pub fn open(dir: &Path) -> anyhow::Result<Self> {
+        fs::create_dir_all(dir)?;
+
+        let merkle_path = dir.join(MERKLE_RECORDS);
+        let data_path = dir.join(DATA_RECORDS);
+
+        let merkle_records = load_records(&merkle_path, |record: &MerkleRecord| record.hash)?;
+        let data_records = load_records(&data_path, |record: &DataHashRecord| record.hash)?;
+
+        let open_log = |path: &Path| OpenOptions::new().create(true).append(true).open(path);
+
+        Ok(LocalTreeDB {
+            merkle_records,
+            data_records,
+            merkle_log: open_log(&merkle_path)?,
+            data_log: open_log(&data_path)?,
+        })
+    }
+}
+
+impl TreeDB for LocalTreeDB {
+    fn get_merkle_record(&self, hash: &[u8; 32]) -> Result<Option<MerkleRecord>, anyhow::Error> {
+        Ok(self.merkle_records.get(hash).cloned())
+    }
+
+    fn set_merkle_record(&mut self, record: MerkleRecord) -> Result<(), anyhow::Error> {
+        self.set_merkle_records(&vec![record])
+    }
+
+    fn set_merkle_records(&mut self, records: &Vec<MerkleRecord>) -> Result<(), anyhow::Error> {
+        let new_records = records
+            .iter()
+            .filter(|record| !self.merkle_records.contains_key(&record.hash))
+            .collect::<Vec<_>>();
+
+        append_records(&mut self.merkle_log, new_records.iter().copied())?;
+
+        for record in new_records {
+            self.merkle_records.insert(record.hash, record.clone());
+        }
+
+        Ok(())
+    }
+
+    fn get_data_record(&self, hash: &[u8; 32]) -> Result<Option<DataHashRecord>, anyhow::Error> {
+        Ok(self.data_records.get(hash).cloned())
+    }
+
+    fn set_data_record(&mut self, record: DataHashRecord) -> Result<(), anyhow::Error> {
+        if !self.data_records.contains_key(&record.hash) {
+            append_records(&mut self.data_log, [&record].into_iter())?;
+            self.data_records.insert(record.hash, record);
+        }
+
+        Ok(())
+    }
+}
+
+/// Opens the `LocalTreeDB` in `dir` to be shared by the merkle and the data cache plugins.
+pub fn open_local_tree_db(dir: &Path) -> anyhow::Result<Rc<RefCell<dyn TreeDB>>> {
+    Ok(Rc::new(RefCell::new(LocalTreeDB::open(dir)?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn db_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir()
+            .join("zkwasm-host-test-local-db")
+            .join(name);
+        let _ = fs::remove_dir_all(&dir);
+
+        dir
+    }
+
+    fn merkle_record(index: u64, seed: u8) -> MerkleRecord {
+        MerkleRecord {
+            index,
+            hash: [seed; 32],
+            left: Some([seed + 1; 32]),
+            right: Some([seed + 2; 32]),
+            data: None,
+        }
+    }
+
+    #[test]
+    fn test_local_db_round_trip() {
+        let dir = db_dir("round_trip");
+
+        {
+            let mut db = LocalTreeDB::open(&dir).unwrap();
+            db.set_merkle_records(&vec![merkle_record(1, 1), merkle_record(2, 10)])
+                .unwrap();
+            db.set_data_record(DataHashRecord {
+                hash: [20; 32],
+                data: vec![1, 2, 3],
+            })
+            .unwrap();
+        }
+
+        let db = LocalTreeDB::open(&dir).unwrap();
+
+        let record = db.get_merkle_record(&[10; 32]).unwrap().unwrap();
+        assert_eq!(record.index, 2);
+        assert_eq!(record.left, Some([11; 32]));
+        assert_eq!(record.right, Some([12; 32]));
+        assert_eq!(record.data, None);
+        assert!(db.get_merkle_record(&[1; 32]).unwrap().is_some());
+        assert!(db.get_merkle_record(&[3; 32]).unwrap().is_none());
+
+        let record = db.get_data_record(&[20; 32]).unwrap().unwrap();
+        assert_eq!(record.data, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_local_db_incomplete_last_line() {
+        let dir = db_dir("incomplete_last_line");
+
+        {
+            let mut db = LocalTreeDB::open(&dir).unwrap();
+            db.set_merkle_record(merkle_record(1, 1)).unwrap();
+        }
+
+        // The process is interrupted while appending a record.
+        let mut log = OpenOptions::new()
+            .append(true)
+            .open(dir.join(MERKLE_RECORDS))
+            .unwrap();
+        log.write_all(b"{\"index\": 2, \"ha").unwrap();
+
+        {
+            let mut db = LocalTreeDB::open(&dir).unwrap();
+            assert!(db.get_merkle_record(&[1; 32]).unwrap().is_some());
+
+            db.set_merkle_record(merkle_record(2, 10)).unwrap();
+        }
+
+        let db = LocalTreeDB::open(&dir).unwrap();
+        assert!(db.get_merkle_record(&[1; 32]).unwrap().is_some());
+        assert!(db.get_merkle_record(&[10; 32]).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_local_db_malformed_line() {
+        let dir = db_dir("malformed_line");
+        fs::create_dir_all(&dir).unwrap();
+
+        fs::write(dir.join(DATA_RECORDS), b"not a record\n").unwrap();
+
+        assert!(LocalTreeDB::open(&dir).is_err());
+    }
+}
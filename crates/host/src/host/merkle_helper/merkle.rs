@@ -2,7 +2,9 @@ use delphinus_zkwasm::runtime::host::host_env::HostEnv;
 use delphinus_zkwasm::runtime::host::ForeignContext;
 use delphinus_zkwasm::runtime::host::ForeignStatics;
 use halo2_proofs::pairing::bn256::Fr;
+use serde::Serialize;
 use std::cell::RefCell;
+use std::collections::BTreeSet;
 use std::rc::Rc;
 use zkwasm_host_circuits::circuits::host::HostOpSelector;
 use zkwasm_host_circuits::circuits::merkle::MerkleChip;
@@ -20,6 +22,40 @@ use zkwasm_host_circuits::host::ReduceRule;
 
 use crate::MERKLE_TREE_HEIGHT;
 
+/// Name of the merkle plugin in the host env.
+pub const MERKLE_PLUGIN_NAME: &str = "foreign_merkle";
+
+/// A root of the merkle tree produced during the execution.
+#[derive(Debug, Clone, Serialize)]
+pub struct RootTransition {
+    /// eid of the host call which produced the root, counted from 1 over the whole execution.
+    pub eid: usize,
+    /// The leaf updated by `merkle_set`, `None` if the root is set by `merkle_setroot`.
+    pub address: Option<u32>,
+    pub root: [u8; 32],
+}
+
+/// A leaf of the merkle tree under `root` with its proof.
+#[derive(Debug, Clone, Serialize)]
+pub struct LeafProof {
+    pub address: u32,
+    pub data: [u64; 4],
+    pub root: [u8; 32],
+    /// The assist hashes of the merkle proof of the leaf.
+    pub assist: Vec<[u8; 32]>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LeafDiff {
+    pub address: u32,
+    pub from: [u64; 4],
+    pub to: [u64; 4],
+}
+
+fn index_of_address(address: u32) -> u64 {
+    (address as u64) + (1u64 << MERKLE_TREE_HEIGHT) - 1
+}
+
 pub struct MerkleContext {
     pub set_root: Reduce<Fr>,
     pub get_root: Reduce<Fr>,
@@ -32,6 +68,9 @@ pub struct MerkleContext {
     pub mongo_datahash: datahelper::MongoDataHash,
     pub tree_db: Option<Rc<RefCell<dyn TreeDB>>>,
     pub used_round: usize,
+    pub root_history: Vec<RootTransition>,
+    /// Addresses of the leaves read or written by the execution.
+    pub touched_addresses: BTreeSet<u32>,
 }
 
 fn new_reduce(rules: Vec<ReduceRule<Fr>>) -> Reduce<Fr> {
@@ -52,22 +91,30 @@ impl MerkleContext {
             mongo_datahash: datahelper::MongoDataHash::construct([0; 32], tree_db.clone()),
             tree_db,
             used_round: 0,
+            root_history: vec![],
+            touched_addresses: BTreeSet::new(),
         }
     }
 
-    pub fn merkle_setroot(&mut self, v: u64) {
+    pub fn merkle_setroot(&mut self, v: u64, eid: usize) {
         self.set_root.reduce(v);
         if self.set_root.cursor == 0 {
             log::debug!("set root: {:?}", &self.set_root.rules[0].bytes_value());
+            let root: [u8; 32] = self.set_root.rules[0]
+                .bytes_value()
+                .unwrap()
+                .try_into()
+                .unwrap();
             self.mongo_merkle = Some(merklehelper::MongoMerkle::construct(
                 [0; 32],
-                self.set_root.rules[0]
-                    .bytes_value()
-                    .unwrap()
-                    .try_into()
-                    .unwrap(),
+                root,
                 self.tree_db.clone(),
             ));
+            self.root_history.push(RootTransition {
+                eid,
+                address: None,
+                root,
+            });
         }
     }
 
@@ -97,11 +144,11 @@ impl MerkleContext {
         self.address.reduce(v);
     }
 
-    pub fn merkle_set(&mut self, v: u64) {
+    pub fn merkle_set(&mut self, v: u64, eid: usize) {
         self.set.reduce(v);
         if self.set.cursor == 0 {
             let address = self.address.rules[0].u64_value().unwrap() as u32;
-            let index = (address as u64) + (1u64 << MERKLE_TREE_HEIGHT) - 1;
+            let index = index_of_address(address);
             let mt = self
                 .mongo_merkle
                 .as_mut()
@@ -109,12 +156,19 @@ impl MerkleContext {
             let hash = self.set.rules[0].bytes_value().unwrap();
             mt.update_leaf_data_with_proof(index, &hash)
                 .expect("Unexpected failure: update leaf with proof fail");
+            self.touched_addresses.insert(address);
+            self.root_history.push(RootTransition {
+                eid,
+                address: Some(address),
+                root: mt.get_root_hash(),
+            });
         }
     }
 
     pub fn merkle_get(&mut self) -> u64 {
         let address = self.address.rules[0].u64_value().unwrap() as u32;
-        let index = (address as u64) + (1u64 << MERKLE_TREE_HEIGHT) - 1;
+        let index = index_of_address(address);
+        self.touched_addresses.insert(address);
         let mt = self
             .mongo_merkle
             .as_ref()
@@ -132,7 +186,61 @@ impl MerkleContext {
     }
 }
 
-impl MerkleContext {}
+impl MerkleContext {
+    /// The leaves at `addresses` of the tree under `root` with their proofs. The nodes are read
+    /// from the db of the context, so `root` should be a root produced by the execution or
+    /// persisted in the db.
+    pub fn export_leaves(
+        &self,
+        root: [u8; 32],
+        addresses: impl IntoIterator<Item = u32>,
+    ) -> anyhow::Result<Vec<LeafProof>> {
+        let mt = merklehelper::MongoMerkle::<MERKLE_TREE_HEIGHT>::construct(
+            [0; 32],
+            root,
+            self.tree_db.clone(),
+        );
+
+        addresses
+            .into_iter()
+            .map(|address| {
+                let (leaf, proof) = mt
+                    .get_leaf_with_proof(index_of_address(address))
+                    .map_err(|e| anyhow::anyhow!("Failed to get leaf {}: {:?}", address, e))?;
+
+                Ok(LeafProof {
+                    address,
+                    data: leaf.data_as_u64(),
+                    root,
+                    assist: proof.assist.to_vec(),
+                })
+            })
+            .collect()
+    }
+
+    /// The touched leaves of the tree under `root` with their proofs.
+    pub fn export_touched_leaves(&self, root: [u8; 32]) -> anyhow::Result<Vec<LeafProof>> {
+        self.export_leaves(root, self.touched_addresses.iter().copied())
+    }
+
+    /// The touched leaves whose data differs between the trees under `from` and `to`. The leaves
+    /// not touched by the execution are not compared.
+    pub fn diff(&self, from: [u8; 32], to: [u8; 32]) -> anyhow::Result<Vec<LeafDiff>> {
+        let from = self.export_touched_leaves(from)?;
+        let to = self.export_touched_leaves(to)?;
+
+        Ok(from
+            .into_iter()
+            .zip(to)
+            .filter(|(from, to)| from.data != to.data)
+            .map(|(from, to)| LeafDiff {
+                address: from.address,
+                from: from.data,
+                to: to.data,
+            })
+            .collect())
+    }
+}
 
 impl ForeignContext for MerkleContext {
     fn get_statics(&self, k: u32) -> Option<ForeignStatics> {
@@ -147,7 +255,7 @@ use specs::external_host_call_table::ExternalHostCallSignature;
 pub fn register_merkle_foreign(env: &mut HostEnv, tree_db: Option<Rc<RefCell<dyn TreeDB>>>) {
     let foreign_merkle_plugin = env
         .external_env
-        .register_plugin("foreign_merkle", Box::new(MerkleContext::new(tree_db)));
+        .register_plugin(MERKLE_PLUGIN_NAME, Box::new(MerkleContext::new(tree_db)));
 
     env.external_env.register_function(
         "merkle_setroot",
@@ -155,10 +263,10 @@ pub fn register_merkle_foreign(env: &mut HostEnv, tree_db: Option<Rc<RefCell<dyn
         ExternalHostCallSignature::Argument,
         foreign_merkle_plugin.clone(),
         Rc::new(
-            |_obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
+            |obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<MerkleContext>().unwrap();
-                context.merkle_setroot(args.nth(0));
-                None
+                context.merkle_setroot(args.nth(0), obs.current_eid());
+                Ok(None)
             },
         ),
     );
@@ -171,7 +279,9 @@ pub fn register_merkle_foreign(env: &mut HostEnv, tree_db: Option<Rc<RefCell<dyn
         Rc::new(
             |_obs, context: &mut dyn ForeignContext, _args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<MerkleContext>().unwrap();
-                Some(wasmi::RuntimeValue::I64(context.merkle_getroot() as i64))
+                Ok(Some(wasmi::RuntimeValue::I64(
+                    context.merkle_getroot() as i64
+                )))
             },
         ),
     );
@@ -185,7 +295,7 @@ pub fn register_merkle_foreign(env: &mut HostEnv, tree_db: Option<Rc<RefCell<dyn
             |_obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<MerkleContext>().unwrap();
                 context.merkle_address(args.nth(0));
-                None
+                Ok(None)
             },
         ),
     );
@@ -196,10 +306,10 @@ pub fn register_merkle_foreign(env: &mut HostEnv, tree_db: Option<Rc<RefCell<dyn
         ExternalHostCallSignature::Argument,
         foreign_merkle_plugin.clone(),
         Rc::new(
-            |_obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
+            |obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<MerkleContext>().unwrap();
-                context.merkle_set(args.nth(0));
-                None
+                context.merkle_set(args.nth(0), obs.current_eid());
+                Ok(None)
             },
         ),
     );
@@ -213,8 +323,126 @@ pub fn register_merkle_foreign(env: &mut HostEnv, tree_db: Option<Rc<RefCell<dyn
             |_obs, context: &mut dyn ForeignContext, _args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<MerkleContext>().unwrap();
 
-                Some(wasmi::RuntimeValue::I64(context.merkle_get() as i64))
+                Ok(Some(wasmi::RuntimeValue::I64(context.merkle_get() as i64)))
             },
         ),
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host::merkle_helper::local_db::open_local_tree_db;
+
+    fn words(bytes: &[u8; 32]) -> Vec<u64> {
+        bytes
+            .chunks(8)
+            .map(|x| u64::from_le_bytes(x.try_into().unwrap()))
+            .collect()
+    }
+
+    fn context(name: &str) -> MerkleContext {
+        let dir = std::env::temp_dir()
+            .join("zkwasm-host-test-merkle")
+            .join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        MerkleContext::new(Some(open_local_tree_db(&dir).unwrap()))
+    }
+
+    fn set(context: &mut MerkleContext, address: u32, data: [u64; 4], eid: usize) {
+        context.merkle_address(address as u64);
+        for v in data {
+            context.merkle_set(v, eid);
+        }
+    }
+
+    #[test]
+    fn test_merkle_root_history_and_diff() {
+        let mut context = context("root_history_and_diff");
+        let default_root = merklehelper::MongoMerkle::<MERKLE_TREE_HEIGHT>::default_root_hash();
+
+        for v in words(&default_root) {
+            context.merkle_setroot(v, 1);
+        }
+        set(&mut context, 0, [1, 2, 3, 4], 3);
+        set(&mut context, 5, [5, 0, 0, 0], 5);
+        // Leaf 7 is read but not updated.
+        context.merkle_address(7);
+        assert_eq!(context.merkle_get(), 0);
+
+        let history = context
+            .root_history
+            .iter()
+            .map(|transition| (transition.eid, transition.address))
+            .collect::<Vec<_>>();
+        assert_eq!(history, vec![(1, None), (3, Some(0)), (5, Some(5))]);
+
+        let roots = context
+            .root_history
+            .iter()
+            .map(|transition| transition.root)
+            .collect::<Vec<_>>();
+        assert_eq!(roots[0], default_root);
+        assert_ne!(roots[1], roots[0]);
+        assert_ne!(roots[2], roots[1]);
+
+        let latest = (0..4).map(|_| context.merkle_getroot()).collect::<Vec<_>>();
+        assert_eq!(latest, words(&roots[2]));
+
+        assert_eq!(
+            context.diff(roots[0], roots[2]).unwrap(),
+            vec![
+                LeafDiff {
+                    address: 0,
+                    from: [0; 4],
+                    to: [1, 2, 3, 4],
+                },
+                LeafDiff {
+                    address: 5,
+                    from: [0; 4],
+                    to: [5, 0, 0, 0],
+                },
+            ]
+        );
+        assert_eq!(
+            context.diff(roots[1], roots[2]).unwrap(),
+            vec![LeafDiff {
+                address: 5,
+                from: [0; 4],
+                to: [5, 0, 0, 0],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_merkle_export_leaves() {
+        let mut context = context("export_leaves");
+        let default_root = merklehelper::MongoMerkle::<MERKLE_TREE_HEIGHT>::default_root_hash();
+
+        for v in words(&default_root) {
+            context.merkle_setroot(v, 1);
+        }
+        set(&mut context, 3, [7, 8, 9, 10], 3);
+        let root = context.root_history.last().unwrap().root;
+
+        let leaves = context.export_leaves(root, [3, 4]).unwrap();
+        assert_eq!(leaves.len(), 2);
+        assert_eq!(leaves[0].address, 3);
+        assert_eq!(leaves[0].data, [7, 8, 9, 10]);
+        assert_eq!(leaves[1].address, 4);
+        assert_eq!(leaves[1].data, [0; 4]);
+        for leaf in &leaves {
+            assert_eq!(leaf.root, root);
+            assert_eq!(leaf.assist.len(), MERKLE_TREE_HEIGHT);
+        }
+
+        let touched = context.export_touched_leaves(root).unwrap();
+        assert_eq!(touched.len(), 1);
+        assert_eq!(touched[0].data, [7, 8, 9, 10]);
+
+        // The leaves of the previous root are still available.
+        let leaves = context.export_leaves(default_root, [3]).unwrap();
+        assert_eq!(leaves[0].data, [0; 4]);
+    }
+}
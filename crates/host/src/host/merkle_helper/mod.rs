@@ -1,2 +1,3 @@
 pub mod datacache;
+pub mod local_db;
 pub mod merkle;
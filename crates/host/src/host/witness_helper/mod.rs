@@ -1,3 +1,4 @@
+use delphinus_zkwasm::error::ExecutionError;
 use delphinus_zkwasm::runtime::host::ForeignContext;
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -34,8 +35,9 @@ impl WitnessContext {
         self.buf.insert(0, new);
     }
 
-    pub fn witness_pop(&mut self) -> u64 {
-        self.buf.pop().unwrap()
+    /// `None` if the buffer is empty.
+    pub fn witness_pop(&mut self) -> Option<u64> {
+        self.buf.pop()
     }
 
     pub fn witness_set_index(&mut self, index: u64) {
@@ -62,10 +64,10 @@ impl WitnessContext {
         }
     }
 
-    pub fn witness_indexed_pop(&mut self) -> u64 {
+    /// `None` if the buffer of the focused index is empty.
+    pub fn witness_indexed_pop(&mut self) -> Option<u64> {
         let mut bind = self.indexed_buf.borrow_mut();
-        let buf = bind.get_mut(&self.focus).unwrap();
-        buf.pop().unwrap()
+        bind.get_mut(&self.focus).and_then(|buf| buf.pop())
     }
 }
 
@@ -86,7 +88,7 @@ pub fn register_witness_foreign(env: &mut HostEnv, index_map: Rc<RefCell<HashMap
             |_obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<WitnessContext>().unwrap();
                 context.witness_insert(args.nth::<u64>(0));
-                None
+                Ok(None)
             },
         ),
     );
@@ -100,7 +102,7 @@ pub fn register_witness_foreign(env: &mut HostEnv, index_map: Rc<RefCell<HashMap
             |_obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<WitnessContext>().unwrap();
                 context.witness_set_index(args.nth::<u64>(0));
-                None
+                Ok(None)
             },
         ),
     );
@@ -114,7 +116,7 @@ pub fn register_witness_foreign(env: &mut HostEnv, index_map: Rc<RefCell<HashMap
             |_obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<WitnessContext>().unwrap();
                 context.witness_indexed_insert(args.nth::<u64>(0));
-                None
+                Ok(None)
             },
         ),
     );
@@ -128,7 +130,7 @@ pub fn register_witness_foreign(env: &mut HostEnv, index_map: Rc<RefCell<HashMap
             |_obs, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<WitnessContext>().unwrap();
                 context.witness_indexed_push(args.nth::<u64>(0));
-                None
+                Ok(None)
             },
         ),
     );
@@ -139,9 +141,12 @@ pub fn register_witness_foreign(env: &mut HostEnv, index_map: Rc<RefCell<HashMap
         ExternalHostCallSignature::Return,
         foreign_witness_plugin.clone(),
         Rc::new(
-            |_obs, context: &mut dyn ForeignContext, _args: wasmi::RuntimeArgs| {
+            |obs, context: &mut dyn ForeignContext, _args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<WitnessContext>().unwrap();
-                Some(wasmi::RuntimeValue::I64(context.witness_pop() as i64))
+                context
+                    .witness_pop()
+                    .map(|witness| Some(wasmi::RuntimeValue::I64(witness as i64)))
+                    .ok_or(ExecutionError::WitnessUnderflow(obs.current_eid()))
             },
         ),
     );
@@ -152,11 +157,15 @@ pub fn register_witness_foreign(env: &mut HostEnv, index_map: Rc<RefCell<HashMap
         ExternalHostCallSignature::Return,
         foreign_witness_plugin.clone(),
         Rc::new(
-            |_obs, context: &mut dyn ForeignContext, _args: wasmi::RuntimeArgs| {
+            |obs, context: &mut dyn ForeignContext, _args: wasmi::RuntimeArgs| {
                 let context = context.downcast_mut::<WitnessContext>().unwrap();
-                Some(wasmi::RuntimeValue::I64(
-                    context.witness_indexed_pop() as i64
-                ))
+                context
+                    .witness_indexed_pop()
+                    .map(|witness| Some(wasmi::RuntimeValue::I64(witness as i64)))
+                    .ok_or(ExecutionError::IndexedWitnessUnderflow(
+                        context.focus,
+                        obs.current_eid(),
+                    ))
             },
         ),
     );
@@ -168,7 +177,7 @@ pub fn register_witness_foreign(env: &mut HostEnv, index_map: Rc<RefCell<HashMap
         foreign_witness_plugin,
         Rc::new(
             |obs, _context: &mut dyn ForeignContext, _args: wasmi::RuntimeArgs| {
-                Some(wasmi::RuntimeValue::I64(obs.counter as i64))
+                Ok(Some(wasmi::RuntimeValue::I64(obs.counter as i64)))
             },
         ),
     );
@@ -7,9 +7,13 @@ use std::rc::Rc;
 
 use delphinus_zkwasm::foreign::context::runtime::register_context_foreign;
 use delphinus_zkwasm::foreign::log_helper::register_log_foreign;
+use delphinus_zkwasm::foreign::output_helper::register_output_channel_foreign;
 use delphinus_zkwasm::foreign::require_helper::register_require_foreign;
 use delphinus_zkwasm::foreign::wasm_input_helper::runtime::register_wasm_input_foreign;
 use delphinus_zkwasm::runtime::host::default_env::ExecutionArg;
+use host::hash_helper::sha256::sha256_max_rounds;
+use host::hash_helper::sha256::sha256_rounds;
+use host::hash_helper::sha256::SHA256_DIGEST_WORDS;
 
 use delphinus_zkwasm::runtime::host::host_env::HostEnv;
 use delphinus_zkwasm::runtime::host::HostEnvBuilder;
@@ -28,9 +32,29 @@ use zkwasm_host_circuits::host::db::TreeDB;
 use zkwasm_host_circuits::host::ForeignInst;
 use zkwasm_host_circuits::proof::OpType;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct HostEnvConfig {
     pub ops: Vec<OpType>,
+    /// SHA-256 has no `OpType` since there is no host circuit for it yet, it's disabled unless
+    /// it's selected explicitly.
+    #[serde(default)]
+    pub sha256: bool,
+}
+
+impl Default for HostEnvConfig {
+    /// Ops of the standard host env.
+    fn default() -> Self {
+        Self {
+            ops: vec![
+                OpType::POSEIDONHASH,
+                OpType::MERKLE,
+                OpType::JUBJUBSUM,
+                OpType::KECCAKHASH,
+                OpType::BN256SUM,
+            ],
+            sha256: false,
+        }
+    }
 }
 
 pub const MERKLE_TREE_HEIGHT: usize = 32;
@@ -52,16 +76,30 @@ impl HostEnvConfig {
         }
     }
 
+    /// The ops which have no group in the flush strategy, their calls could be split into
+    /// different slices.
+    pub fn ungrouped_ops(&self) -> Vec<OpType> {
+        self.ops
+            .iter()
+            .filter(|op| !op.is_grouped())
+            .cloned()
+            .collect()
+    }
+
     fn register_ops(&self, env: &mut HostEnv, tree_db: Option<Rc<RefCell<dyn TreeDB>>>) {
         for op in &self.ops {
             Self::register_op(op, env, tree_db.clone());
         }
+
+        if self.sha256 {
+            host::hash_helper::sha256::register_sha256_foreign(env);
+        }
     }
 }
 
 pub struct StandardHostEnvBuilder {
     k: u32,
-    ops: Vec<OpType>,
+    config: HostEnvConfig,
 }
 
 trait GroupedForeign {
@@ -92,31 +130,84 @@ impl GroupedForeign for ForeignInst {
 
 impl StandardHostEnvBuilder {
     pub fn new(k: u32) -> Self {
-        Self {
-            k,
-            ops: vec![
-                OpType::POSEIDONHASH,
-                OpType::MERKLE,
-                OpType::JUBJUBSUM,
-                OpType::KECCAKHASH,
-                OpType::BN256SUM,
-            ],
-        }
+        Self::with_config(k, HostEnvConfig::default())
+    }
+
+    /// Build the env with the ops of `config` instead of the standard ones.
+    pub fn with_config(k: u32, config: HostEnvConfig) -> Self {
+        Self { k, config }
     }
 }
 
+// Transaction id of SHA-256, the ids of the other ops are their `OpType`.
+const SHA256_TRANSACTION: usize = usize::MAX;
+
 #[derive(Default)]
 struct StandardHostEnvFlushStrategy {
     k: u32,
+    // Only the registered ops are grouped.
+    enabled_ops: Vec<usize>,
     ops: HashMap<usize, (usize, usize)>,
+
+    enable_sha256: bool,
+    // (pushed words, finalize calls) of the ongoing SHA-256 hash.
+    sha256: Option<(usize, usize)>,
+    sha256_rounds: usize,
+}
+
+impl StandardHostEnvFlushStrategy {
+    // A hash is grouped from `sha256_new` to the fourth `sha256_finalize` which returns the
+    // last word of the digest. The size is unknown, so the rounds are bounded by the words.
+    fn notify_sha256(&mut self, inst: ForeignInst) -> Command {
+        match inst {
+            ForeignInst::SHA256New => {
+                self.sha256 = Some((0, 0));
+                Command::Start(SHA256_TRANSACTION)
+            }
+            ForeignInst::SHA256Push => {
+                if let Some((words, _)) = self.sha256.as_mut() {
+                    *words += 1;
+                }
+                Command::Noop
+            }
+            ForeignInst::SHA256Finalize => {
+                let Some((words, finalized)) = self.sha256.as_mut() else {
+                    return Command::Noop;
+                };
+
+                *finalized += 1;
+                if *finalized < SHA256_DIGEST_WORDS {
+                    return Command::Noop;
+                }
+
+                self.sha256_rounds += sha256_rounds(*words * 8);
+                self.sha256 = None;
+
+                if self.sha256_rounds >= sha256_max_rounds(self.k) {
+                    Command::CommitAndAbort(SHA256_TRANSACTION)
+                } else {
+                    Command::Commit(SHA256_TRANSACTION)
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
 }
 
 trait OpTypeFlushHelper {
+    fn is_grouped(&self) -> bool;
     fn get_group_size(&self) -> usize;
     fn get_max_bound(&self, k: usize) -> usize;
 }
 
 impl OpTypeFlushHelper for OpType {
+    fn is_grouped(&self) -> bool {
+        matches!(
+            self,
+            OpType::MERKLE | OpType::JUBJUBSUM | OpType::POSEIDONHASH
+        )
+    }
+
     fn get_group_size(&self) -> usize {
         match self {
             OpType::MERKLE => 1 + 4 + 4 + 4, // address + set_root + get/set + get_root
@@ -140,7 +231,24 @@ impl FlushStrategy for StandardHostEnvFlushStrategy {
     fn notify(&mut self, op: Event) -> Command {
         match op {
             Event::HostCall(op) => {
-                let op_type = ForeignInst::from_usize(op).unwrap().get_optype();
+                // The ops without host circuit, e.g. the output channels, are not in `ForeignInst`.
+                let Some(inst) = ForeignInst::from_usize(op) else {
+                    return Command::Noop;
+                };
+                if self.enable_sha256
+                    && matches!(
+                        inst,
+                        ForeignInst::SHA256New
+                            | ForeignInst::SHA256Push
+                            | ForeignInst::SHA256Finalize
+                    )
+                {
+                    return self.notify_sha256(inst);
+                }
+
+                let op_type = inst
+                    .get_optype()
+                    .filter(|optype| self.enabled_ops.contains(&(optype.clone() as usize)));
                 if let Some(optype) = op_type {
                     // cargo clippy false positive
                     #[allow(clippy::redundant_clone)]
@@ -168,6 +276,8 @@ impl FlushStrategy for StandardHostEnvFlushStrategy {
             }
             Event::Reset => {
                 self.ops.clear();
+                self.sha256 = None;
+                self.sha256_rounds = 0;
                 Command::Noop
             }
         }
@@ -177,10 +287,8 @@ impl FlushStrategy for StandardHostEnvFlushStrategy {
 impl HostEnvBuilder for StandardHostEnvBuilder {
     fn create_env_without_value(&self) -> HostEnv {
         let mut env = HostEnv::new(self.k);
-        let host_env_config = HostEnvConfig {
-            ops: self.ops.clone(),
-        };
-        register_wasm_input_foreign(&mut env, vec![], vec![]);
+        register_wasm_input_foreign(&mut env, vec![], vec![].into());
+        register_output_channel_foreign(&mut env, vec![]);
         register_require_foreign(&mut env);
         register_log_foreign(&mut env);
         register_context_foreign(&mut env, vec![]);
@@ -188,7 +296,7 @@ impl HostEnvBuilder for StandardHostEnvBuilder {
             &mut env,
             Rc::new(RefCell::new(HashMap::new())),
         );
-        host_env_config.register_ops(&mut env, None);
+        self.config.register_ops(&mut env, None);
 
         env.finalize();
 
@@ -197,16 +305,14 @@ impl HostEnvBuilder for StandardHostEnvBuilder {
 
     fn create_env(&self, arg: ExecutionArg) -> HostEnv {
         let mut env = HostEnv::new(self.k);
-        let host_env_config = HostEnvConfig {
-            ops: self.ops.clone(),
-        };
 
         register_wasm_input_foreign(&mut env, arg.public_inputs, arg.private_inputs);
+        register_output_channel_foreign(&mut env, arg.output_channels);
         register_require_foreign(&mut env);
         register_log_foreign(&mut env);
         register_context_foreign(&mut env, arg.context_inputs);
         host::witness_helper::register_witness_foreign(&mut env, arg.indexed_witness);
-        host_env_config.register_ops(&mut env, arg.tree_db);
+        self.config.register_ops(&mut env, arg.tree_db);
 
         env.finalize();
 
@@ -216,7 +322,39 @@ impl HostEnvBuilder for StandardHostEnvBuilder {
     fn create_flush_strategy(&self) -> Box<dyn FlushStrategy> {
         Box::new(StandardHostEnvFlushStrategy {
             k: self.k,
-            ops: HashMap::new(),
+            enabled_ops: self
+                .config
+                .ops
+                .iter()
+                .map(|op| op.clone() as usize)
+                .collect(),
+            enable_sha256: self.config.sha256,
+            ..Default::default()
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::HostEnvConfig;
+
+    #[test]
+    fn test_host_env_config_default_matches_serde_default() {
+        let config: HostEnvConfig =
+            serde_json::from_str(r#"{"ops": ["POSEIDONHASH", "MERKLE"]}"#).unwrap();
+
+        assert_eq!(config.sha256, HostEnvConfig::default().sha256);
+        assert!(!config.sha256);
+    }
+
+    #[test]
+    fn test_host_env_config_ungrouped_ops() {
+        let config: HostEnvConfig =
+            serde_json::from_str(r#"{"ops": ["POSEIDONHASH", "MERKLE", "JUBJUBSUM"]}"#).unwrap();
+        assert!(config.ungrouped_ops().is_empty());
+
+        let config: HostEnvConfig =
+            serde_json::from_str(r#"{"ops": ["MERKLE", "KECCAKHASH", "BN256PAIR"]}"#).unwrap();
+        assert_eq!(config.ungrouped_ops().len(), 2);
+    }
+}
@@ -16,7 +16,7 @@ const K: u32 = MIN_K;
 
 fn main() -> Result<()> {
     let wasm = std::fs::read("wasm/context.wasm")?;
-    let module = ZkWasmLoader::parse_module(&wasm)?;
+    let module = ZkWasmLoader::parse_module(&wasm, K)?;
 
     let context_output = {
         let env_builder = DefaultHostEnvBuilder::new(K);
@@ -24,10 +24,11 @@ fn main() -> Result<()> {
         let env = env_builder.create_env(
             ExecutionArg {
                 public_inputs: vec![],
-                private_inputs: vec![],
+                private_inputs: vec![].into(),
                 context_inputs: vec![2, 1],
                 indexed_witness: Rc::new(RefCell::new(HashMap::default())),
                 tree_db: None,
+                output_channels: vec![],
             },
         );
 
@@ -43,7 +44,7 @@ fn main() -> Result<()> {
         let runner = loader.compile(&module, &mut monitor)?;
         let result = loader.run(runner, &mut monitor)?;
 
-        let slices: Slices<Fr> = Slices::new(K, monitor.into_tables(), None)?;
+        let slices: Slices<Fr> = Slices::new(K, monitor.into_tables()?, None)?;
         slices.mock_test_all(result.public_inputs_and_outputs())?;
 
         result.context_outputs
@@ -55,10 +56,11 @@ fn main() -> Result<()> {
         let env = env_builder.create_env(
             ExecutionArg {
                 public_inputs: vec![],
-                private_inputs: vec![],
+                private_inputs: vec![].into(),
                 context_inputs: context_output.0,
                 indexed_witness: Rc::new(RefCell::new(HashMap::default())),
                 tree_db: None,
+                output_channels: vec![],
             },
         );
 
@@ -74,7 +76,7 @@ fn main() -> Result<()> {
         let runner = loader.compile(&module, &mut monitor)?;
         let result = loader.run(runner, &mut monitor)?;
 
-        let slices: Slices<Fr> = Slices::new(K, monitor.into_tables(), None)?;
+        let slices: Slices<Fr> = Slices::new(K, monitor.into_tables()?, None)?;
         slices.mock_test_all(result.public_inputs_and_outputs())?;
     }
 
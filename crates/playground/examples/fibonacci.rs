@@ -16,16 +16,17 @@ const K: u32 = MIN_K;
 
 fn main() -> Result<()> {
     let wasm = std::fs::read("wasm/fibonacci.wasm")?;
-    let module = ZkWasmLoader::parse_module(&wasm)?;
+    let module = ZkWasmLoader::parse_module(&wasm, K)?;
     let env_builder = DefaultHostEnvBuilder::new(K);
 
     let env = env_builder.create_env(
         ExecutionArg {
             public_inputs: vec![5],
-            private_inputs: vec![],
+            private_inputs: vec![].into(),
             context_inputs: vec![],
             indexed_witness: Rc::new(RefCell::new(HashMap::default())),
             tree_db: None,
+            output_channels: vec![],
         },
     );
     let mut monitor = TableMonitor::new(
@@ -41,7 +42,7 @@ fn main() -> Result<()> {
     let result = loader.run(runner, &mut monitor)?;
     let instances = result.public_inputs_and_outputs::<Fr>();
 
-    let slices = Slices::new(K, monitor.into_tables(), None)?;
+    let slices = Slices::new(K, monitor.into_tables()?, None)?;
     slices.mock_test_all(instances)?;
 
     Ok(())
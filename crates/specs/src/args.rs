@@ -1,59 +1,407 @@
-pub fn parse_args<T: AsRef<str>>(values: &[T]) -> Vec<u64> {
-    values
-        .iter()
-        .flat_map(|v| {
-            let [v, t] = v.as_ref().split(':').collect::<Vec<&str>>()[..] else { todo!() };
-            match t {
-                "i64" => {
-                    if v.starts_with("0x") {
-                        vec![
-                            u64::from_str_radix(String::from(v).trim_start_matches("0x"), 16)
-                                .unwrap(),
-                        ]
-                    } else {
-                        vec![v.parse::<u64>().unwrap()]
-                    }
-                }
-                "bytes" => {
-                    if !v.starts_with("0x") {
-                        panic!("bytes input need start with 0x");
-                    }
-                    let bytes = hex::decode(String::from(v).trim_start_matches("0x")).unwrap();
-                    bytes.into_iter().map(u64::from).collect::<Vec<u64>>()
-                }
-                "bytes-packed" => {
-                    if !v.starts_with("0x") {
-                        panic!("bytes input need start with 0x");
-                    }
-                    let bytes = hex::decode(String::from(v).trim_start_matches("0x")).unwrap();
-                    let bytes = bytes.chunks(8);
-                    bytes
-                        .into_iter()
-                        .map(|x| {
-                            let mut data = [0u8; 8];
-                            data[..x.len()].copy_from_slice(x);
-
-                            u64::from_le_bytes(data)
-                        })
-                        .collect::<Vec<u64>>()
-                }
-                "file" => {
-                    let bytes = std::fs::read(v).unwrap();
-                    let bytes = bytes.chunks(8);
-                    bytes
-                        .into_iter()
-                        .map(|x| {
-                            let mut data = [0u8; 8];
-                            data[..x.len()].copy_from_slice(x);
-
-                            u64::from_be_bytes(data)
-                        })
-                        .collect()
-                }
-                _ => {
-                    panic!("Unsupported input data type: {}", t)
-                }
+use std::fmt::Display;
+use std::num::IntErrorKind;
+
+use base64::Engine;
+
+#[derive(Debug)]
+pub enum ArgsError {
+    MissingType {
+        arg: String,
+    },
+    UnsupportedType {
+        arg: String,
+        ty: String,
+    },
+    InvalidInteger {
+        arg: String,
+        reason: String,
+    },
+    MissingHexPrefix {
+        arg: String,
+    },
+    InvalidHex {
+        arg: String,
+        err: hex::FromHexError,
+    },
+    InvalidHexWords {
+        arg: String,
+        digits: usize,
+    },
+    InvalidBase64 {
+        arg: String,
+        err: base64::DecodeError,
+    },
+    InvalidJson {
+        arg: String,
+        reason: String,
+    },
+    Io {
+        arg: String,
+        err: std::io::Error,
+    },
+}
+
+impl Display for ArgsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArgsError::MissingType { arg } => {
+                write!(f, "input {} has no type, expect value:type", arg)
+            }
+            ArgsError::UnsupportedType { arg, ty } => write!(
+                f,
+                "input {} has unsupported type {}, expect one of {}",
+                arg,
+                ty,
+                SUPPORTED_TYPES.join("|")
+            ),
+            ArgsError::InvalidInteger { arg, reason } => {
+                write!(f, "input {} is not a valid integer: {}", arg, reason)
+            }
+            ArgsError::MissingHexPrefix { arg } => {
+                write!(f, "input {} should start with 0x", arg)
+            }
+            ArgsError::InvalidHex { arg, err } => {
+                write!(f, "input {} is not valid hex: {}", arg, err)
             }
+            ArgsError::InvalidHexWords { arg, digits } => write!(
+                f,
+                "input {} has {} hex digits, expect a multiple of 16",
+                arg, digits
+            ),
+            ArgsError::InvalidBase64 { arg, err } => {
+                write!(f, "input {} is not valid base64: {}", arg, err)
+            }
+            ArgsError::InvalidJson { arg, reason } => {
+                write!(
+                    f,
+                    "input {} is not a JSON array of integers: {}",
+                    arg, reason
+                )
+            }
+            ArgsError::Io { arg, err } => write!(f, "failed to read input {}: {}", arg, err),
+        }
+    }
+}
+
+impl std::error::Error for ArgsError {}
+
+const SUPPORTED_TYPES: [&str; 11] = [
+    "i64",
+    "u64",
+    "i32",
+    "bytes",
+    "bytes-packed",
+    "hex-words",
+    "base64",
+    "json",
+    "file",
+    "file-le",
+    "file-be",
+];
+
+fn parse_unsigned(arg: &str, v: &str, max: u64) -> Result<u64, ArgsError> {
+    let value = match v.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => v.parse::<u64>(),
+    }
+    .map_err(|err| ArgsError::InvalidInteger {
+        arg: arg.to_string(),
+        reason: err.to_string(),
+    })?;
+
+    if value > max {
+        return Err(ArgsError::InvalidInteger {
+            arg: arg.to_string(),
+            reason: format!("{} exceeds {}", value, max),
+        });
+    }
+
+    Ok(value)
+}
+
+/// Parse a decimal which may be negative, or a hex with `0x` prefix, within `bits` bits. Negative
+/// values are returned in two's complement of `bits` bits.
+fn parse_signed(arg: &str, v: &str, bits: u32) -> Result<u64, ArgsError> {
+    let mask = u64::MAX >> (64 - bits);
+
+    if v.starts_with('-') {
+        let min = -(1i128 << (bits - 1));
+
+        let value = v.parse::<i64>().map_err(|err| ArgsError::InvalidInteger {
+            arg: arg.to_string(),
+            reason: match err.kind() {
+                IntErrorKind::NegOverflow => format!("{} is less than {}", v, min),
+                _ => err.to_string(),
+            },
+        })?;
+
+        if (value as i128) < min {
+            return Err(ArgsError::InvalidInteger {
+                arg: arg.to_string(),
+                reason: format!("{} is less than {}", v, min),
+            });
+        }
+
+        Ok(value as u64 & mask)
+    } else {
+        parse_unsigned(arg, v, mask)
+    }
+}
+
+fn decode_hex(arg: &str, v: &str) -> Result<Vec<u8>, ArgsError> {
+    let Some(hex) = v.strip_prefix("0x") else {
+        return Err(ArgsError::MissingHexPrefix {
+            arg: arg.to_string(),
+        });
+    };
+
+    hex::decode(hex).map_err(|err| ArgsError::InvalidHex {
+        arg: arg.to_string(),
+        err,
+    })
+}
+
+/// Pack the bytes into words of 8 bytes, the last word is padded with zeros.
+fn pack_bytes(bytes: &[u8], from_bytes: fn([u8; 8]) -> u64) -> Vec<u64> {
+    bytes
+        .chunks(8)
+        .map(|x| {
+            let mut data = [0u8; 8];
+            data[..x.len()].copy_from_slice(x);
+
+            from_bytes(data)
+        })
+        .collect()
+}
+
+fn parse_json(arg: &str, v: &str) -> Result<Vec<u64>, ArgsError> {
+    let invalid = |reason: String| ArgsError::InvalidJson {
+        arg: arg.to_string(),
+        reason,
+    };
+
+    let value =
+        serde_json::from_str::<serde_json::Value>(v).map_err(|err| invalid(err.to_string()))?;
+    let serde_json::Value::Array(elements) = value else {
+        return Err(invalid("not an array".to_string()));
+    };
+
+    elements
+        .iter()
+        .enumerate()
+        .map(|(i, element)| {
+            element
+                .as_u64()
+                .or_else(|| element.as_i64().map(|v| v as u64))
+                .ok_or_else(|| invalid(format!("element {} is {}", i, element)))
         })
         .collect()
 }
+
+fn parse_arg(arg: &str) -> Result<Vec<u64>, ArgsError> {
+    // Split at the last `:` so that the value could contain `:`, e.g. a path.
+    let Some((v, t)) = arg.rsplit_once(':') else {
+        return Err(ArgsError::MissingType {
+            arg: arg.to_string(),
+        });
+    };
+
+    match t {
+        "i64" => Ok(vec![parse_signed(arg, v, 64)?]),
+        "u64" => Ok(vec![parse_unsigned(arg, v, u64::MAX)?]),
+        "i32" => Ok(vec![parse_signed(arg, v, 32)?]),
+        "bytes" => Ok(decode_hex(arg, v)?.into_iter().map(u64::from).collect()),
+        "bytes-packed" => Ok(pack_bytes(&decode_hex(arg, v)?, u64::from_le_bytes)),
+        "hex-words" => {
+            let bytes = decode_hex(arg, v)?;
+            if bytes.len() % 8 != 0 {
+                return Err(ArgsError::InvalidHexWords {
+                    arg: arg.to_string(),
+                    digits: bytes.len() * 2,
+                });
+            }
+
+            Ok(pack_bytes(&bytes, u64::from_be_bytes))
+        }
+        "base64" => {
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(v)
+                .map_err(|err| ArgsError::InvalidBase64 {
+                    arg: arg.to_string(),
+                    err,
+                })?;
+
+            Ok(bytes.into_iter().map(u64::from).collect())
+        }
+        "json" => parse_json(arg, v),
+        "file" | "file-be" | "file-le" => {
+            let bytes = std::fs::read(v).map_err(|err| ArgsError::Io {
+                arg: arg.to_string(),
+                err,
+            })?;
+
+            if t == "file-le" {
+                Ok(pack_bytes(&bytes, u64::from_le_bytes))
+            } else {
+                Ok(pack_bytes(&bytes, u64::from_be_bytes))
+            }
+        }
+        _ => Err(ArgsError::UnsupportedType {
+            arg: arg.to_string(),
+            ty: t.to_string(),
+        }),
+    }
+}
+
+/// Parse the inputs with format `value:type`, each input may expand to multiple words.
+///
+/// - `i64`, `u64`: a decimal or a hex with `0x` prefix, `i64` accepts negative decimals.
+/// - `i32`: as `i64` but within 32 bits, negative values are in 32-bit two's complement.
+/// - `bytes`: a hex with `0x` prefix, each byte is a word.
+/// - `bytes-packed`: a hex with `0x` prefix, every 8 bytes are a little-endian word.
+/// - `hex-words`: a hex with `0x` prefix, every 16 digits are a word as written.
+/// - `base64`: standard base64 with padding, each byte is a word.
+/// - `json`: an array of integers, e.g. `[1,2,3]:json`.
+/// - `file-le`, `file-be`: path to a file, every 8 bytes are a little/big-endian word.
+/// - `file`: alias of `file-be`.
+pub fn parse_args<T: AsRef<str>>(values: &[T]) -> Result<Vec<u64>, ArgsError> {
+    let mut words = vec![];
+
+    for value in values {
+        words.extend(parse_arg(value.as_ref())?);
+    }
+
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_i32() {
+        assert_eq!(parse_args(&["-1:i32"]).unwrap(), vec![0xffff_ffff]);
+        assert_eq!(parse_args(&["-2147483648:i32"]).unwrap(), vec![0x8000_0000]);
+        assert_eq!(parse_args(&["0xffffffff:i32"]).unwrap(), vec![0xffff_ffff]);
+
+        assert!(matches!(
+            parse_args(&["-2147483649:i32"]),
+            Err(ArgsError::InvalidInteger { .. })
+        ));
+        assert!(matches!(
+            parse_args(&["4294967296:i32"]),
+            Err(ArgsError::InvalidInteger { .. })
+        ));
+        assert!(matches!(
+            parse_args(&["-99999999999999999999:i32"]),
+            Err(ArgsError::InvalidInteger { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_i64_and_u64() {
+        assert_eq!(parse_args(&["-1:i64"]).unwrap(), vec![u64::MAX]);
+        assert_eq!(
+            parse_args(&["0xffffffffffffffff:u64", "42:u64"]).unwrap(),
+            vec![u64::MAX, 42]
+        );
+
+        assert!(matches!(
+            parse_args(&["0x10000000000000000:u64"]),
+            Err(ArgsError::InvalidInteger { .. })
+        ));
+        assert!(matches!(
+            parse_args(&["-1:u64"]),
+            Err(ArgsError::InvalidInteger { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        assert_eq!(parse_args(&["0x0102:bytes"]).unwrap(), vec![1, 2]);
+        assert_eq!(
+            parse_args(&["0x010203040506070809:bytes-packed"]).unwrap(),
+            vec![0x0807060504030201, 0x09]
+        );
+        assert!(matches!(
+            parse_args(&["0102:bytes"]),
+            Err(ArgsError::MissingHexPrefix { .. })
+        ));
+        assert!(matches!(
+            parse_args(&["0x0g:bytes"]),
+            Err(ArgsError::InvalidHex { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_hex_words() {
+        assert_eq!(
+            parse_args(&["0x0102030405060708000000000000002a:hex-words"]).unwrap(),
+            vec![0x0102030405060708, 42]
+        );
+        assert!(matches!(
+            parse_args(&["0x01020304:hex-words"]),
+            Err(ArgsError::InvalidHexWords { digits: 8, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_base64() {
+        assert_eq!(parse_args(&["AQID:base64"]).unwrap(), vec![1, 2, 3]);
+        assert!(matches!(
+            parse_args(&["AQI*:base64"]),
+            Err(ArgsError::InvalidBase64 { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_json() {
+        assert_eq!(
+            parse_args(&["[1,-1,18446744073709551615]:json"]).unwrap(),
+            vec![1, u64::MAX, u64::MAX]
+        );
+        assert!(matches!(
+            parse_args(&["[1,1.5]:json"]),
+            Err(ArgsError::InvalidJson { reason, .. }) if reason.contains("element 1")
+        ));
+        assert!(matches!(
+            parse_args(&["{\"a\":1}:json"]),
+            Err(ArgsError::InvalidJson { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_file() {
+        let path = std::env::temp_dir().join("specs-test-args.bin");
+        std::fs::write(&path, [1u8, 0, 0, 0, 0, 0, 0, 0, 2]).unwrap();
+
+        assert_eq!(
+            parse_args(&[format!("{}:file-le", path.display())]).unwrap(),
+            vec![1, 2]
+        );
+        assert_eq!(
+            parse_args(&[format!("{}:file-be", path.display())]).unwrap(),
+            vec![1 << 56, 2 << 56]
+        );
+        assert_eq!(
+            parse_args(&[format!("{}:file", path.display())]).unwrap(),
+            parse_args(&[format!("{}:file-be", path.display())]).unwrap()
+        );
+        assert!(matches!(
+            parse_args(&["/nonexistent/specs-test-args.bin:file"]),
+            Err(ArgsError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_type() {
+        assert!(matches!(
+            parse_args(&["42"]),
+            Err(ArgsError::MissingType { .. })
+        ));
+        assert!(matches!(
+            parse_args(&["42:u128"]),
+            Err(ArgsError::UnsupportedType { ty, .. }) if ty == "u128"
+        ));
+    }
+}
@@ -1,6 +1,4 @@
-use std::io::Read;
-use std::io::Write;
-use std::path::PathBuf;
+use std::path::Path;
 
 use serde::Deserialize;
 use serde::Serialize;
@@ -8,8 +6,11 @@ use serde::Serialize;
 use crate::itable::InstructionTable;
 use crate::itable::InstructionTableEntry;
 use crate::step::StepInfo;
-
-const JSON: bool = false;
+use crate::trace::write_trace;
+use crate::trace::TraceError;
+use crate::trace::TraceHeader;
+use crate::trace::TraceKind;
+use crate::trace::TraceReader;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EventTableEntry {
@@ -60,27 +61,17 @@ impl EventTable {
         Self(entries)
     }
 
-    pub fn write(&self, path: &PathBuf) -> std::io::Result<()> {
-        let mut fd = std::fs::File::create(path)?;
-
-        if JSON {
-            fd.write_all(serde_json::to_string_pretty(self).unwrap().as_bytes())?;
-        } else {
-            fd.write_all(&bincode::serialize(self).unwrap())?;
-        }
-        Ok(())
+    pub fn write(&self, path: &Path, header: &TraceHeader) -> Result<(), TraceError> {
+        write_trace(path, TraceKind::EventTable, header, &(), &self.0)
     }
 
-    pub fn read(path: &PathBuf) -> std::io::Result<Self> {
-        let mut fd = std::fs::File::open(path)?;
-        let mut buf = Vec::new();
-        fd.read_to_end(&mut buf)?;
+    /// Iterate over the entries of the event table at `path` without loading the whole table.
+    pub fn stream(path: &Path) -> Result<TraceReader<(), EventTableEntry>, TraceError> {
+        TraceReader::open(path, TraceKind::EventTable)
+    }
 
-        if JSON {
-            Ok(serde_json::from_slice(&buf).unwrap())
-        } else {
-            Ok(bincode::deserialize(&buf).unwrap())
-        }
+    pub fn read(path: &Path) -> Result<Self, TraceError> {
+        Ok(Self(Self::stream(path)?.collect::<Result<_, _>>()?))
     }
 
     pub fn unwrap(self) -> Vec<EventTableEntry> {
@@ -1,3 +1,6 @@
+use std::io::Read;
+use std::path::PathBuf;
+
 use serde::ser::SerializeStruct;
 use serde::Deserialize;
 use serde::Serialize;
@@ -54,7 +57,33 @@ impl Serialize for ExternalHostCallEntry {
     }
 }
 
-#[derive(Default, Serialize)]
+impl<'de> Deserialize<'de> for ExternalHostCallEntry {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Entry {
+            op: usize,
+            value: u64,
+            is_ret: bool,
+        }
+
+        let entry = Entry::deserialize(deserializer)?;
+
+        Ok(ExternalHostCallEntry {
+            op: entry.op,
+            value: entry.value,
+            sig: if entry.is_ret {
+                ExternalHostCallSignature::Return
+            } else {
+                ExternalHostCallSignature::Argument
+            },
+        })
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
 pub struct ExternalHostCallTable(pub(crate) Vec<ExternalHostCallEntry>);
 
 impl ExternalHostCallTable {
@@ -62,6 +91,14 @@ impl ExternalHostCallTable {
         Self(entries)
     }
 
+    pub fn read(path: &PathBuf) -> std::io::Result<Self> {
+        let mut fd = std::fs::File::open(path)?;
+        let mut buf = Vec::new();
+        fd.read_to_end(&mut buf)?;
+
+        serde_json::from_slice(&buf).map_err(std::io::Error::from)
+    }
+
     pub fn entries(&self) -> &Vec<ExternalHostCallEntry> {
         &self.0
     }
@@ -55,6 +55,12 @@ pub enum OpcodeClass {
     MemoryGrow,
     Conversion,
     ForeignPluginStart,
+    // The float classes follow the classes of the foreign plugins, so that the opcodes of the
+    // other classes are kept whether the float circuits are enabled or not.
+    FloatUnary = 32,
+    FloatBin,
+    FloatRel,
+    FloatConversion,
 }
 
 impl OpcodeClass {
@@ -78,7 +84,7 @@ impl OpcodeClass {
             OpcodeClass::BrIf => 1,
             OpcodeClass::BrIfEqz => 1,
             OpcodeClass::BrTable => 1,
-            OpcodeClass::Unreachable => todo!(),
+            OpcodeClass::Unreachable => 0,
             OpcodeClass::Call => 0,
             OpcodeClass::CallHost => 1, // Push or pop
             OpcodeClass::CallIndirect => 1,
@@ -87,6 +93,10 @@ impl OpcodeClass {
             OpcodeClass::MemorySize => 1,
             OpcodeClass::MemoryGrow => 2,
             OpcodeClass::Conversion => 2,
+            OpcodeClass::FloatUnary => 2,
+            OpcodeClass::FloatBin => 3,
+            OpcodeClass::FloatRel => 3,
+            OpcodeClass::FloatConversion => 2,
             OpcodeClass::ForeignPluginStart => 0,
         }
     }
@@ -185,6 +195,51 @@ pub enum ConversionOp {
     I64Extend32S,
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, EnumIter, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FloatUnaryOp {
+    Abs,
+    Neg,
+    Ceil,
+    Floor,
+    Trunc,
+    Nearest,
+    Sqrt,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, EnumIter, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FloatBinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Min,
+    Max,
+    Copysign,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, EnumIter, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FloatRelOp {
+    Eq,
+    Ne,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+}
+
+/// The source and destination types are carried by `Opcode::FloatConversion`,
+/// e.g. `i32.trunc_f64_s` is `TruncSigned` from `F64` to `I32`.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, EnumIter, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FloatConversionOp {
+    TruncSigned,
+    TruncUnsigned,
+    ConvertSigned,
+    ConvertUnsigned,
+    Demote,
+    Promote,
+    Reinterpret,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct BrTarget {
     pub drop: u32,
@@ -296,6 +351,23 @@ pub enum Opcode {
     Conversion {
         class: ConversionOp,
     },
+    FloatUnary {
+        class: FloatUnaryOp,
+        vtype: ValueType,
+    },
+    FloatBin {
+        class: FloatBinOp,
+        vtype: ValueType,
+    },
+    FloatRel {
+        class: FloatRelOp,
+        vtype: ValueType,
+    },
+    FloatConversion {
+        class: FloatConversionOp,
+        from: ValueType,
+        to: ValueType,
+    },
 }
 
 impl Opcode {
@@ -536,6 +608,27 @@ impl From<&Opcode> for BigUint {
                     1u64.into(),
                 ),
             },
+            Opcode::FloatUnary { class, vtype } => {
+                (BigUint::from(OpcodeClass::FloatUnary as u64) << OPCODE_CLASS_SHIFT)
+                    + (BigUint::from(*class as u64) << OPCODE_ARG0_SHIFT)
+                    + (BigUint::from(VarType::from(vtype) as u64) << OPCODE_ARG1_SHIFT)
+            }
+            Opcode::FloatBin { class, vtype } => {
+                (BigUint::from(OpcodeClass::FloatBin as u64) << OPCODE_CLASS_SHIFT)
+                    + (BigUint::from(*class as u64) << OPCODE_ARG0_SHIFT)
+                    + (BigUint::from(VarType::from(vtype) as u64) << OPCODE_ARG1_SHIFT)
+            }
+            Opcode::FloatRel { class, vtype } => {
+                (BigUint::from(OpcodeClass::FloatRel as u64) << OPCODE_CLASS_SHIFT)
+                    + (BigUint::from(*class as u64) << OPCODE_ARG0_SHIFT)
+                    + (BigUint::from(VarType::from(vtype) as u64) << OPCODE_ARG1_SHIFT)
+            }
+            Opcode::FloatConversion { class, from, to } => {
+                (BigUint::from(OpcodeClass::FloatConversion as u64) << OPCODE_CLASS_SHIFT)
+                    + (BigUint::from(*class as u64) << OPCODE_ARG0_SHIFT)
+                    + (BigUint::from(VarType::from(from) as u64) << OPCODE_ARG1_SHIFT)
+                    + VarType::from(to) as u64
+            }
         };
         assert!(bn < BigUint::from(1u64) << OPCODE_SHIFT);
         bn
@@ -574,6 +667,10 @@ impl From<&Opcode> for OpcodeClass {
             Opcode::MemorySize => OpcodeClass::MemorySize,
             Opcode::MemoryGrow => OpcodeClass::MemoryGrow,
             Opcode::Conversion { .. } => OpcodeClass::Conversion,
+            Opcode::FloatUnary { .. } => OpcodeClass::FloatUnary,
+            Opcode::FloatBin { .. } => OpcodeClass::FloatBin,
+            Opcode::FloatRel { .. } => OpcodeClass::FloatRel,
+            Opcode::FloatConversion { .. } => OpcodeClass::FloatConversion,
         }
     }
 }
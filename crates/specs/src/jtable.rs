@@ -1,19 +1,21 @@
-use std::io::Read;
-use std::io::Write;
-use std::path::PathBuf;
+use std::path::Path;
 use std::sync::Arc;
 
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::trace::write_trace;
+use crate::trace::TraceError;
+use crate::trace::TraceHeader;
+use crate::trace::TraceKind;
+use crate::trace::TraceReader;
+
 // Inherited frame table entries:
 // 1. zkmain
 // 2. start(optional)
 // 3. active frames inherited from last slice
 pub const INHERITED_FRAME_TABLE_ENTRIES: usize = 4096;
 
-const JSON: bool = false;
-
 #[derive(Copy, Clone, Debug, Default, Serialize, Deserialize)]
 pub struct FrameTableEntryInternal {
     // caller eid (unique)
@@ -116,26 +118,35 @@ pub struct FrameTable {
 }
 
 impl FrameTable {
-    pub fn read(path: &PathBuf) -> std::io::Result<Self> {
-        let mut fd = std::fs::File::open(path)?;
-        let mut buf = Vec::new();
-        fd.read_to_end(&mut buf)?;
-
-        if JSON {
-            Ok(serde_json::from_slice(&buf).unwrap())
-        } else {
-            Ok(bincode::deserialize(&buf).unwrap())
-        }
+    /// The inherited entries are stored ahead of the called entries, so they can be read
+    /// from the reader before iterating over the called entries.
+    pub fn stream(
+        path: &Path,
+    ) -> Result<TraceReader<InheritedFrameEntries, CalledFrameTableEntry>, TraceError> {
+        TraceReader::open(path, TraceKind::FrameTable)
+    }
+
+    pub fn read_inherited(path: &Path) -> Result<InheritedFrameEntries, TraceError> {
+        Ok(Self::stream(path)?.into_prelude())
     }
 
-    pub fn write(&self, path: &PathBuf) -> std::io::Result<()> {
-        let mut fd = std::fs::File::create(path)?;
+    pub fn read(path: &Path) -> Result<Self, TraceError> {
+        let mut reader = Self::stream(path)?;
+        let called = reader.by_ref().collect::<Result<_, _>>()?;
 
-        if JSON {
-            fd.write_all(serde_json::to_string_pretty(self).unwrap().as_bytes())?;
-        } else {
-            fd.write_all(&bincode::serialize(self).unwrap())?;
-        }
-        Ok(())
+        Ok(FrameTable {
+            inherited: Arc::new(reader.into_prelude()),
+            called: CalledFrameTable::new(called),
+        })
+    }
+
+    pub fn write(&self, path: &Path, header: &TraceHeader) -> Result<(), TraceError> {
+        write_trace(
+            path,
+            TraceKind::FrameTable,
+            header,
+            self.inherited.as_ref(),
+            &self.called.0,
+        )
     }
 }
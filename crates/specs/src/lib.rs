@@ -17,10 +17,19 @@ use brtable::ElemTable;
 use configure_table::ConfigureTable;
 use etable::EventTable;
 use imtable::InitMemoryTable;
+use imtable::InitMemoryTableEntry;
 use itable::InstructionTable;
 use jtable::FrameTable;
 use jtable::InheritedFrameTable;
+use jtable::InheritedFrameTableEntry;
+use serde::Deserialize;
+use serde::Serialize;
 use state::InitializationState;
+use trace::write_trace;
+use trace::TraceError;
+use trace::TraceHeader;
+use trace::TraceKind;
+use trace::TraceReader;
 
 use crate::external_host_call_table::ExternalHostCallTable;
 
@@ -41,12 +50,19 @@ pub mod mtable;
 pub mod slice;
 pub mod state;
 pub mod step;
+pub mod trace;
+pub mod trap;
 pub mod types;
 
+/// Name of the file of the compilation tables in the trace directory.
+pub const COMPILATION_TABLES: &str = "compilation_tables.data";
+/// Name of the file of the context tables and the number of slices in the trace directory.
+pub const CONTEXT_TABLES: &str = "context_tables.json";
+
 pub enum TraceBackend {
     File {
-        event_table_writer: Box<dyn Fn(usize, &EventTable) -> PathBuf>,
-        frame_table_writer: Box<dyn Fn(usize, &FrameTable) -> PathBuf>,
+        event_table_writer: Box<dyn Fn(usize, &EventTable) -> Result<PathBuf, TraceError>>,
+        frame_table_writer: Box<dyn Fn(usize, &FrameTable) -> Result<PathBuf, TraceError>>,
     },
     Memory,
 }
@@ -81,33 +97,103 @@ pub struct Tables {
     pub execution_tables: ExecutionTable,
 }
 
+impl CompilationTable {
+    pub fn write(&self, path: &Path, header: &TraceHeader) -> Result<(), TraceError> {
+        let data = CompilationTableData {
+            itable: (*self.itable).clone(),
+            imtable: self.imtable.0.values().cloned().collect(),
+            br_table: (*self.br_table).clone(),
+            elem_table: (*self.elem_table).clone(),
+            configure_table: *self.configure_table,
+            initial_frame_table: self.initial_frame_table.iter().cloned().collect(),
+            initialization_state: (*self.initialization_state).clone(),
+        };
+
+        write_trace::<_, ()>(path, TraceKind::CompilationTable, header, &data, &[])
+    }
+
+    pub fn read(path: &Path) -> Result<Self, TraceError> {
+        let data: CompilationTableData =
+            TraceReader::<_, ()>::open(path, TraceKind::CompilationTable)?.into_prelude();
+
+        let initial_frame_table: InheritedFrameTable =
+            data.initial_frame_table.try_into().map_err(|_| {
+                TraceError::Encoding(Box::new(bincode::ErrorKind::Custom(
+                    "too many inherited frame table entries".to_string(),
+                )))
+            })?;
+
+        Ok(CompilationTable {
+            itable: Arc::new(data.itable),
+            imtable: Arc::new(InitMemoryTable::new(data.imtable)),
+            br_table: Arc::new(data.br_table),
+            elem_table: Arc::new(data.elem_table),
+            configure_table: Arc::new(data.configure_table),
+            initial_frame_table: Arc::new(initial_frame_table),
+            initialization_state: Arc::new(data.initialization_state),
+        })
+    }
+}
+
+// The serializable form of `CompilationTable`.
+#[derive(Serialize, Deserialize)]
+struct CompilationTableData {
+    itable: InstructionTable,
+    imtable: Vec<InitMemoryTableEntry>,
+    br_table: BrTable,
+    elem_table: ElemTable,
+    configure_table: ConfigureTable,
+    initial_frame_table: Vec<InheritedFrameTableEntry>,
+    initialization_state: InitializationState<u32>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContextTables {
+    slices: usize,
+    context_input_table: Vec<u64>,
+    context_output_table: Vec<u64>,
+}
+
 impl Tables {
-    pub fn write(&self, dir: &Path, name_of_frame_table_slice: impl Fn(usize) -> String) {
-        fn write_file(folder: &Path, filename: &str, buf: &String) {
+    /// Write the tables to `dir`, they could be loaded by `Tables::read` to build the slices
+    /// without executing the image again.
+    pub fn write(
+        &self,
+        dir: &Path,
+        trace_header: impl Fn(usize) -> TraceHeader,
+        name_of_etable_slice: impl Fn(usize) -> String,
+        name_of_frame_table_slice: impl Fn(usize) -> String,
+        name_of_external_host_call_table_slice: impl Fn(usize) -> String,
+    ) -> Result<(), TraceError> {
+        fn write_file(folder: &Path, filename: &str, buf: &String) -> std::io::Result<()> {
             let folder = folder.join(filename);
-            let mut fd = File::create(folder.as_path()).unwrap();
+            let mut fd = File::create(folder.as_path())?;
 
-            fd.write_all(buf.as_bytes()).unwrap();
+            fd.write_all(buf.as_bytes())
         }
 
         write_file(
             dir,
             "itable.json",
             &serde_json::to_string_pretty(&self.compilation_tables.itable).unwrap(),
-        );
-        self.execution_tables
-            .frame_table
-            .iter()
-            .enumerate()
-            .for_each(|(slice, frame_table)| {
-                if let TableBackend::Memory(frame_table) = frame_table {
-                    write_file(
-                        dir,
-                        &name_of_frame_table_slice(slice),
-                        &serde_json::to_string_pretty(frame_table).unwrap(),
-                    );
-                }
-            });
+        )?;
+        self.compilation_tables
+            .write(&dir.join(COMPILATION_TABLES), &trace_header(0))?;
+
+        for (slice, etable) in self.execution_tables.etable.iter().enumerate() {
+            if let TableBackend::Memory(etable) = etable {
+                etable.write(&dir.join(name_of_etable_slice(slice)), &trace_header(slice))?;
+            }
+        }
+
+        for (slice, frame_table) in self.execution_tables.frame_table.iter().enumerate() {
+            if let TableBackend::Memory(frame_table) = frame_table {
+                frame_table.write(
+                    &dir.join(name_of_frame_table_slice(slice)),
+                    &trace_header(slice),
+                )?;
+            }
+        }
 
         for (i, external_host_call_table) in self
             .execution_tables
@@ -117,9 +203,60 @@ impl Tables {
         {
             write_file(
                 dir,
-                &format!("external_host_table.{}.json", i),
+                &name_of_external_host_call_table_slice(i),
                 &serde_json::to_string_pretty(&external_host_call_table).unwrap(),
-            );
+            )?;
         }
+
+        write_file(
+            dir,
+            CONTEXT_TABLES,
+            &serde_json::to_string_pretty(&ContextTables {
+                slices: self.execution_tables.etable.len(),
+                context_input_table: self.execution_tables.context_input_table.clone(),
+                context_output_table: self.execution_tables.context_output_table.clone(),
+            })
+            .unwrap(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Load the tables written by `Tables::write`. Event tables and frame tables are not loaded
+    /// until the slices are built.
+    pub fn read(
+        dir: &Path,
+        name_of_etable_slice: impl Fn(usize) -> String,
+        name_of_frame_table_slice: impl Fn(usize) -> String,
+        name_of_external_host_call_table_slice: impl Fn(usize) -> String,
+    ) -> Result<Self, TraceError> {
+        let compilation_tables = CompilationTable::read(&dir.join(COMPILATION_TABLES))?;
+
+        let context_tables: ContextTables =
+            serde_json::from_slice(&std::fs::read(dir.join(CONTEXT_TABLES))?)
+                .map_err(std::io::Error::from)?;
+
+        let execution_tables = ExecutionTable {
+            etable: (0..context_tables.slices)
+                .map(|slice| TableBackend::Json(dir.join(name_of_etable_slice(slice))))
+                .collect(),
+            frame_table: (0..context_tables.slices)
+                .map(|slice| TableBackend::Json(dir.join(name_of_frame_table_slice(slice))))
+                .collect(),
+            external_host_call_table: (0..context_tables.slices)
+                .map(|slice| {
+                    ExternalHostCallTable::read(
+                        &dir.join(name_of_external_host_call_table_slice(slice)),
+                    )
+                })
+                .collect::<std::io::Result<_>>()?,
+            context_input_table: context_tables.context_input_table,
+            context_output_table: context_tables.context_output_table,
+        };
+
+        Ok(Tables {
+            compilation_tables,
+            execution_tables,
+        })
     }
 }
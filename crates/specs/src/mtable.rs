@@ -73,12 +73,17 @@ impl MemoryStoreSize {
     }
 }
 
+// VarType is the storage type of a stack/memory slot. Floats are kept as their raw
+// IEEE-754 bits, so they share the slot type of the integer of the same width.
 impl From<parity_wasm::elements::ValueType> for VarType {
     fn from(v: parity_wasm::elements::ValueType) -> Self {
         match v {
-            parity_wasm::elements::ValueType::I32 => Self::I32,
-            parity_wasm::elements::ValueType::I64 => Self::I64,
-            _ => todo!(),
+            parity_wasm::elements::ValueType::I32 | parity_wasm::elements::ValueType::F32 => {
+                Self::I32
+            }
+            parity_wasm::elements::ValueType::I64 | parity_wasm::elements::ValueType::F64 => {
+                Self::I64
+            }
         }
     }
 }
@@ -86,18 +91,15 @@ impl From<parity_wasm::elements::ValueType> for VarType {
 impl From<crate::types::ValueType> for VarType {
     fn from(v: crate::types::ValueType) -> Self {
         match v {
-            crate::types::ValueType::I32 => Self::I32,
-            crate::types::ValueType::I64 => Self::I64,
+            crate::types::ValueType::I32 | crate::types::ValueType::F32 => Self::I32,
+            crate::types::ValueType::I64 | crate::types::ValueType::F64 => Self::I64,
         }
     }
 }
 
 impl From<&crate::types::ValueType> for VarType {
     fn from(v: &crate::types::ValueType) -> Self {
-        match v {
-            crate::types::ValueType::I32 => Self::I32,
-            crate::types::ValueType::I64 => Self::I64,
-        }
+        VarType::from(*v)
     }
 }
 
@@ -1,10 +1,15 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::io::Read;
+use std::io::Write;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use rayon::iter::IntoParallelRefIterator;
 use rayon::iter::ParallelIterator;
 use rayon::prelude::ParallelSliceMut;
+use serde::Deserialize;
+use serde::Serialize;
 
 use crate::brtable::BrTable;
 use crate::brtable::ElemTable;
@@ -13,15 +18,18 @@ use crate::etable::EventTable;
 use crate::etable::EventTableEntry;
 use crate::external_host_call_table::ExternalHostCallTable;
 use crate::imtable::InitMemoryTable;
+use crate::imtable::InitMemoryTableEntry;
 use crate::itable::InstructionTable;
 use crate::jtable::CalledFrameTable;
 use crate::jtable::FrameTable;
+use crate::jtable::InheritedFrameEntries;
 use crate::jtable::InheritedFrameTable;
 use crate::mtable::AccessType;
 use crate::mtable::LocationType;
 use crate::mtable::MTable;
 use crate::mtable::MemoryTableEntry;
 use crate::state::InitializationState;
+use crate::trap::TrapCode;
 use crate::CompilationTable;
 
 #[derive(Debug)]
@@ -55,6 +63,37 @@ impl FrameTableSlice {
     }
 }
 
+/// The state after a slice is executed, i.e. the state the next slice starts from.
+///
+/// It's persisted so that the following slices can be built without threading the state
+/// from the first slice.
+#[derive(Serialize, Deserialize)]
+pub struct SliceState {
+    pub post_imtable: Vec<InitMemoryTableEntry>,
+    pub post_initialization_state: InitializationState<u32>,
+    pub post_inherited_frame_table: InheritedFrameEntries,
+}
+
+impl SliceState {
+    pub fn write(&self, path: &PathBuf) -> std::io::Result<()> {
+        let mut fd = std::fs::File::create(path)?;
+        let data = bincode::serialize(self)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+        fd.write_all(&data)?;
+
+        Ok(())
+    }
+
+    pub fn read(path: &PathBuf) -> std::io::Result<Self> {
+        let mut fd = std::fs::File::open(path)?;
+        let mut buf = Vec::new();
+        fd.read_to_end(&mut buf)?;
+
+        bincode::deserialize(&buf)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+}
+
 pub struct Slice {
     pub itable: Arc<InstructionTable>,
     pub br_table: Arc<BrTable>,
@@ -112,6 +151,29 @@ impl Slice {
         }
     }
 
+    /// The trap which terminates the execution, only the last slice of a trapped execution
+    /// ends with a trap.
+    pub fn trap(&self) -> Option<TrapCode> {
+        if !self.is_last_slice {
+            return None;
+        }
+
+        self.etable.entries().last().and_then(TrapCode::of_step)
+    }
+
+    pub fn post_state(&self) -> SliceState {
+        SliceState {
+            post_imtable: self.post_imtable.0.values().cloned().collect(),
+            post_initialization_state: (*self.post_initialization_state).clone(),
+            post_inherited_frame_table: self
+                .post_inherited_frame_table
+                .iter()
+                .cloned()
+                .collect::<Vec<_>>()
+                .into(),
+        }
+    }
+
     pub fn create_memory_table(
         &self,
         memory_event_of_step: fn(&EventTableEntry) -> Vec<MemoryTableEntry>,
@@ -3,6 +3,10 @@ use crate::host_function::HostPlugin;
 use crate::host_function::Signature;
 use crate::itable::BinOp;
 use crate::itable::BitOp;
+use crate::itable::FloatBinOp;
+use crate::itable::FloatConversionOp;
+use crate::itable::FloatRelOp;
+use crate::itable::FloatUnaryOp;
 use crate::itable::RelOp;
 use crate::itable::ShiftOp;
 use crate::itable::UnaryOp;
@@ -49,6 +53,8 @@ pub enum StepInfo {
     },
 
     Drop,
+    /// Only recorded as the last step of an execution which traps.
+    Unreachable,
     Select {
         val1: u64,
         val2: u64,
@@ -238,4 +244,33 @@ pub enum StepInfo {
         value: i64,
         result: i64,
     },
+
+    // Float operands and results are raw IEEE-754 bits.
+    FloatUnaryOp {
+        class: FloatUnaryOp,
+        vtype: ValueType,
+        operand: u64,
+        result: u64,
+    },
+    FloatBinOp {
+        class: FloatBinOp,
+        vtype: ValueType,
+        left: u64,
+        right: u64,
+        value: u64,
+    },
+    FloatComp {
+        class: FloatRelOp,
+        vtype: ValueType,
+        left: u64,
+        right: u64,
+        value: bool,
+    },
+    FloatConversion {
+        class: FloatConversionOp,
+        from: ValueType,
+        to: ValueType,
+        value: u64,
+        result: u64,
+    },
 }
@@ -0,0 +1,347 @@
+//! On-disk format of the traces.
+//!
+//! A trace file starts with `TRACE_MAGIC`, the format version and the kind of the table,
+//! followed by a `TraceHeader`, an optional prelude and the entries. Entries are split into
+//! chunks of at most `TRACE_CHUNK_SIZE` entries, each chunk is prefixed by its length in bytes
+//! and a zero length marks the end of the entries. All values are encoded by bincode.
+//!
+//! Chunks allow the entries to be streamed by `TraceReader` without loading the whole table.
+
+use std::fmt::Display;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Seek;
+use std::io::Write;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+
+pub const TRACE_MAGIC: [u8; 8] = *b"ZKWTRACE";
+pub const TRACE_VERSION: u32 = 1;
+pub const TRACE_CHUNK_SIZE: usize = 1 << 16;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraceKind {
+    CompilationTable,
+    EventTable,
+    FrameTable,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TraceHeader {
+    pub k: u32,
+    /// Digest of the image which the trace is generated from.
+    pub image_checksum: String,
+    /// Index of the slice, it's always 0 for the compilation tables.
+    pub slice: usize,
+}
+
+#[derive(Debug)]
+pub enum TraceError {
+    Io(std::io::Error),
+    Encoding(bincode::Error),
+    NotATrace,
+    UnsupportedVersion {
+        version: u32,
+    },
+    KindMismatch {
+        expected: TraceKind,
+        found: TraceKind,
+    },
+}
+
+impl Display for TraceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TraceError::Io(err) => write!(f, "failed to access the trace: {}", err),
+            TraceError::Encoding(err) => write!(f, "malformed trace: {}", err),
+            TraceError::NotATrace => write!(f, "the file is not a trace"),
+            TraceError::UnsupportedVersion { version } => write!(
+                f,
+                "the trace is of version {} but version {} is expected",
+                version, TRACE_VERSION
+            ),
+            TraceError::KindMismatch { expected, found } => write!(
+                f,
+                "the trace is of {:?} but {:?} is expected",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TraceError {}
+
+impl From<std::io::Error> for TraceError {
+    fn from(err: std::io::Error) -> Self {
+        TraceError::Io(err)
+    }
+}
+
+impl From<bincode::Error> for TraceError {
+    fn from(err: bincode::Error) -> Self {
+        TraceError::Encoding(err)
+    }
+}
+
+pub(crate) fn write_trace<P: Serialize, T: Serialize>(
+    path: &Path,
+    kind: TraceKind,
+    header: &TraceHeader,
+    prelude: &P,
+    entries: &[T],
+) -> Result<(), TraceError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    writer.write_all(&TRACE_MAGIC)?;
+    writer.write_all(&TRACE_VERSION.to_le_bytes())?;
+    bincode::serialize_into(&mut writer, &kind)?;
+    bincode::serialize_into(&mut writer, header)?;
+    bincode::serialize_into(&mut writer, prelude)?;
+
+    for chunk in entries.chunks(TRACE_CHUNK_SIZE) {
+        let buf = bincode::serialize(chunk)?;
+
+        writer.write_all(&(buf.len() as u64).to_le_bytes())?;
+        writer.write_all(&buf)?;
+    }
+    writer.write_all(&0u64.to_le_bytes())?;
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+fn read_preamble(reader: &mut impl Read, kind: TraceKind) -> Result<TraceHeader, TraceError> {
+    let mut magic = [0u8; 8];
+    reader
+        .read_exact(&mut magic)
+        .map_err(|_| TraceError::NotATrace)?;
+    if magic != TRACE_MAGIC {
+        return Err(TraceError::NotATrace);
+    }
+
+    let mut version = [0u8; 4];
+    reader.read_exact(&mut version)?;
+    let version = u32::from_le_bytes(version);
+    if version != TRACE_VERSION {
+        return Err(TraceError::UnsupportedVersion { version });
+    }
+
+    let found: TraceKind = bincode::deserialize_from(&mut *reader)?;
+    if found != kind {
+        return Err(TraceError::KindMismatch {
+            expected: kind,
+            found,
+        });
+    }
+
+    Ok(bincode::deserialize_from(reader)?)
+}
+
+/// Read the header of the trace at `path` without loading its content.
+pub fn read_header(path: &Path, kind: TraceKind) -> Result<TraceHeader, TraceError> {
+    read_preamble(&mut BufReader::new(File::open(path)?), kind)
+}
+
+/// Streaming reader of the entries of a trace.
+pub struct TraceReader<P, T> {
+    reader: BufReader<File>,
+    /// Length of the trace file, a chunk never exceeds the rest of the file.
+    len: u64,
+    header: TraceHeader,
+    prelude: P,
+    chunk: std::vec::IntoIter<T>,
+    finished: bool,
+}
+
+impl<P: DeserializeOwned, T: DeserializeOwned> TraceReader<P, T> {
+    pub(crate) fn open(path: &Path, kind: TraceKind) -> Result<Self, TraceError> {
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+        let mut reader = BufReader::new(file);
+
+        let header = read_preamble(&mut reader, kind)?;
+        let prelude = bincode::deserialize_from(&mut reader)?;
+
+        Ok(TraceReader {
+            reader,
+            len,
+            header,
+            prelude,
+            chunk: vec![].into_iter(),
+            finished: false,
+        })
+    }
+
+    pub fn header(&self) -> &TraceHeader {
+        &self.header
+    }
+
+    pub fn prelude(&self) -> &P {
+        &self.prelude
+    }
+
+    pub fn into_prelude(self) -> P {
+        self.prelude
+    }
+
+    fn next_chunk(&mut self) -> Result<Option<Vec<T>>, TraceError> {
+        let mut len = [0u8; 8];
+        self.reader.read_exact(&mut len)?;
+        let len = u64::from_le_bytes(len);
+
+        if len == 0 {
+            return Ok(None);
+        }
+
+        let remaining = self.len.saturating_sub(self.reader.stream_position()?);
+        if len > remaining {
+            return Err(TraceError::Encoding(Box::new(bincode::ErrorKind::Custom(
+                format!(
+                    "chunk of {} bytes exceeds the remaining {} bytes of the trace",
+                    len, remaining
+                ),
+            ))));
+        }
+
+        let mut buf = vec![0u8; len as usize];
+        self.reader.read_exact(&mut buf)?;
+
+        Ok(Some(bincode::deserialize(&buf)?))
+    }
+}
+
+impl<P: DeserializeOwned, T: DeserializeOwned> Iterator for TraceReader<P, T> {
+    type Item = Result<T, TraceError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(entry) = self.chunk.next() {
+                return Some(Ok(entry));
+            }
+
+            if self.finished {
+                return None;
+            }
+
+            match self.next_chunk() {
+                Ok(Some(chunk)) => self.chunk = chunk.into_iter(),
+                Ok(None) => self.finished = true,
+                Err(err) => {
+                    self.finished = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn trace_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("specs-test-trace");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        dir.join(name)
+    }
+
+    fn header() -> TraceHeader {
+        TraceHeader {
+            k: 18,
+            image_checksum: "checksum".to_string(),
+            slice: 1,
+        }
+    }
+
+    /// Write the preamble of a trace with `version` and `kind` followed by `rest`.
+    fn write_raw(path: &Path, version: u32, kind: TraceKind, rest: &[u8]) {
+        let mut buf = TRACE_MAGIC.to_vec();
+        buf.extend(version.to_le_bytes());
+        buf.extend(bincode::serialize(&kind).unwrap());
+        buf.extend(bincode::serialize(&header()).unwrap());
+        buf.extend(rest);
+
+        std::fs::write(path, buf).unwrap();
+    }
+
+    #[test]
+    fn test_trace_round_trip() {
+        let path = trace_path("round_trip.trace");
+        let entries = (0..2 * TRACE_CHUNK_SIZE as u64 + 3).collect::<Vec<_>>();
+
+        write_trace(&path, TraceKind::EventTable, &header(), &7u32, &entries).unwrap();
+
+        let reader = TraceReader::<u32, u64>::open(&path, TraceKind::EventTable).unwrap();
+        assert_eq!(reader.header(), &header());
+        assert_eq!(reader.prelude(), &7);
+        assert_eq!(reader.collect::<Result<Vec<_>, _>>().unwrap(), entries);
+        assert_eq!(read_header(&path, TraceKind::EventTable).unwrap(), header());
+    }
+
+    #[test]
+    fn test_trace_not_a_trace() {
+        let path = trace_path("not_a_trace.trace");
+
+        std::fs::write(&path, b"{\"entries\": []}").unwrap();
+        assert!(matches!(
+            read_header(&path, TraceKind::EventTable),
+            Err(TraceError::NotATrace)
+        ));
+
+        std::fs::write(&path, b"ZKW").unwrap();
+        assert!(matches!(
+            read_header(&path, TraceKind::EventTable),
+            Err(TraceError::NotATrace)
+        ));
+    }
+
+    #[test]
+    fn test_trace_unsupported_version() {
+        let path = trace_path("unsupported_version.trace");
+
+        write_raw(&path, TRACE_VERSION + 1, TraceKind::EventTable, &[]);
+        assert!(matches!(
+            read_header(&path, TraceKind::EventTable),
+            Err(TraceError::UnsupportedVersion { version }) if version == TRACE_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn test_trace_kind_mismatch() {
+        let path = trace_path("kind_mismatch.trace");
+
+        write_trace::<(), u64>(&path, TraceKind::FrameTable, &header(), &(), &[]).unwrap();
+        assert!(matches!(
+            TraceReader::<(), u64>::open(&path, TraceKind::EventTable),
+            Err(TraceError::KindMismatch {
+                expected: TraceKind::EventTable,
+                found: TraceKind::FrameTable,
+            })
+        ));
+    }
+
+    #[test]
+    fn test_trace_oversized_chunk() {
+        let path = trace_path("oversized_chunk.trace");
+
+        write_raw(
+            &path,
+            TRACE_VERSION,
+            TraceKind::EventTable,
+            &u64::MAX.to_le_bytes(),
+        );
+
+        let mut reader = TraceReader::<(), u64>::open(&path, TraceKind::EventTable).unwrap();
+        assert!(matches!(reader.next(), Some(Err(TraceError::Encoding(_)))));
+        assert!(reader.next().is_none());
+    }
+}
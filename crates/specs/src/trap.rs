@@ -0,0 +1,36 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::etable::EventTableEntry;
+use crate::host_function::HostPlugin;
+use crate::step::StepInfo;
+
+/// The outcome of an execution which ends with a trap instead of returning from the entry.
+///
+/// The code is exposed as the public instance following the public inputs and the outputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TrapCode {
+    Unreachable = 1,
+    RequireFailed = 2,
+}
+
+impl TrapCode {
+    /// The trap of `entry` if it's the terminating step of a trapped execution.
+    pub fn of_step(entry: &EventTableEntry) -> Option<TrapCode> {
+        match &entry.step_info {
+            StepInfo::Unreachable => Some(TrapCode::Unreachable),
+            StepInfo::CallHost {
+                plugin: HostPlugin::Require,
+                args,
+                ..
+            } if args[0] == 0 => Some(TrapCode::RequireFailed),
+            _ => None,
+        }
+    }
+}
+
+impl From<TrapCode> for u64 {
+    fn from(code: TrapCode) -> Self {
+        code as u64
+    }
+}
@@ -9,6 +9,14 @@ use crate::mtable::VarType;
 pub enum ValueType {
     I32,
     I64,
+    F32,
+    F64,
+}
+
+impl ValueType {
+    pub fn is_float(&self) -> bool {
+        matches!(self, ValueType::F32 | ValueType::F64)
+    }
 }
 
 impl From<parity_wasm::elements::ValueType> for ValueType {
@@ -16,8 +24,8 @@ impl From<parity_wasm::elements::ValueType> for ValueType {
         match v {
             parity_wasm::elements::ValueType::I32 => ValueType::I32,
             parity_wasm::elements::ValueType::I64 => ValueType::I64,
-            parity_wasm::elements::ValueType::F32 => todo!(),
-            parity_wasm::elements::ValueType::F64 => todo!(),
+            parity_wasm::elements::ValueType::F32 => ValueType::F32,
+            parity_wasm::elements::ValueType::F64 => ValueType::F64,
         }
     }
 }
@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::Range;
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::dev::metadata;
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::dev::VerifyFailure;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use specs::itable::Opcode;
+use specs::mtable::LocationType;
+use specs::slice::Slice;
+
+use super::etable::EVENT_TABLE_ENTRY_ROWS;
+use super::jtable::FrameTableValueOffset;
+use super::mtable::MEMORY_TABLE_ENTRY_ROWS;
+use super::utils::table_entry::MemoryWritingTable;
+use super::zkwasm_circuit::ZkWasmCircuitConfig;
+use crate::error::MockTestError;
+use crate::runtime::memory_event_of_step;
+
+/// The step of the event table which failed the mock test.
+#[derive(Debug, Clone)]
+pub struct FailedStep {
+    pub eid: u32,
+    pub fid: u32,
+    pub iid: u32,
+    pub opcode: Opcode,
+    /// Filled by `MockTestError::with_function_names`.
+    pub function_name: Option<String>,
+}
+
+/// Where a failure of the mock prover comes from.
+#[derive(Debug, Clone)]
+pub enum FailureLocation {
+    /// `step` is `None` if the row is beyond the steps of the slice, e.g. the padding.
+    EventTable {
+        index: usize,
+        step: Option<FailedStep>,
+    },
+    MemoryTable {
+        index: usize,
+        location: Option<(LocationType, u32)>,
+        start_eid: Option<u32>,
+    },
+    /// The inherited entries come first, followed by the called entries.
+    FrameTable {
+        index: usize,
+    },
+    ImageTable {
+        row: usize,
+    },
+    BitTable {
+        row: usize,
+    },
+    Unknown {
+        row: Option<usize>,
+    },
+}
+
+impl Display for FailureLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FailureLocation::EventTable {
+                index,
+                step: Some(step),
+            } => {
+                write!(f, "event table entry {} (eid {}, ", index, step.eid)?;
+                match &step.function_name {
+                    Some(name) => write!(f, "{}({})", name, step.fid)?,
+                    None => write!(f, "func[{}]", step.fid)?,
+                }
+                write!(f, ":{} {:?})", step.iid, step.opcode)
+            }
+            FailureLocation::EventTable { index, step: None } => {
+                write!(f, "event table entry {} (no step)", index)
+            }
+            FailureLocation::MemoryTable {
+                index,
+                location: Some((ltype, offset)),
+                start_eid: Some(start_eid),
+            } => write!(
+                f,
+                "memory table entry {} ({:?}[{}] written at eid {})",
+                index, ltype, offset, start_eid
+            ),
+            FailureLocation::MemoryTable { index, .. } => {
+                write!(f, "memory table entry {} (no entry)", index)
+            }
+            FailureLocation::FrameTable { index } => write!(f, "frame table entry {}", index),
+            FailureLocation::ImageTable { row } => write!(f, "image table row {}", row),
+            FailureLocation::BitTable { row } => write!(f, "bit table row {}", row),
+            FailureLocation::Unknown { row: Some(row) } => write!(f, "row {}", row),
+            FailureLocation::Unknown { row: None } => write!(f, "unknown location"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MockFailure {
+    pub location: FailureLocation,
+    /// The failure reported by the mock prover.
+    pub message: String,
+}
+
+impl Display for MockFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Table {
+    Event,
+    Memory,
+    Frame,
+    Image,
+    Bit,
+}
+
+/// The gates and lookups configured by each table, the others are not located.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TableLayout {
+    gates: Vec<(Table, Range<usize>)>,
+    lookups: Vec<(Table, Range<usize>)>,
+}
+
+impl TableLayout {
+    /// Run `configure` and record the gates and lookups it adds as the ones of `table`.
+    pub(crate) fn configure<F: FieldExt, C>(
+        &mut self,
+        meta: &mut ConstraintSystem<F>,
+        table: Table,
+        configure: impl FnOnce(&mut ConstraintSystem<F>) -> C,
+    ) -> C {
+        let (gates, lookups) = (meta.gates().len(), meta.lookups().len());
+
+        let config = configure(meta);
+
+        self.gates.push((table, gates..meta.gates().len()));
+        self.lookups.push((table, lookups..meta.lookups().len()));
+
+        config
+    }
+
+    fn table_of_lookup(&self, lookup_index: usize) -> Option<Table> {
+        self.lookups
+            .iter()
+            .find(|(_, lookups)| lookups.contains(&lookup_index))
+            .map(|(table, _)| *table)
+    }
+
+    fn table_of_constraint<F: FieldExt>(
+        &self,
+        meta: &ConstraintSystem<F>,
+        constraint: &metadata::Constraint,
+    ) -> Option<Table> {
+        let is_in_gate = |index: usize| {
+            let gate = &meta.gates()[index];
+
+            (0..gate.polynomials().len()).any(|i| {
+                let gate_metadata = metadata::Gate::from((index, gate.name()));
+
+                *constraint
+                    == metadata::Constraint::from((gate_metadata, i, gate.constraint_name(i)))
+            })
+        };
+
+        self.gates
+            .iter()
+            .find(|(_, gates)| gates.clone().any(is_in_gate))
+            .map(|(table, _)| *table)
+    }
+}
+
+struct Locator<'a, F: FieldExt> {
+    k: u32,
+    slice: &'a Slice,
+    meta: &'a ConstraintSystem<F>,
+    layout: &'a TableLayout,
+    mtable: Option<MemoryWritingTable>,
+}
+
+impl<'a, F: FieldExt> Locator<'a, F> {
+    // All regions of the circuit start at the row 0.
+    fn locate(&mut self, failure: &VerifyFailure) -> FailureLocation {
+        let (table, row) = match failure {
+            VerifyFailure::ConstraintNotSatisfied {
+                constraint, row, ..
+            } => (self.layout.table_of_constraint(self.meta, constraint), *row),
+            VerifyFailure::Lookup {
+                lookup_index, row, ..
+            } => (self.layout.table_of_lookup(*lookup_index), *row),
+            VerifyFailure::Permutation { row, .. } => (None, *row),
+            _ => return FailureLocation::Unknown { row: None },
+        };
+
+        let table = match table {
+            Some(table) => table,
+            None => return FailureLocation::Unknown { row: Some(row) },
+        };
+
+        match table {
+            Table::Event => {
+                let index = row / EVENT_TABLE_ENTRY_ROWS as usize;
+                let step = self.slice.etable.entries().get(index).map(|entry| {
+                    let instruction = entry.get_instruction(&self.slice.itable);
+
+                    FailedStep {
+                        eid: entry.eid,
+                        fid: entry.fid,
+                        iid: entry.iid,
+                        opcode: instruction.opcode.clone(),
+                        function_name: None,
+                    }
+                });
+
+                FailureLocation::EventTable { index, step }
+            }
+            Table::Memory => {
+                let (k, slice) = (self.k, self.slice);
+                let mtable = self.mtable.get_or_insert_with(|| {
+                    MemoryWritingTable::from(k, slice.create_memory_table(memory_event_of_step))
+                });
+
+                let index = row / MEMORY_TABLE_ENTRY_ROWS as usize;
+                let entry = mtable.0.get(index).map(|entry| &entry.entry);
+
+                FailureLocation::MemoryTable {
+                    index,
+                    location: entry.map(|entry| (entry.ltype, entry.offset)),
+                    start_eid: entry.map(|entry| entry.eid),
+                }
+            }
+            Table::Frame => FailureLocation::FrameTable {
+                index: row / FrameTableValueOffset::Max as usize,
+            },
+            Table::Image => FailureLocation::ImageTable { row },
+            Table::Bit => FailureLocation::BitTable { row },
+        }
+    }
+}
+
+/// Run the mock prover and map each failure back to the table row it comes from.
+pub(crate) fn mock_test<F: FieldExt, C: Circuit<F, Config = ZkWasmCircuitConfig<F>>>(
+    k: u32,
+    circuit: &C,
+    slice: &Slice,
+    instances: Vec<F>,
+) -> Result<(), MockTestError> {
+    let prover = MockProver::run(k, circuit, vec![instances])?;
+
+    let failures = match prover.verify() {
+        Ok(()) => return Ok(()),
+        Err(failures) => failures,
+    };
+
+    // Configure the circuit again to find the gates and lookups of each table.
+    let mut meta = ConstraintSystem::default();
+    let config = C::configure(&mut meta);
+
+    let mut locator = Locator {
+        k,
+        slice,
+        meta: &meta,
+        layout: &config.layout,
+        mtable: None,
+    };
+
+    Err(MockTestError::Unsatisfied(
+        failures
+            .iter()
+            .map(|failure| MockFailure {
+                location: locator.locate(failure),
+                message: failure.to_string(),
+            })
+            .collect(),
+    ))
+}
+
+pub(crate) fn name_functions(failures: &mut [MockFailure], function_names: &HashMap<u32, String>) {
+    for failure in failures {
+        if let FailureLocation::EventTable {
+            step: Some(step), ..
+        } = &mut failure.location
+        {
+            step.function_name = function_names.get(&step.fid).cloned();
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use serde::Serialize;
+use specs::etable::EventTable;
+use specs::etable::EventTableEntry;
+use specs::jtable::FrameTable;
+use specs::jtable::INHERITED_FRAME_TABLE_ENTRIES;
+use specs::mtable::AccessType;
+use specs::mtable::LocationType;
+use specs::state::InitializationState;
+use specs::trace::TraceError;
+use specs::TableBackend;
+use specs::Tables;
+
+use super::compute_slice_capability;
+use super::etable::EVENT_TABLE_ENTRY_ROWS;
+use super::image_table::compute_maximal_pages;
+use super::image_table::PAGE_ENTRIES;
+use super::jtable::FrameTableValueOffset;
+use super::mtable::MEMORY_TABLE_ENTRY_ROWS;
+use super::utils::image_table::GLOBAL_CAPABILITY;
+use super::utils::image_table::INIT_MEMORY_ENTRIES_OFFSET;
+use super::utils::image_table::STACK_CAPABILITY;
+use super::zkwasm_circuit::RESERVE_ROWS;
+use crate::runtime::host::ForeignStatics;
+use crate::runtime::memory_event_of_step;
+
+/// Rows available to the memory table and the frame table, the blinding rows are covered by
+/// the same margin as `compute_slice_capability`.
+fn compute_available_rows(k: u32) -> usize {
+    (1 << k) - RESERVE_ROWS - 1024
+}
+
+/// A table, or a host circuit, which can't hold the execution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum Bottleneck {
+    EventTable,
+    MemoryTable,
+    FrameTable,
+    /// The instructions and the br table entries exceed the image table, regardless of K.
+    ImageTable,
+    MemoryPages,
+    HostOp(String),
+}
+
+/// Estimated usage of the circuit for a candidate K.
+#[derive(Debug, Clone, Serialize)]
+pub struct CandidateK {
+    pub k: u32,
+    /// The tables exceeded if the execution is proved in a single circuit.
+    pub bottlenecks: Vec<Bottleneck>,
+    /// Estimated number of slices with continuation, `None` if the image or the memory pages
+    /// exceed the circuit which can't be fixed by slicing.
+    pub slices: Option<usize>,
+}
+
+/// Rows of the tables used by an execution, counted from its tables as if they are proved in
+/// a single circuit.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct RowEstimate {
+    pub etable_entries: usize,
+    /// The init entries and the write entries, reading entries don't occupy the table.
+    pub mtable_entries: usize,
+    /// Including the inherited entries.
+    pub frame_table_entries: usize,
+    pub image_entries: usize,
+    pub memory_pages: u32,
+}
+
+impl RowEstimate {
+    fn record(
+        &mut self,
+        tables: &Tables,
+        locations: &mut HashSet<(LocationType, u32)>,
+        entry: &EventTableEntry,
+    ) {
+        self.etable_entries += 1;
+        self.memory_pages = self.memory_pages.max(entry.allocated_memory_pages);
+
+        for memory_entry in memory_event_of_step(entry) {
+            if memory_entry.atype == AccessType::Write {
+                self.mtable_entries += 1;
+            }
+
+            // Same as `Slice::create_memory_table`, each location accessed has an init entry
+            // if it's initialized by the image or it's on the heap.
+            let location = (memory_entry.ltype, memory_entry.offset);
+            if !locations.contains(&location)
+                && (memory_entry.ltype == LocationType::Heap
+                    || tables
+                        .compilation_tables
+                        .imtable
+                        .try_find(memory_entry.ltype, memory_entry.offset)
+                        .is_some())
+            {
+                locations.insert(location);
+                self.mtable_entries += 1;
+            }
+        }
+    }
+
+    /// The event tables and the frame tables backed by files are streamed.
+    pub fn from_tables(tables: &Tables) -> Result<Self, TraceError> {
+        let compilation_tables = &tables.compilation_tables;
+
+        let mut estimate = RowEstimate {
+            frame_table_entries: INHERITED_FRAME_TABLE_ENTRIES,
+            // Add one for default lookup value of the instructions and the br table.
+            image_entries: InitializationState::<u32>::field_count()
+                + INHERITED_FRAME_TABLE_ENTRIES
+                + compilation_tables.itable.len()
+                + 1
+                + compilation_tables.br_table.entries().len()
+                + compilation_tables.elem_table.entries().len()
+                + 1,
+            memory_pages: compilation_tables.configure_table.init_memory_pages,
+            ..Default::default()
+        };
+        let mut locations = HashSet::new();
+
+        for etable in tables.execution_tables.etable.iter() {
+            match etable {
+                TableBackend::Memory(etable) => {
+                    for entry in etable.entries() {
+                        estimate.record(tables, &mut locations, entry);
+                    }
+                }
+                TableBackend::Json(path) => {
+                    for entry in EventTable::stream(path)? {
+                        estimate.record(tables, &mut locations, &entry?);
+                    }
+                }
+            }
+        }
+
+        for frame_table in tables.execution_tables.frame_table.iter() {
+            match frame_table {
+                TableBackend::Memory(frame_table) => {
+                    estimate.frame_table_entries += frame_table.called.iter().count();
+                }
+                TableBackend::Json(path) => {
+                    for entry in FrameTable::stream(path)? {
+                        entry?;
+                        estimate.frame_table_entries += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(estimate)
+    }
+
+    pub fn etable_rows(&self) -> usize {
+        self.etable_entries * EVENT_TABLE_ENTRY_ROWS as usize
+    }
+
+    pub fn mtable_rows(&self) -> usize {
+        self.mtable_entries * MEMORY_TABLE_ENTRY_ROWS as usize
+    }
+
+    pub fn frame_table_rows(&self) -> usize {
+        self.frame_table_entries * FrameTableValueOffset::Max as usize
+    }
+
+    /// Rows of the image table with the memory pages of the execution.
+    pub fn image_table_rows(&self) -> usize {
+        INIT_MEMORY_ENTRIES_OFFSET
+            + STACK_CAPABILITY
+            + GLOBAL_CAPABILITY
+            + (self.memory_pages * PAGE_ENTRIES) as usize
+    }
+
+    /// `host_statics` are the statics of the host plugins for `k`.
+    pub fn candidate(&self, k: u32, host_statics: &HashMap<String, ForeignStatics>) -> CandidateK {
+        let slice_capability = compute_slice_capability(k) as usize;
+        let available_rows = compute_available_rows(k);
+
+        let mut bottlenecks = vec![];
+        if self.image_entries > INIT_MEMORY_ENTRIES_OFFSET {
+            bottlenecks.push(Bottleneck::ImageTable);
+        }
+        if self.memory_pages > compute_maximal_pages(k) {
+            bottlenecks.push(Bottleneck::MemoryPages);
+        }
+
+        let slices = if bottlenecks.is_empty() {
+            // The init entries are duplicated if a location is accessed by several slices,
+            // so the number of slices is a lower bound.
+            Some(
+                [
+                    self.etable_entries.div_ceil(slice_capability),
+                    self.mtable_rows().div_ceil(available_rows),
+                    // The inherited entries are repeated in each slice.
+                    (self.frame_table_entries - INHERITED_FRAME_TABLE_ENTRIES).div_ceil(
+                        available_rows / FrameTableValueOffset::Max as usize
+                            - INHERITED_FRAME_TABLE_ENTRIES,
+                    ),
+                ]
+                .into_iter()
+                .max()
+                .unwrap()
+                .max(1),
+            )
+        } else {
+            None
+        };
+
+        if self.etable_entries > slice_capability {
+            bottlenecks.push(Bottleneck::EventTable);
+        }
+        if self.mtable_rows() > available_rows {
+            bottlenecks.push(Bottleneck::MemoryTable);
+        }
+        if self.frame_table_rows() > available_rows {
+            bottlenecks.push(Bottleneck::FrameTable);
+        }
+
+        let mut host_statics = host_statics.iter().collect::<Vec<_>>();
+        host_statics.sort_by_key(|(name, _)| *name);
+        for (name, statics) in host_statics {
+            if statics.used_round > statics.max_round {
+                bottlenecks.push(Bottleneck::HostOp(name.clone()));
+            }
+        }
+
+        CandidateK {
+            k,
+            bottlenecks,
+            slices,
+        }
+    }
+
+    /// The minimal K in `candidates` which proves the execution in a single circuit.
+    pub fn minimal_k(
+        &self,
+        candidates: impl IntoIterator<Item = u32>,
+        host_statics: impl Fn(u32) -> HashMap<String, ForeignStatics>,
+    ) -> Option<u32> {
+        candidates
+            .into_iter()
+            .find(|k| self.candidate(*k, &host_statics(*k)).bottlenecks.is_empty())
+    }
+}
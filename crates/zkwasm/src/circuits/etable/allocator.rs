@@ -155,7 +155,8 @@ pub(crate) enum EventTableCellType {
     MTableLookup,
 }
 
-const BIT_COLUMNS: usize = 12;
+// The selectors of the float opcode classes take one more column.
+const BIT_COLUMNS: usize = if cfg!(feature = "float") { 13 } else { 12 };
 const U8_COLUMNS: usize = 1;
 const U32_CELLS: usize = 2;
 const U32_PERMUTATION_CELLS: usize = if cfg!(feature = "continuation") {
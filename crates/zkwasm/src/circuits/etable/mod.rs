@@ -24,6 +24,14 @@ use crate::circuits::etable::op_configure::op_call_indirect::CallIndirectConfigB
 use crate::circuits::etable::op_configure::op_const::ConstConfigBuilder;
 use crate::circuits::etable::op_configure::op_conversion::ConversionConfigBuilder;
 use crate::circuits::etable::op_configure::op_drop::DropConfigBuilder;
+#[cfg(feature = "float")]
+use crate::circuits::etable::op_configure::op_float_bin::FloatBinConfigBuilder;
+#[cfg(feature = "float")]
+use crate::circuits::etable::op_configure::op_float_conversion::FloatConversionConfigBuilder;
+#[cfg(feature = "float")]
+use crate::circuits::etable::op_configure::op_float_rel::FloatRelConfigBuilder;
+#[cfg(feature = "float")]
+use crate::circuits::etable::op_configure::op_float_unary::FloatUnaryConfigBuilder;
 use crate::circuits::etable::op_configure::op_global_get::GlobalGetConfigBuilder;
 use crate::circuits::etable::op_configure::op_global_set::GlobalSetConfigBuilder;
 use crate::circuits::etable::op_configure::op_load::LoadConfigBuilder;
@@ -38,10 +46,12 @@ use crate::circuits::etable::op_configure::op_select::SelectConfigBuilder;
 use crate::circuits::etable::op_configure::op_store::StoreConfigBuilder;
 use crate::circuits::etable::op_configure::op_test::TestConfigBuilder;
 use crate::circuits::etable::op_configure::op_unary::UnaryConfigBuilder;
+use crate::circuits::etable::op_configure::op_unreachable::UnreachableConfigBuilder;
 use crate::constant_from;
 use crate::fixed_curr;
 use crate::foreign::context::etable_op_configure::ETableContextHelperTableConfigBuilder;
 use crate::foreign::require_helper::etable_op_configure::ETableRequireHelperTableConfigBuilder;
+use crate::foreign::wasm_input_helper::circuits::WASM_INPUT_FOREIGN_TABLE_KEY;
 use crate::foreign::wasm_input_helper::etable_op_configure::ETableWasmInputHelperTableConfigBuilder;
 use crate::foreign::EventTableForeignCallConfigBuilder;
 use crate::foreign::ForeignTableConfig;
@@ -73,7 +83,7 @@ type AllocatedU32StateCell<F> = AllocatedU32PermutationCell<F>;
 type AllocatedU32StateCell<F> = AllocatedCommonRangeCell<F>;
 
 pub(crate) const EVENT_TABLE_ENTRY_ROWS: i32 = 4;
-pub(crate) const OP_CAPABILITY: usize = 32;
+pub(crate) const OP_CAPABILITY: usize = if cfg!(feature = "float") { 36 } else { 32 };
 
 const FOREIGN_LOOKUP_CAPABILITY: usize = 6;
 
@@ -214,6 +224,15 @@ pub trait EventTableOpcodeConfig<F: FieldExt> {
     fn is_external_host_call(&self, _entry: &EventTableEntry) -> bool {
         false
     }
+
+    /// A bit expression which is set if the step traps, the step must be the last one.
+    fn is_trap(&self, _meta: &mut VirtualCells<'_, F>) -> Option<Expression<F>> {
+        None
+    }
+    /// The trap code, it's looked up in the public instances at the input index of the step.
+    fn trap_code(&self, _meta: &mut VirtualCells<'_, F>) -> Option<Expression<F>> {
+        None
+    }
 }
 
 struct OpcodeConfig<F: FieldExt>(Box<dyn EventTableOpcodeConfig<F>>);
@@ -240,6 +259,7 @@ impl<F: FieldExt> EventTableConfig<F> {
         bit_table: &BitTableConfig<F>,
         external_host_call_table: &ExternalHostCallTableConfig<F>,
         foreign_table_configs: &BTreeMap<&'static str, Box<dyn ForeignTableConfig<F>>>,
+        allow_trap: bool,
     ) -> EventTableConfig<F> {
         let step_sel = meta.fixed_column();
 
@@ -385,6 +405,14 @@ impl<F: FieldExt> EventTableConfig<F> {
         configure!(OpcodeClass::MemoryGrow, MemoryGrowConfigBuilder);
         configure!(OpcodeClass::BrTable, BrTableConfigBuilder);
         configure!(OpcodeClass::CallIndirect, CallIndirectConfigBuilder);
+        configure!(OpcodeClass::Unreachable, UnreachableConfigBuilder);
+        #[cfg(feature = "float")]
+        {
+            configure!(OpcodeClass::FloatUnary, FloatUnaryConfigBuilder);
+            configure!(OpcodeClass::FloatBin, FloatBinConfigBuilder);
+            configure!(OpcodeClass::FloatRel, FloatRelConfigBuilder);
+            configure!(OpcodeClass::FloatConversion, FloatConversionConfigBuilder);
+        }
 
         macro_rules! configure_foreign {
             ($x:ident, $i:expr) => {
@@ -670,6 +698,42 @@ impl<F: FieldExt> EventTableConfig<F> {
             ]
         });
 
+        let is_trap = |meta: &mut VirtualCells<'_, F>| {
+            sum_ops_expr(meta, &|meta, config: &OpcodeConfig<F>| {
+                config.0.is_trap(meta)
+            })
+        };
+
+        if allow_trap {
+            meta.create_gate("c10a. the execution ends with a trap", |meta| {
+                vec![
+                    is_trap(meta) * enabled_cell.next_expr(meta) * fixed_curr!(meta, step_sel),
+                    enabled_cell.curr_expr(meta)
+                        * (constant_from!(1) - enabled_cell.next_expr(meta))
+                        * (constant_from!(1) - is_trap(meta))
+                        * fixed_curr!(meta, step_sel),
+                ]
+            });
+
+            foreign_table_configs
+                .get(WASM_INPUT_FOREIGN_TABLE_KEY)
+                .unwrap()
+                .configure_in_table(meta, "c10b. trap code in public instances", &|meta| {
+                    vec![
+                        is_trap(meta)
+                            * input_index_cell.curr_expr(meta)
+                            * fixed_curr!(meta, step_sel),
+                        sum_ops_expr(meta, &|meta, config: &OpcodeConfig<F>| {
+                            config.0.trap_code(meta)
+                        }) * fixed_curr!(meta, step_sel),
+                    ]
+                });
+        } else {
+            meta.create_gate("c10a. no trap", |meta| {
+                vec![is_trap(meta) * fixed_curr!(meta, step_sel)]
+            });
+        }
+
         Self {
             step_sel,
             common_config,
@@ -11,6 +11,14 @@ pub mod op_call_indirect;
 pub mod op_const;
 pub mod op_conversion;
 pub mod op_drop;
+#[cfg(feature = "float")]
+pub mod op_float_bin;
+#[cfg(feature = "float")]
+pub mod op_float_conversion;
+#[cfg(feature = "float")]
+pub mod op_float_rel;
+#[cfg(feature = "float")]
+pub mod op_float_unary;
 pub mod op_global_get;
 pub mod op_global_set;
 pub mod op_load;
@@ -25,3 +33,4 @@ pub mod op_select;
 pub mod op_store;
 pub mod op_test;
 pub mod op_unary;
+pub mod op_unreachable;
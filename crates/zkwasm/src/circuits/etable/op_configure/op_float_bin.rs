@@ -0,0 +1,200 @@
+use crate::circuits::cell::*;
+use crate::circuits::etable::allocator::*;
+use crate::circuits::etable::ConstraintBuilder;
+use crate::circuits::etable::EventTableCommonConfig;
+use crate::circuits::etable::EventTableOpcodeConfig;
+use crate::circuits::etable::EventTableOpcodeConfigBuilder;
+use crate::circuits::utils::bn_to_field;
+use crate::circuits::utils::step_status::StepStatus;
+use crate::circuits::utils::table_entry::EventTableEntryWithMemoryInfo;
+use crate::circuits::utils::Context;
+use crate::constant;
+use crate::constant_from;
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::plonk::Error;
+use halo2_proofs::plonk::Expression;
+use halo2_proofs::plonk::VirtualCells;
+use num_bigint::BigUint;
+use specs::etable::EventTableEntry;
+use specs::itable::FloatBinOp;
+use specs::itable::OpcodeClass;
+use specs::itable::OPCODE_ARG0_SHIFT;
+use specs::itable::OPCODE_ARG1_SHIFT;
+use specs::itable::OPCODE_CLASS_SHIFT;
+use specs::mtable::LocationType;
+use specs::step::StepInfo;
+use specs::types::ValueType;
+
+pub struct FloatBinConfig<F: FieldExt> {
+    is_f32: AllocatedBitCell<F>,
+
+    lhs: AllocatedU64CellWithFlagBitDyn<F>,
+    rhs: AllocatedU64CellWithFlagBitDyn<F>,
+
+    memory_table_lookup_stack_read_lhs: AllocatedMemoryTableLookupReadCell<F>,
+    memory_table_lookup_stack_read_rhs: AllocatedMemoryTableLookupReadCell<F>,
+    memory_table_lookup_stack_write: AllocatedMemoryTableLookupWriteCell<F>,
+}
+
+/// Only `copysign` is supported, the other ops are rejected by the precheck of the loader.
+pub struct FloatBinConfigBuilder {}
+
+impl<F: FieldExt> EventTableOpcodeConfigBuilder<F> for FloatBinConfigBuilder {
+    fn configure(
+        common_config: &EventTableCommonConfig<F>,
+        allocator: &mut EventTableCellAllocator<F>,
+        constraint_builder: &mut ConstraintBuilder<F>,
+    ) -> Box<dyn EventTableOpcodeConfig<F>> {
+        let is_f32 = allocator.alloc_bit_cell();
+
+        let lhs = allocator
+            .alloc_u64_with_flag_bit_cell_dyn(constraint_builder, move |meta| is_f32.expr(meta));
+        let rhs = allocator
+            .alloc_u64_with_flag_bit_cell_dyn(constraint_builder, move |meta| is_f32.expr(meta));
+
+        let eid = common_config.eid_cell;
+        let sp = common_config.sp_cell;
+
+        let memory_table_lookup_stack_read_rhs = allocator.alloc_memory_table_lookup_read_cell(
+            "op_float_bin stack read",
+            constraint_builder,
+            eid,
+            move |____| constant_from!(LocationType::Stack as u64),
+            move |meta| sp.expr(meta) + constant_from!(1),
+            move |meta| is_f32.expr(meta),
+            move |meta| rhs.u64_cell.expr(meta),
+            move |____| constant_from!(1),
+        );
+
+        let memory_table_lookup_stack_read_lhs = allocator.alloc_memory_table_lookup_read_cell(
+            "op_float_bin stack read",
+            constraint_builder,
+            eid,
+            move |____| constant_from!(LocationType::Stack as u64),
+            move |meta| sp.expr(meta) + constant_from!(2),
+            move |meta| is_f32.expr(meta),
+            move |meta| lhs.u64_cell.expr(meta),
+            move |____| constant_from!(1),
+        );
+
+        let memory_table_lookup_stack_write = allocator
+            .alloc_memory_table_lookup_write_cell_with_value(
+                "op_float_bin stack write",
+                constraint_builder,
+                eid,
+                move |____| constant_from!(LocationType::Stack as u64),
+                move |meta| sp.expr(meta) + constant_from!(2),
+                move |meta| is_f32.expr(meta),
+                move |____| constant_from!(1),
+            );
+        let res = memory_table_lookup_stack_write.value_cell;
+
+        // copysign: res = lhs - lhs_sign * 2^(bits - 1) + rhs_sign * 2^(bits - 1)
+        constraint_builder.push(
+            "op_float_bin: copysign",
+            Box::new(move |meta| {
+                let half = is_f32.expr(meta) * constant_from!(1u64 << 31)
+                    + (constant_from!(1) - is_f32.expr(meta)) * constant_from!(1u64 << 63);
+
+                vec![
+                    res.expr(meta) - lhs.u64_cell.expr(meta)
+                        + (lhs.flag_bit_cell.expr(meta) - rhs.flag_bit_cell.expr(meta)) * half,
+                ]
+            }),
+        );
+
+        Box::new(FloatBinConfig {
+            is_f32,
+            lhs,
+            rhs,
+            memory_table_lookup_stack_read_lhs,
+            memory_table_lookup_stack_read_rhs,
+            memory_table_lookup_stack_write,
+        })
+    }
+}
+
+impl<F: FieldExt> EventTableOpcodeConfig<F> for FloatBinConfig<F> {
+    fn opcode(&self, meta: &mut VirtualCells<'_, F>) -> Expression<F> {
+        let opcode_class = constant!(bn_to_field(
+            &(BigUint::from(OpcodeClass::FloatBin as u64) << OPCODE_CLASS_SHIFT)
+        ));
+        let var_type = self.is_f32.expr(meta)
+            * constant!(bn_to_field(&(BigUint::from(1u64) << OPCODE_ARG1_SHIFT)));
+        let op = constant!(bn_to_field(
+            &(BigUint::from(FloatBinOp::Copysign as u64) << OPCODE_ARG0_SHIFT)
+        ));
+
+        opcode_class + var_type + op
+    }
+
+    fn assign(
+        &self,
+        ctx: &mut Context<'_, F>,
+        step: &mut StepStatus<F>,
+        entry: &EventTableEntryWithMemoryInfo,
+    ) -> Result<(), Error> {
+        match &entry.eentry.step_info {
+            StepInfo::FloatBinOp {
+                class: FloatBinOp::Copysign,
+                vtype,
+                left,
+                right,
+                value,
+            } => {
+                let is_f32 = *vtype == ValueType::F32;
+
+                self.is_f32.assign_bool(ctx, is_f32)?;
+                self.lhs.assign(ctx, *left, is_f32)?;
+                self.rhs.assign(ctx, *right, is_f32)?;
+
+                self.memory_table_lookup_stack_read_rhs.assign(
+                    ctx,
+                    entry.memory_rw_entires[0].start_eid,
+                    step.current.eid,
+                    entry.memory_rw_entires[0].end_eid,
+                    step.current.sp + 1,
+                    LocationType::Stack,
+                    is_f32,
+                    *right,
+                )?;
+
+                self.memory_table_lookup_stack_read_lhs.assign(
+                    ctx,
+                    entry.memory_rw_entires[1].start_eid,
+                    step.current.eid,
+                    entry.memory_rw_entires[1].end_eid,
+                    step.current.sp + 2,
+                    LocationType::Stack,
+                    is_f32,
+                    *left,
+                )?;
+
+                self.memory_table_lookup_stack_write.assign(
+                    ctx,
+                    step.current.eid,
+                    entry.memory_rw_entires[2].end_eid,
+                    step.current.sp + 2,
+                    LocationType::Stack,
+                    is_f32,
+                    *value,
+                )?;
+            }
+            _ => unreachable!(),
+        }
+
+        Ok(())
+    }
+
+    fn mops(&self, _meta: &mut VirtualCells<'_, F>) -> Option<Expression<F>> {
+        Some(constant_from!(1))
+    }
+
+    fn memory_writing_ops(&self, _: &EventTableEntry) -> u32 {
+        1
+    }
+
+    fn sp_diff(&self, _meta: &mut VirtualCells<'_, F>) -> Option<Expression<F>> {
+        Some(constant!(F::one()))
+    }
+}
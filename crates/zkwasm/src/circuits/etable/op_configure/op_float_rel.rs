@@ -0,0 +1,438 @@
+use crate::circuits::cell::*;
+use crate::circuits::etable::allocator::*;
+use crate::circuits::etable::ConstraintBuilder;
+use crate::circuits::etable::EventTableCommonConfig;
+use crate::circuits::etable::EventTableOpcodeConfig;
+use crate::circuits::etable::EventTableOpcodeConfigBuilder;
+use crate::circuits::utils::bn_to_field;
+use crate::circuits::utils::step_status::StepStatus;
+use crate::circuits::utils::table_entry::EventTableEntryWithMemoryInfo;
+use crate::circuits::utils::Context;
+use crate::constant;
+use crate::constant_from;
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::plonk::Error;
+use halo2_proofs::plonk::Expression;
+use halo2_proofs::plonk::VirtualCells;
+use num_bigint::BigUint;
+use specs::etable::EventTableEntry;
+use specs::itable::FloatRelOp;
+use specs::itable::OpcodeClass;
+use specs::itable::OPCODE_ARG0_SHIFT;
+use specs::itable::OPCODE_ARG1_SHIFT;
+use specs::itable::OPCODE_CLASS_SHIFT;
+use specs::mtable::LocationType;
+use specs::step::StepInfo;
+use specs::types::ValueType;
+
+const F32_INF: u64 = 0x7f80_0000;
+const F64_INF: u64 = 0x7ff0_0000_0000_0000;
+
+pub struct FloatRelConfig<F: FieldExt> {
+    is_f32: AllocatedBitCell<F>,
+
+    is_eq: AllocatedBitCell<F>,
+    is_ne: AllocatedBitCell<F>,
+    is_lt: AllocatedBitCell<F>,
+    is_gt: AllocatedBitCell<F>,
+    is_le: AllocatedBitCell<F>,
+    is_ge: AllocatedBitCell<F>,
+
+    lhs: AllocatedU64CellWithFlagBitDyn<F>,
+    rhs: AllocatedU64CellWithFlagBitDyn<F>,
+
+    lhs_is_nan: AllocatedBitCell<F>,
+    rhs_is_nan: AllocatedBitCell<F>,
+    lhs_nan_diff: AllocatedU64Cell<F>,
+    rhs_nan_diff: AllocatedU64Cell<F>,
+    is_nan: AllocatedUnlimitedCell<F>,
+
+    lhs_key: AllocatedUnlimitedCell<F>,
+    rhs_key: AllocatedUnlimitedCell<F>,
+    key_eq: AllocatedBitCell<F>,
+    key_lt: AllocatedBitCell<F>,
+    key_diff: AllocatedU64Cell<F>,
+
+    memory_table_lookup_stack_read_lhs: AllocatedMemoryTableLookupReadCell<F>,
+    memory_table_lookup_stack_read_rhs: AllocatedMemoryTableLookupReadCell<F>,
+    memory_table_lookup_stack_write: AllocatedMemoryTableLookupWriteCell<F>,
+}
+
+pub struct FloatRelConfigBuilder {}
+
+impl<F: FieldExt> EventTableOpcodeConfigBuilder<F> for FloatRelConfigBuilder {
+    fn configure(
+        common_config: &EventTableCommonConfig<F>,
+        allocator: &mut EventTableCellAllocator<F>,
+        constraint_builder: &mut ConstraintBuilder<F>,
+    ) -> Box<dyn EventTableOpcodeConfig<F>> {
+        let is_f32 = allocator.alloc_bit_cell();
+
+        let is_eq = allocator.alloc_bit_cell();
+        let is_ne = allocator.alloc_bit_cell();
+        let is_lt = allocator.alloc_bit_cell();
+        let is_gt = allocator.alloc_bit_cell();
+        let is_le = allocator.alloc_bit_cell();
+        let is_ge = allocator.alloc_bit_cell();
+
+        let lhs = allocator
+            .alloc_u64_with_flag_bit_cell_dyn(constraint_builder, move |meta| is_f32.expr(meta));
+        let rhs = allocator
+            .alloc_u64_with_flag_bit_cell_dyn(constraint_builder, move |meta| is_f32.expr(meta));
+
+        let lhs_is_nan = allocator.alloc_bit_cell();
+        let rhs_is_nan = allocator.alloc_bit_cell();
+        let lhs_nan_diff = allocator.alloc_u64_cell();
+        let rhs_nan_diff = allocator.alloc_u64_cell();
+        let is_nan = allocator.alloc_unlimited_cell();
+
+        let lhs_key = allocator.alloc_unlimited_cell();
+        let rhs_key = allocator.alloc_unlimited_cell();
+        let key_eq = allocator.alloc_bit_cell();
+        let key_lt = allocator.alloc_bit_cell();
+        let key_diff = allocator.alloc_u64_cell();
+
+        let eid = common_config.eid_cell;
+        let sp = common_config.sp_cell;
+
+        let memory_table_lookup_stack_read_rhs = allocator.alloc_memory_table_lookup_read_cell(
+            "op_float_rel stack read",
+            constraint_builder,
+            eid,
+            move |____| constant_from!(LocationType::Stack as u64),
+            move |meta| sp.expr(meta) + constant_from!(1),
+            move |meta| is_f32.expr(meta),
+            move |meta| rhs.u64_cell.expr(meta),
+            move |____| constant_from!(1),
+        );
+
+        let memory_table_lookup_stack_read_lhs = allocator.alloc_memory_table_lookup_read_cell(
+            "op_float_rel stack read",
+            constraint_builder,
+            eid,
+            move |____| constant_from!(LocationType::Stack as u64),
+            move |meta| sp.expr(meta) + constant_from!(2),
+            move |meta| is_f32.expr(meta),
+            move |meta| lhs.u64_cell.expr(meta),
+            move |____| constant_from!(1),
+        );
+
+        let memory_table_lookup_stack_write = allocator
+            .alloc_memory_table_lookup_write_cell_with_value(
+                "op_float_rel stack write",
+                constraint_builder,
+                eid,
+                move |____| constant_from!(LocationType::Stack as u64),
+                move |meta| sp.expr(meta) + constant_from!(2),
+                move |____| constant_from!(1),
+                move |____| constant_from!(1),
+            );
+        let res = memory_table_lookup_stack_write.value_cell;
+
+        constraint_builder.push(
+            "op_float_rel: selector",
+            Box::new(move |meta| {
+                vec![
+                    is_eq.expr(meta)
+                        + is_ne.expr(meta)
+                        + is_lt.expr(meta)
+                        + is_gt.expr(meta)
+                        + is_le.expr(meta)
+                        + is_ge.expr(meta)
+                        - constant_from!(1),
+                ]
+            }),
+        );
+
+        let half = move |meta: &mut VirtualCells<'_, F>| {
+            is_f32.expr(meta) * constant_from!(1u64 << 31)
+                + (constant_from!(1) - is_f32.expr(meta)) * constant_from!(1u64 << 63)
+        };
+        let inf = move |meta: &mut VirtualCells<'_, F>| {
+            is_f32.expr(meta) * constant_from!(F32_INF)
+                + (constant_from!(1) - is_f32.expr(meta)) * constant_from!(F64_INF)
+        };
+
+        /*
+         * A value is NaN iff its magnitude (all bits except the sign bit) is greater than
+         * the bits of infinity:
+         *   is_nan:  magnitude - inf - 1 = diff
+         *   !is_nan: inf - magnitude = diff
+         */
+        constraint_builder.push(
+            "op_float_rel: nan",
+            Box::new(move |meta| {
+                let mut nan_check =
+                    |value: AllocatedU64CellWithFlagBitDyn<F>,
+                     value_is_nan: AllocatedBitCell<F>,
+                     diff: AllocatedU64Cell<F>| {
+                        let magnitude =
+                            value.u64_cell.expr(meta) - value.flag_bit_cell.expr(meta) * half(meta);
+
+                        value_is_nan.expr(meta)
+                            * (magnitude.clone()
+                                - inf(meta)
+                                - constant_from!(1)
+                                - diff.u64_cell.expr(meta))
+                            + (constant_from!(1) - value_is_nan.expr(meta))
+                                * (inf(meta) - magnitude - diff.u64_cell.expr(meta))
+                    };
+
+                vec![
+                    nan_check(lhs, lhs_is_nan, lhs_nan_diff),
+                    nan_check(rhs, rhs_is_nan, rhs_nan_diff),
+                    is_nan.expr(meta) - lhs_is_nan.expr(meta) - rhs_is_nan.expr(meta)
+                        + lhs_is_nan.expr(meta) * rhs_is_nan.expr(meta),
+                ]
+            }),
+        );
+
+        /*
+         * Map sign-magnitude onto a signed integer key so that +0 and -0 collide and the
+         * ordering of non-NaN floats matches the ordering of keys:
+         *   key = magnitude * (1 - 2 * sign)
+         */
+        constraint_builder.push(
+            "op_float_rel: key",
+            Box::new(move |meta| {
+                let mut key = |value: AllocatedU64CellWithFlagBitDyn<F>,
+                               key_cell: AllocatedUnlimitedCell<F>| {
+                    let sign = value.flag_bit_cell.expr(meta);
+                    let magnitude = value.u64_cell.expr(meta) - sign.clone() * half(meta);
+
+                    key_cell.expr(meta) - magnitude * (constant_from!(1) - constant_from!(2) * sign)
+                };
+
+                vec![key(lhs, lhs_key), key(rhs, rhs_key)]
+            }),
+        );
+
+        constraint_builder.push(
+            "op_float_rel: compare keys",
+            Box::new(move |meta| {
+                let key_gt = constant_from!(1) - key_eq.expr(meta) - key_lt.expr(meta);
+
+                vec![
+                    key_eq.expr(meta) * key_lt.expr(meta),
+                    key_eq.expr(meta) * (lhs_key.expr(meta) - rhs_key.expr(meta)),
+                    key_lt.expr(meta)
+                        * (rhs_key.expr(meta)
+                            - lhs_key.expr(meta)
+                            - constant_from!(1)
+                            - key_diff.u64_cell.expr(meta)),
+                    key_gt
+                        * (lhs_key.expr(meta)
+                            - rhs_key.expr(meta)
+                            - constant_from!(1)
+                            - key_diff.u64_cell.expr(meta)),
+                ]
+            }),
+        );
+
+        // Every comparison involving NaN is false, except ne.
+        constraint_builder.push(
+            "op_float_rel: res",
+            Box::new(move |meta| {
+                let not_nan = constant_from!(1) - is_nan.expr(meta);
+                let key_gt = constant_from!(1) - key_eq.expr(meta) - key_lt.expr(meta);
+
+                vec![
+                    is_eq.expr(meta) * (res.expr(meta) - not_nan.clone() * key_eq.expr(meta)),
+                    is_ne.expr(meta)
+                        * (res.expr(meta) - constant_from!(1)
+                            + not_nan.clone() * key_eq.expr(meta)),
+                    is_lt.expr(meta) * (res.expr(meta) - not_nan.clone() * key_lt.expr(meta)),
+                    is_gt.expr(meta) * (res.expr(meta) - not_nan.clone() * key_gt.clone()),
+                    is_le.expr(meta)
+                        * (res.expr(meta)
+                            - not_nan.clone() * (key_lt.expr(meta) + key_eq.expr(meta))),
+                    is_ge.expr(meta) * (res.expr(meta) - not_nan * (key_gt + key_eq.expr(meta))),
+                ]
+            }),
+        );
+
+        Box::new(FloatRelConfig {
+            is_f32,
+            is_eq,
+            is_ne,
+            is_lt,
+            is_gt,
+            is_le,
+            is_ge,
+            lhs,
+            rhs,
+            lhs_is_nan,
+            rhs_is_nan,
+            lhs_nan_diff,
+            rhs_nan_diff,
+            is_nan,
+            lhs_key,
+            rhs_key,
+            key_eq,
+            key_lt,
+            key_diff,
+            memory_table_lookup_stack_read_lhs,
+            memory_table_lookup_stack_read_rhs,
+            memory_table_lookup_stack_write,
+        })
+    }
+}
+
+impl<F: FieldExt> EventTableOpcodeConfig<F> for FloatRelConfig<F> {
+    fn opcode(&self, meta: &mut VirtualCells<'_, F>) -> Expression<F> {
+        macro_rules! op_expr {
+            ($op: expr, $field: ident) => {
+                self.$field.expr(meta)
+                    * constant!(bn_to_field(
+                        &(BigUint::from($op as u64) << OPCODE_ARG0_SHIFT)
+                    ))
+            };
+        }
+
+        let opcode_class = constant!(bn_to_field(
+            &(BigUint::from(OpcodeClass::FloatRel as u64) << OPCODE_CLASS_SHIFT)
+        ));
+        let var_type = self.is_f32.expr(meta)
+            * constant!(bn_to_field(&(BigUint::from(1u64) << OPCODE_ARG1_SHIFT)));
+        let op = op_expr!(FloatRelOp::Eq, is_eq)
+            + op_expr!(FloatRelOp::Ne, is_ne)
+            + op_expr!(FloatRelOp::Lt, is_lt)
+            + op_expr!(FloatRelOp::Gt, is_gt)
+            + op_expr!(FloatRelOp::Le, is_le)
+            + op_expr!(FloatRelOp::Ge, is_ge);
+
+        opcode_class + var_type + op
+    }
+
+    fn assign(
+        &self,
+        ctx: &mut Context<'_, F>,
+        step: &mut StepStatus<F>,
+        entry: &EventTableEntryWithMemoryInfo,
+    ) -> Result<(), Error> {
+        match &entry.eentry.step_info {
+            StepInfo::FloatComp {
+                class,
+                vtype,
+                left,
+                right,
+                value,
+            } => {
+                let is_f32 = *vtype == ValueType::F32;
+                let (half, inf) = if is_f32 {
+                    (1u64 << 31, F32_INF)
+                } else {
+                    (1u64 << 63, F64_INF)
+                };
+
+                self.is_f32.assign_bool(ctx, is_f32)?;
+                self.lhs.assign(ctx, *left, is_f32)?;
+                self.rhs.assign(ctx, *right, is_f32)?;
+
+                match class {
+                    FloatRelOp::Eq => self.is_eq.assign_bool(ctx, true)?,
+                    FloatRelOp::Ne => self.is_ne.assign_bool(ctx, true)?,
+                    FloatRelOp::Lt => self.is_lt.assign_bool(ctx, true)?,
+                    FloatRelOp::Gt => self.is_gt.assign_bool(ctx, true)?,
+                    FloatRelOp::Le => self.is_le.assign_bool(ctx, true)?,
+                    FloatRelOp::Ge => self.is_ge.assign_bool(ctx, true)?,
+                };
+
+                let lhs_magnitude = *left & (half - 1);
+                let rhs_magnitude = *right & (half - 1);
+                let lhs_is_nan = lhs_magnitude > inf;
+                let rhs_is_nan = rhs_magnitude > inf;
+
+                let nan_diff = |magnitude: u64, is_nan: bool| {
+                    if is_nan {
+                        magnitude - inf - 1
+                    } else {
+                        inf - magnitude
+                    }
+                };
+
+                self.lhs_is_nan.assign_bool(ctx, lhs_is_nan)?;
+                self.rhs_is_nan.assign_bool(ctx, rhs_is_nan)?;
+                self.lhs_nan_diff
+                    .assign(ctx, nan_diff(lhs_magnitude, lhs_is_nan))?;
+                self.rhs_nan_diff
+                    .assign(ctx, nan_diff(rhs_magnitude, rhs_is_nan))?;
+                self.is_nan.assign_bool(ctx, lhs_is_nan || rhs_is_nan)?;
+
+                let key = |value: u64, magnitude: u64| {
+                    if value & half != 0 {
+                        -(magnitude as i128)
+                    } else {
+                        magnitude as i128
+                    }
+                };
+                let key_to_field = |key: i128| {
+                    if key < 0 {
+                        -F::from((-key) as u64)
+                    } else {
+                        F::from(key as u64)
+                    }
+                };
+
+                let lhs_key = key(*left, lhs_magnitude);
+                let rhs_key = key(*right, rhs_magnitude);
+
+                self.lhs_key.assign(ctx, key_to_field(lhs_key))?;
+                self.rhs_key.assign(ctx, key_to_field(rhs_key))?;
+                self.key_eq.assign_bool(ctx, lhs_key == rhs_key)?;
+                self.key_lt.assign_bool(ctx, lhs_key < rhs_key)?;
+                if lhs_key != rhs_key {
+                    self.key_diff
+                        .assign(ctx, ((lhs_key - rhs_key).unsigned_abs() - 1) as u64)?;
+                }
+
+                self.memory_table_lookup_stack_read_rhs.assign(
+                    ctx,
+                    entry.memory_rw_entires[0].start_eid,
+                    step.current.eid,
+                    entry.memory_rw_entires[0].end_eid,
+                    step.current.sp + 1,
+                    LocationType::Stack,
+                    is_f32,
+                    *right,
+                )?;
+
+                self.memory_table_lookup_stack_read_lhs.assign(
+                    ctx,
+                    entry.memory_rw_entires[1].start_eid,
+                    step.current.eid,
+                    entry.memory_rw_entires[1].end_eid,
+                    step.current.sp + 2,
+                    LocationType::Stack,
+                    is_f32,
+                    *left,
+                )?;
+
+                self.memory_table_lookup_stack_write.assign(
+                    ctx,
+                    step.current.eid,
+                    entry.memory_rw_entires[2].end_eid,
+                    step.current.sp + 2,
+                    LocationType::Stack,
+                    true,
+                    *value as u64,
+                )?;
+            }
+            _ => unreachable!(),
+        }
+
+        Ok(())
+    }
+
+    fn mops(&self, _meta: &mut VirtualCells<'_, F>) -> Option<Expression<F>> {
+        Some(constant_from!(1))
+    }
+
+    fn memory_writing_ops(&self, _: &EventTableEntry) -> u32 {
+        1
+    }
+
+    fn sp_diff(&self, _meta: &mut VirtualCells<'_, F>) -> Option<Expression<F>> {
+        Some(constant!(F::one()))
+    }
+}
@@ -0,0 +1,202 @@
+use crate::circuits::cell::*;
+use crate::circuits::etable::allocator::*;
+use crate::circuits::etable::ConstraintBuilder;
+use crate::circuits::etable::EventTableCommonConfig;
+use crate::circuits::etable::EventTableOpcodeConfig;
+use crate::circuits::etable::EventTableOpcodeConfigBuilder;
+use crate::circuits::utils::bn_to_field;
+use crate::circuits::utils::step_status::StepStatus;
+use crate::circuits::utils::table_entry::EventTableEntryWithMemoryInfo;
+use crate::circuits::utils::Context;
+use crate::constant;
+use crate::constant_from;
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::plonk::Error;
+use halo2_proofs::plonk::Expression;
+use halo2_proofs::plonk::VirtualCells;
+use num_bigint::BigUint;
+use specs::etable::EventTableEntry;
+use specs::itable::FloatUnaryOp;
+use specs::itable::OpcodeClass;
+use specs::itable::OPCODE_ARG0_SHIFT;
+use specs::itable::OPCODE_ARG1_SHIFT;
+use specs::itable::OPCODE_CLASS_SHIFT;
+use specs::mtable::LocationType;
+use specs::step::StepInfo;
+use specs::types::ValueType;
+
+pub struct FloatUnaryConfig<F: FieldExt> {
+    is_f32: AllocatedBitCell<F>,
+
+    is_abs: AllocatedBitCell<F>,
+    is_neg: AllocatedBitCell<F>,
+
+    operand: AllocatedU64CellWithFlagBitDyn<F>,
+
+    memory_table_lookup_stack_read: AllocatedMemoryTableLookupReadCell<F>,
+    memory_table_lookup_stack_write: AllocatedMemoryTableLookupWriteCell<F>,
+}
+
+/// Only `abs` and `neg` are supported, the other ops are rejected by the precheck of the loader.
+pub struct FloatUnaryConfigBuilder {}
+
+impl<F: FieldExt> EventTableOpcodeConfigBuilder<F> for FloatUnaryConfigBuilder {
+    fn configure(
+        common_config: &EventTableCommonConfig<F>,
+        allocator: &mut EventTableCellAllocator<F>,
+        constraint_builder: &mut ConstraintBuilder<F>,
+    ) -> Box<dyn EventTableOpcodeConfig<F>> {
+        let is_f32 = allocator.alloc_bit_cell();
+
+        let is_abs = allocator.alloc_bit_cell();
+        let is_neg = allocator.alloc_bit_cell();
+
+        let operand = allocator
+            .alloc_u64_with_flag_bit_cell_dyn(constraint_builder, move |meta| is_f32.expr(meta));
+
+        let eid = common_config.eid_cell;
+        let sp = common_config.sp_cell;
+
+        let memory_table_lookup_stack_read = allocator.alloc_memory_table_lookup_read_cell(
+            "op_float_unary stack read",
+            constraint_builder,
+            eid,
+            move |____| constant_from!(LocationType::Stack as u64),
+            move |meta| sp.expr(meta) + constant_from!(1),
+            move |meta| is_f32.expr(meta),
+            move |meta| operand.u64_cell.expr(meta),
+            move |____| constant_from!(1),
+        );
+
+        let memory_table_lookup_stack_write = allocator
+            .alloc_memory_table_lookup_write_cell_with_value(
+                "op_float_unary stack write",
+                constraint_builder,
+                eid,
+                move |____| constant_from!(LocationType::Stack as u64),
+                move |meta| sp.expr(meta) + constant_from!(1),
+                move |meta| is_f32.expr(meta),
+                move |____| constant_from!(1),
+            );
+        let result = memory_table_lookup_stack_write.value_cell;
+
+        constraint_builder.push(
+            "op_float_unary: selector",
+            Box::new(move |meta| vec![is_abs.expr(meta) + is_neg.expr(meta) - constant_from!(1)]),
+        );
+
+        /*
+         * abs and neg only touch the sign bit:
+         *   abs: result = operand - sign * 2^(bits - 1)
+         *   neg: result = operand + (1 - 2 * sign) * 2^(bits - 1)
+         */
+        constraint_builder.push(
+            "op_float_unary: abs/neg",
+            Box::new(move |meta| {
+                let half = is_f32.expr(meta) * constant_from!(1u64 << 31)
+                    + (constant_from!(1) - is_f32.expr(meta)) * constant_from!(1u64 << 63);
+                let sign = operand.flag_bit_cell.expr(meta);
+
+                vec![
+                    is_abs.expr(meta)
+                        * (result.expr(meta) - operand.u64_cell.expr(meta)
+                            + sign.clone() * half.clone()),
+                    is_neg.expr(meta)
+                        * (result.expr(meta)
+                            - operand.u64_cell.expr(meta)
+                            - (constant_from!(1) - constant_from!(2) * sign) * half),
+                ]
+            }),
+        );
+
+        Box::new(FloatUnaryConfig {
+            is_f32,
+            is_abs,
+            is_neg,
+            operand,
+            memory_table_lookup_stack_read,
+            memory_table_lookup_stack_write,
+        })
+    }
+}
+
+impl<F: FieldExt> EventTableOpcodeConfig<F> for FloatUnaryConfig<F> {
+    fn opcode(&self, meta: &mut VirtualCells<'_, F>) -> Expression<F> {
+        macro_rules! op_expr {
+            ($op: expr, $field: ident) => {
+                self.$field.expr(meta)
+                    * constant!(bn_to_field(
+                        &(BigUint::from($op as u64) << OPCODE_ARG0_SHIFT)
+                    ))
+            };
+        }
+
+        let opcode_class = constant!(bn_to_field(
+            &(BigUint::from(OpcodeClass::FloatUnary as u64) << OPCODE_CLASS_SHIFT)
+        ));
+        let var_type = self.is_f32.expr(meta)
+            * constant!(bn_to_field(&(BigUint::from(1u64) << OPCODE_ARG1_SHIFT)));
+        let op = op_expr!(FloatUnaryOp::Abs, is_abs) + op_expr!(FloatUnaryOp::Neg, is_neg);
+
+        opcode_class + var_type + op
+    }
+
+    fn assign(
+        &self,
+        ctx: &mut Context<'_, F>,
+        step: &mut StepStatus<F>,
+        entry: &EventTableEntryWithMemoryInfo,
+    ) -> Result<(), Error> {
+        match &entry.eentry.step_info {
+            StepInfo::FloatUnaryOp {
+                class,
+                vtype,
+                operand,
+                result,
+            } => {
+                let is_f32 = *vtype == ValueType::F32;
+
+                self.is_f32.assign_bool(ctx, is_f32)?;
+                self.operand.assign(ctx, *operand, is_f32)?;
+
+                match class {
+                    FloatUnaryOp::Abs => self.is_abs.assign_bool(ctx, true)?,
+                    FloatUnaryOp::Neg => self.is_neg.assign_bool(ctx, true)?,
+                    _ => unreachable!(),
+                };
+
+                self.memory_table_lookup_stack_read.assign(
+                    ctx,
+                    entry.memory_rw_entires[0].start_eid,
+                    step.current.eid,
+                    entry.memory_rw_entires[0].end_eid,
+                    step.current.sp + 1,
+                    LocationType::Stack,
+                    is_f32,
+                    *operand,
+                )?;
+
+                self.memory_table_lookup_stack_write.assign(
+                    ctx,
+                    step.current.eid,
+                    entry.memory_rw_entires[1].end_eid,
+                    step.current.sp + 1,
+                    LocationType::Stack,
+                    is_f32,
+                    *result,
+                )?;
+            }
+            _ => unreachable!(),
+        }
+
+        Ok(())
+    }
+
+    fn mops(&self, _meta: &mut VirtualCells<'_, F>) -> Option<Expression<F>> {
+        Some(constant_from!(1))
+    }
+
+    fn memory_writing_ops(&self, _: &EventTableEntry) -> u32 {
+        1
+    }
+}
@@ -1,8 +1,8 @@
 use crate::circuits::utils::Context;
 use crate::error::BuildingCircuitError;
+use crate::error::MockTestError;
 
 use halo2_proofs::arithmetic::FieldExt;
-use halo2_proofs::dev::MockProver;
 use halo2_proofs::plonk::ConstraintSystem;
 use halo2_proofs::plonk::Expression;
 use halo2_proofs::plonk::VirtualCells;
@@ -30,6 +30,8 @@ pub mod post_image_table;
 #[path = "./post_image_table/trivial.rs"]
 pub mod post_image_table;
 
+pub mod diagnostic;
+pub mod estimate;
 pub mod image_table;
 pub mod jtable;
 pub mod rtable;
@@ -138,14 +140,39 @@ impl<F: FieldExt> LastSliceCircuit<F> {
         })
     }
 }
+/// The last slice of an execution terminated by a trap, it proves that the execution traps
+/// instead of returning from the entry.
+pub struct TrapSliceCircuit<F: FieldExt> {
+    pub k: u32,
+    pub slice: Slice,
+    _data: PhantomData<F>,
+}
+
+impl<F: FieldExt> TrapSliceCircuit<F> {
+    pub fn new(k: u32, slice: Slice) -> Result<Self, BuildingCircuitError> {
+        let circuit = LastSliceCircuit::<F>::new(k, slice)?;
+
+        Ok(TrapSliceCircuit {
+            k: circuit.k,
+            slice: circuit.slice,
+            _data: PhantomData,
+        })
+    }
+}
+
 pub enum ZkWasmCircuit<F: FieldExt> {
     Ongoing(OngoingCircuit<F>),
     LastSliceCircuit(LastSliceCircuit<F>),
+    TrapSliceCircuit(TrapSliceCircuit<F>),
 }
 
 impl<F: FieldExt> ZkWasmCircuit<F> {
     pub fn new(k: u32, slice: Slice) -> Result<Self, BuildingCircuitError> {
-        if slice.is_last_slice {
+        if slice.trap().is_some() {
+            Ok(ZkWasmCircuit::TrapSliceCircuit(TrapSliceCircuit::new(
+                k, slice,
+            )?))
+        } else if slice.is_last_slice {
             Ok(ZkWasmCircuit::LastSliceCircuit(LastSliceCircuit::new(
                 k, slice,
             )?))
@@ -154,19 +181,19 @@ impl<F: FieldExt> ZkWasmCircuit<F> {
         }
     }
 
-    pub fn mock_test(&self, instances: Vec<F>) -> anyhow::Result<()> {
+    /// Run the mock prover, the failures are mapped back to the rows of the tables.
+    pub fn mock_test(&self, instances: Vec<F>) -> Result<(), MockTestError> {
         match self {
             ZkWasmCircuit::Ongoing(circuit) => {
-                let prover = MockProver::run(circuit.k, circuit, vec![instances])?;
-                assert_eq!(prover.verify(), Ok(()));
+                diagnostic::mock_test(circuit.k, circuit, &circuit.slice, instances)
             }
             ZkWasmCircuit::LastSliceCircuit(circuit) => {
-                let prover = MockProver::run(circuit.k, circuit, vec![instances])?;
-                assert_eq!(prover.verify(), Ok(()));
+                diagnostic::mock_test(circuit.k, circuit, &circuit.slice, instances)
+            }
+            ZkWasmCircuit::TrapSliceCircuit(circuit) => {
+                diagnostic::mock_test(circuit.k, circuit, &circuit.slice, instances)
             }
         }
-
-        Ok(())
     }
 }
 
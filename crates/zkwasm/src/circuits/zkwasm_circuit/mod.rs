@@ -26,6 +26,8 @@ use crate::circuits::bit_table::BitTableChip;
 use crate::circuits::bit_table::BitTableConfig;
 use crate::circuits::bit_table::BitTableTrait;
 use crate::circuits::compute_slice_capability;
+use crate::circuits::diagnostic::Table;
+use crate::circuits::diagnostic::TableLayout;
 use crate::circuits::etable::EventTableChip;
 use crate::circuits::etable::EventTableConfig;
 use crate::circuits::external_host_call_table::ExternalHostCallChip;
@@ -60,6 +62,7 @@ use super::jtable::FrameEtablePermutationCells;
 use super::post_image_table::PostImageTableConfig;
 use super::LastSliceCircuit;
 use super::OngoingCircuit;
+use super::TrapSliceCircuit;
 
 pub const VAR_COLUMNS: usize = 40;
 
@@ -97,10 +100,12 @@ pub struct ZkWasmCircuitConfig<F: FieldExt> {
     foreign_table_from_zero_index: Column<Fixed>,
 
     blinding_factors: usize,
+    /// The gates and lookups of each table, for the failures of the mock prover.
+    pub(super) layout: TableLayout,
 }
 
 macro_rules! impl_zkwasm_circuit {
-    ($name:ident, $last_slice:expr) => {
+    ($name:ident, $last_slice:expr, $allow_trap:expr) => {
         impl<F: FieldExt> Circuit<F> for $name<F> {
             type Config = ZkWasmCircuitConfig<F>;
 
@@ -166,25 +171,38 @@ macro_rules! impl_zkwasm_circuit {
 
                 let mut cols = [(); VAR_COLUMNS].map(|_| meta.advice_column()).into_iter();
 
+                let mut layout = TableLayout::default();
+
                 let rtable = RangeTableConfig::configure(meta);
-                let image_table = ImageTableConfig::configure(meta, memory_addr_sel);
-                let mtable = MemoryTableConfig::configure(
-                    meta,
-                    (l_0, l_active, l_active_last),
-                    &mut cols,
-                    &rtable,
-                    &image_table,
-                );
-                let frame_table = JumpTableConfig::configure(meta, $last_slice);
-                let post_image_table = PostImageTableConfig::configure(
-                    meta,
-                    memory_addr_sel,
-                    &mtable,
-                    &frame_table,
-                    &image_table,
-                );
+                let image_table = layout.configure(meta, Table::Image, |meta| {
+                    ImageTableConfig::configure(meta, memory_addr_sel)
+                });
+                let mtable = layout.configure(meta, Table::Memory, |meta| {
+                    MemoryTableConfig::configure(
+                        meta,
+                        (l_0, l_active, l_active_last),
+                        &mut cols,
+                        &rtable,
+                        &image_table,
+                    )
+                });
+                // The frames are not returned if the execution is terminated by a trap.
+                let frame_table = layout.configure(meta, Table::Frame, |meta| {
+                    JumpTableConfig::configure(meta, $last_slice && !$allow_trap)
+                });
+                let post_image_table = layout.configure(meta, Table::Image, |meta| {
+                    PostImageTableConfig::configure(
+                        meta,
+                        memory_addr_sel,
+                        &mtable,
+                        &frame_table,
+                        &image_table,
+                    )
+                });
                 let external_host_call_table = ExternalHostCallTableConfig::configure(meta);
-                let bit_table = BitTableConfig::configure(meta, &rtable);
+                let bit_table = layout.configure(meta, Table::Bit, |meta| {
+                    BitTableConfig::configure(meta, &rtable)
+                });
 
                 let wasm_input_helper_table =
                     WasmInputHelperTableConfig::configure(meta, foreign_table_from_zero_index);
@@ -202,18 +220,21 @@ macro_rules! impl_zkwasm_circuit {
                     Box::new(context_helper_table.clone()),
                 );
 
-                let etable = EventTableConfig::configure(
-                    meta,
-                    (l_0, l_active, l_active_last),
-                    &mut cols,
-                    &rtable,
-                    &image_table,
-                    &mtable,
-                    &frame_table,
-                    &bit_table,
-                    &external_host_call_table,
-                    &foreign_table_configs,
-                );
+                let etable = layout.configure(meta, Table::Event, |meta| {
+                    EventTableConfig::configure(
+                        meta,
+                        (l_0, l_active, l_active_last),
+                        &mut cols,
+                        &rtable,
+                        &image_table,
+                        &mtable,
+                        &frame_table,
+                        &bit_table,
+                        &external_host_call_table,
+                        &foreign_table_configs,
+                        $allow_trap,
+                    )
+                });
 
                 assert_eq!(cols.count(), 0);
 
@@ -231,6 +252,7 @@ macro_rules! impl_zkwasm_circuit {
                     foreign_table_from_zero_index,
 
                     blinding_factors: meta.blinding_factors(),
+                    layout,
                 }
             }
 
@@ -612,5 +634,6 @@ macro_rules! impl_zkwasm_circuit {
     };
 }
 
-impl_zkwasm_circuit!(OngoingCircuit, false);
-impl_zkwasm_circuit!(LastSliceCircuit, true);
+impl_zkwasm_circuit!(OngoingCircuit, false, false);
+impl_zkwasm_circuit!(LastSliceCircuit, true, false);
+impl_zkwasm_circuit!(TrapSliceCircuit, true, true);
@@ -0,0 +1,315 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::Arc;
+
+use specs::etable::EventTable;
+use specs::etable::EventTableEntry;
+use specs::itable::InstructionTable;
+use specs::itable::InstructionTableEntry;
+use specs::jtable::FrameTable;
+use specs::jtable::FrameTableEntryInternal;
+use specs::jtable::InheritedFrameTable;
+use specs::mtable::LocationType;
+use specs::mtable::MemoryTableEntry;
+use specs::trace::TraceError;
+use specs::TableBackend;
+use specs::Tables;
+
+use crate::loader::Module;
+use crate::runtime::memory_event_of_step;
+
+/// Function names from the name section of the image, indexed by fid.
+pub fn function_names(module: &Module) -> HashMap<u32, String> {
+    module
+        .module()
+        .names_section()
+        .and_then(|names| names.functions())
+        .map_or(HashMap::default(), |functions| {
+            functions
+                .names()
+                .iter()
+                .map(|(fid, name)| (fid, name.clone()))
+                .collect()
+        })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Break on any instruction of the function.
+    Function(String),
+    /// Break on the instruction `iid` of any function.
+    Iid(u32),
+    /// Break on any access to the byte `address` of the linear memory.
+    Memory(u32),
+}
+
+impl Display for Breakpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Breakpoint::Function(name) => write!(f, "function {}", name),
+            Breakpoint::Iid(iid) => write!(f, "iid {}", iid),
+            Breakpoint::Memory(address) => write!(f, "memory {:#x}", address),
+        }
+    }
+}
+
+/// A frame of the call stack, `iid` is the instruction being executed in the function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallFrame {
+    pub fid: u32,
+    pub iid: u32,
+}
+
+type Location = (LocationType, u32);
+
+/// Step through the event table of an execution.
+///
+/// The debugger stands before the step at `position`, i.e. the memory events of all the
+/// previous steps are applied. The value stack and the memory are reconstructed from the
+/// memory events, so the memory is only known at the addresses accessed before.
+pub struct Debugger {
+    itable: Arc<InstructionTable>,
+    etable: Vec<EventTableEntry>,
+    frames: HashMap<u32, FrameTableEntryInternal>,
+    function_names: HashMap<u32, String>,
+    breakpoints: Vec<Breakpoint>,
+
+    position: usize,
+    locations: BTreeMap<Location, u64>,
+    // The previous values of the locations updated by each applied step.
+    undo_logs: Vec<Vec<(Location, Option<u64>)>>,
+}
+
+impl Debugger {
+    /// Event tables and frame tables of all slices are loaded.
+    pub fn new(tables: &Tables, function_names: HashMap<u32, String>) -> Result<Self, TraceError> {
+        let mut etable = vec![];
+        for table in tables.execution_tables.etable.iter() {
+            match table {
+                TableBackend::Memory(table) => etable.extend_from_slice(table.entries()),
+                TableBackend::Json(path) => etable.append(&mut EventTable::read(path)?.unwrap()),
+            }
+        }
+
+        let mut frames = HashMap::new();
+        let mut insert_frames = |frame_table: &FrameTable| {
+            let inherited: InheritedFrameTable =
+                (*frame_table.inherited).clone().try_into().unwrap();
+
+            for entry in inherited.iter().filter_map(|entry| entry.0) {
+                frames.insert(entry.frame_id, entry);
+            }
+            for entry in frame_table.called.iter() {
+                frames.insert(entry.0.frame_id, entry.0);
+            }
+        };
+        for table in tables.execution_tables.frame_table.iter() {
+            match table {
+                TableBackend::Memory(table) => insert_frames(table),
+                TableBackend::Json(path) => insert_frames(&FrameTable::read(path)?),
+            }
+        }
+
+        Ok(Debugger {
+            itable: tables.compilation_tables.itable.clone(),
+            etable,
+            frames,
+            function_names,
+            breakpoints: vec![],
+
+            position: 0,
+            locations: BTreeMap::new(),
+            undo_logs: vec![],
+        })
+    }
+
+    /// The number of steps.
+    pub fn len(&self) -> usize {
+        self.etable.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.etable.is_empty()
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The step to be executed, `None` if all steps are executed.
+    pub fn current(&self) -> Option<&EventTableEntry> {
+        self.etable.get(self.position)
+    }
+
+    pub fn instruction(&self) -> Option<&InstructionTableEntry> {
+        self.current()
+            .map(|entry| entry.get_instruction(&self.itable))
+    }
+
+    pub fn function_name(&self, fid: u32) -> Option<&str> {
+        self.function_names.get(&fid).map(|name| name.as_str())
+    }
+
+    /// Memory events of the current step.
+    pub fn memory_events(&self) -> Vec<MemoryTableEntry> {
+        self.current().map_or(vec![], memory_event_of_step)
+    }
+
+    /// Execute the current step, returns false if all steps are executed.
+    pub fn step_forward(&mut self) -> bool {
+        let events = match self.current() {
+            Some(entry) => memory_event_of_step(entry),
+            None => return false,
+        };
+
+        let mut undo_log = vec![];
+        for event in events {
+            let location = (event.ltype, event.offset);
+            undo_log.push((location, self.locations.insert(location, event.value)));
+        }
+
+        self.undo_logs.push(undo_log);
+        self.position += 1;
+
+        true
+    }
+
+    /// Revert the previous step, returns false if no step is executed.
+    pub fn step_backward(&mut self) -> bool {
+        let undo_log = match self.undo_logs.pop() {
+            Some(undo_log) => undo_log,
+            None => return false,
+        };
+
+        for (location, value) in undo_log.into_iter().rev() {
+            match value {
+                Some(value) => self.locations.insert(location, value),
+                None => self.locations.remove(&location),
+            };
+        }
+        self.position -= 1;
+
+        true
+    }
+
+    /// Move to the step `eid`, returns false if the step doesn't exist.
+    pub fn seek(&mut self, eid: u32) -> bool {
+        let target = match self.etable.binary_search_by_key(&eid, |entry| entry.eid) {
+            Ok(target) => target,
+            Err(_) => return false,
+        };
+
+        while self.position < target {
+            self.step_forward();
+        }
+        while self.position > target {
+            self.step_backward();
+        }
+
+        true
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.push(breakpoint);
+    }
+
+    pub fn remove_breakpoint(&mut self, index: usize) -> Option<Breakpoint> {
+        (index < self.breakpoints.len()).then(|| self.breakpoints.remove(index))
+    }
+
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    /// The first breakpoint hit by the current step.
+    pub fn hit_breakpoint(&self) -> Option<&Breakpoint> {
+        let entry = self.current()?;
+
+        self.breakpoints.iter().find(|breakpoint| match breakpoint {
+            Breakpoint::Function(name) => self.function_name(entry.fid) == Some(name.as_str()),
+            Breakpoint::Iid(iid) => entry.iid == *iid,
+            Breakpoint::Memory(address) => memory_event_of_step(entry)
+                .iter()
+                .any(|event| event.ltype == LocationType::Heap && event.offset == address / 8),
+        })
+    }
+
+    /// Step forward until a breakpoint is hit or all steps are executed.
+    pub fn continue_forward(&mut self) -> Option<&Breakpoint> {
+        while self.step_forward() {
+            if self.hit_breakpoint().is_some() {
+                return self.hit_breakpoint();
+            }
+        }
+
+        None
+    }
+
+    /// Step backward until a breakpoint is hit or the first step is reached.
+    pub fn continue_backward(&mut self) -> Option<&Breakpoint> {
+        while self.step_backward() {
+            if self.hit_breakpoint().is_some() {
+                return self.hit_breakpoint();
+            }
+        }
+
+        None
+    }
+
+    /// The value stack before the current step, the top of the stack comes first.
+    pub fn value_stack(&self) -> Vec<u64> {
+        let sp = match self.current() {
+            Some(entry) => entry.sp,
+            None => return vec![],
+        };
+
+        self.locations
+            .range((LocationType::Stack, sp + 1)..=(LocationType::Stack, u32::MAX))
+            .map(|(_, value)| *value)
+            .collect()
+    }
+
+    /// The 8-byte block of the linear memory which contains `address`, `None` if the block has
+    /// not been accessed yet.
+    pub fn memory(&self, address: u32) -> Option<u64> {
+        self.locations
+            .get(&(LocationType::Heap, address / 8))
+            .copied()
+    }
+
+    /// The global `index`, `None` if it has not been accessed yet.
+    pub fn global(&self, index: u32) -> Option<u64> {
+        self.locations.get(&(LocationType::Global, index)).copied()
+    }
+
+    /// The call stack of the current step, the innermost frame comes first.
+    pub fn call_stack(&self) -> Vec<CallFrame> {
+        let entry = match self.current() {
+            Some(entry) => entry,
+            None => return vec![],
+        };
+
+        let mut call_stack = vec![CallFrame {
+            fid: entry.fid,
+            iid: entry.iid,
+        }];
+
+        // The frame id is the eid of the call, 0 is reserved for the entry function.
+        let mut frame_id = entry.last_jump_eid;
+        while frame_id != 0 {
+            match self.frames.get(&frame_id) {
+                Some(frame) if call_stack.len() <= self.frames.len() => {
+                    call_stack.push(CallFrame {
+                        fid: frame.fid,
+                        iid: frame.iid,
+                    });
+                    frame_id = frame.next_frame_id;
+                }
+                _ => break,
+            }
+        }
+
+        call_stack
+    }
+}
@@ -1,10 +1,49 @@
+use std::collections::HashMap;
+
+use halo2_proofs::plonk;
+use specs::trace::TraceError;
 use thiserror::Error;
+use wasmi::HostError;
+
+use crate::circuits::diagnostic::name_functions;
+use crate::circuits::diagnostic::MockFailure;
 
 #[derive(Debug, Error)]
 pub enum CompilationError {}
 
-#[derive(Debug, Error)]
-pub enum ExecutionError {}
+/// Errors raised by the host functions, they trap the execution with `wasmi::Trap::Host` and
+/// are returned by `ZkWasmLoader::run`. `eid` is the eid of the host call.
+#[derive(Debug, Clone, Error)]
+pub enum ExecutionError {
+    #[error("Failed to read public input at eid {0}, please check your inputs.")]
+    PublicInputExhausted(usize),
+    #[error("Failed to read private input at eid {0}, the private inputs run out at byte offset {1}, please check your inputs.")]
+    PrivateInputExhausted(usize, u64),
+    #[error("Failed to read private input at eid {0}, the private inputs end in the middle of a word at byte offset {1}.")]
+    PrivateInputTruncated(usize, u64),
+    #[error("Failed to read private input at eid {0} from byte offset {1}: {2}")]
+    PrivateInputUnreadable(usize, u64, String),
+    #[error("Invalid argument {0} of wasm_input at eid {1}, expect 0 for private inputs or 1 for public inputs.")]
+    InvalidInputKind(i32, usize),
+    #[error("Failed to read context input at eid {0}, please check your context inputs.")]
+    ContextUnderflow(usize),
+    #[error("require is not satisfied at eid {0}, which is a false assertion in the wasm code. Please check the logic of your image or input.")]
+    RequireFailed(usize),
+    #[error("Failed to pop witness at eid {0}, the witness buffer is empty.")]
+    WitnessUnderflow(usize),
+    #[error("Failed to pop witness {0} at eid {1}, the witness buffer is empty.")]
+    IndexedWitnessUnderflow(u64, usize),
+    #[error("Output channel {0} selected at eid {1} is not configured.")]
+    UnknownOutputChannel(u64, usize),
+    #[error("Failed to push output at eid {0}, no output channel is selected.")]
+    OutputChannelNotSelected(usize),
+    #[error("Failed to push output at eid {1}, the output channel {0} is committed, please write it by wasm_output.")]
+    OutputChannelCommitted(String, usize),
+    #[error("Failed to write wasm_output at eid {1}, the selected output channel {0} is not committed, please write it by wasm_output_channel_push.")]
+    OutputChannelNotCommitted(String, usize),
+}
+
+impl HostError for ExecutionError {}
 
 #[derive(Debug, Error)]
 pub enum BuildingCircuitError {
@@ -14,4 +53,38 @@ pub enum BuildingCircuitError {
     PagesExceedLimit(u32, u32, u32),
     #[error("Etable entries({0}) exceed the limit({1}). Current K is {2}, consider increasing the circuit size K.")]
     EtableEntriesExceedLimit(u32, u32, u32),
+    #[error("The state of slice {0} is required to resume from slice {1}.")]
+    MissingSliceState(usize, usize),
+    #[error("Cannot resume from slice {0}, there are only {1} slice(s).")]
+    ResumeIndexOutOfRange(usize, usize),
+    #[error("Failed to process the trace: {0}")]
+    Trace(#[from] TraceError),
+}
+
+#[derive(Debug, Error)]
+pub enum MockTestError {
+    #[error(transparent)]
+    BuildingCircuit(#[from] BuildingCircuitError),
+    #[error("Failed to synthesize the circuit: {0}")]
+    Synthesis(#[from] plonk::Error),
+    #[error("The mock prover rejected the circuit with {} failure(s):{}", .0.len(), display_failures(.0))]
+    Unsatisfied(Vec<MockFailure>),
+}
+
+fn display_failures(failures: &[MockFailure]) -> String {
+    failures
+        .iter()
+        .map(|failure| format!("\n  {}", failure))
+        .collect()
+}
+
+impl MockTestError {
+    /// Attach the function names, e.g. from `debugger::function_names`, to the failed steps.
+    pub fn with_function_names(mut self, function_names: &HashMap<u32, String>) -> Self {
+        if let MockTestError::Unsatisfied(failures) = &mut self {
+            name_functions(failures, function_names);
+        }
+
+        self
+    }
 }
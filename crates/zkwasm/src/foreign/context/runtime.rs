@@ -5,6 +5,7 @@ use specs::host_function::Signature;
 use specs::types::ValueType;
 use wasmi::RuntimeArgs;
 
+use crate::error::ExecutionError;
 use crate::runtime::host::host_env::HostEnv;
 use crate::runtime::host::ForeignContext;
 
@@ -30,10 +31,8 @@ impl Context {
         self.outputs.push(value)
     }
 
-    pub fn read_context(&mut self) -> u64 {
-        self.inputs
-            .pop()
-            .expect("Failed to pop value from context_in array, please check you inputs")
+    pub fn read_context(&mut self) -> Option<u64> {
+        self.inputs.pop()
     }
 }
 
@@ -59,10 +58,13 @@ pub fn register_context_foreign(env: &mut HostEnv, context_input: Vec<u64>) {
         HostPlugin::Context,
         Op::ReadContext as usize,
         Rc::new(
-            |_obs, context: &mut dyn ForeignContext, _args: RuntimeArgs| {
+            |obs, context: &mut dyn ForeignContext, _args: RuntimeArgs| {
                 let context = context.downcast_mut::<Context>().unwrap();
 
-                Some(wasmi::RuntimeValue::I64(context.read_context() as i64))
+                context
+                    .read_context()
+                    .map(|value| Some(wasmi::RuntimeValue::I64(value as i64)))
+                    .ok_or(ExecutionError::ContextUnderflow(obs.current_eid()))
             },
         ),
     );
@@ -82,7 +84,7 @@ pub fn register_context_foreign(env: &mut HostEnv, context_input: Vec<u64>) {
                 let value: i64 = args.nth(0);
                 context.write_context(value as u64);
 
-                None
+                Ok(None)
             },
         ),
     );
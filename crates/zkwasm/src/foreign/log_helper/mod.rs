@@ -19,16 +19,16 @@ pub fn register_log_foreign(env: &mut HostEnv) {
     let print = Rc::new(
         |_observer: &Observer, _context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
             let value: u64 = args.nth(0);
-            println!("{}", value);
-            None
+            eprintln!("{}", value);
+            Ok(None)
         },
     );
 
     let printchar = Rc::new(
         |_observer: &Observer, _context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
             let value: u64 = args.nth(0);
-            print!("{}", value as u8 as char);
-            None
+            eprint!("{}", value as u8 as char);
+            Ok(None)
         },
     );
 
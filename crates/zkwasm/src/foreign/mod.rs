@@ -10,6 +10,7 @@ use halo2_proofs::plonk::VirtualCells;
 
 pub mod context;
 pub mod log_helper;
+pub mod output_helper;
 pub mod require_helper;
 pub mod wasm_input_helper;
 
@@ -0,0 +1,225 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::rc::Rc;
+
+use serde::Deserialize;
+use serde::Serialize;
+use specs::external_host_call_table::ExternalHostCallSignature;
+use specs::host_function::HostPlugin;
+
+use crate::error::ExecutionError;
+use crate::foreign::wasm_input_helper::runtime::Context as WasmInputContext;
+use crate::runtime::host::host_env::HostEnv;
+use crate::runtime::host::ForeignContext;
+use crate::runtime::monitor::observer::Observer;
+
+// The ops have no host circuit, they are placed after the ones of `ForeignInst`.
+const OUTPUT_CHANNEL_SELECT: usize = 0x100;
+const OUTPUT_CHANNEL_PUSH: usize = 0x101;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// A JSON document of the name, whether it's committed and the values.
+    #[default]
+    Json,
+    /// The values as little-endian words, which could be read back with the input type `file-le`.
+    Binary,
+}
+
+/// A named output channel selected by `wasm_output_channel(id)`.
+///
+/// The values of a committed channel are written by `wasm_output` and become public instances,
+/// the values of the other channels are written by `wasm_output_channel_push` and are only known
+/// to the host.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutputChannelConfig {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub committed: bool,
+    #[serde(default)]
+    pub format: OutputFormat,
+}
+
+#[derive(Clone, Debug)]
+pub struct OutputChannel {
+    pub config: OutputChannelConfig,
+    pub values: Vec<u64>,
+}
+
+impl OutputChannel {
+    pub fn file_name(&self) -> String {
+        match self.config.format {
+            OutputFormat::Json => format!("{}.json", self.config.name),
+            OutputFormat::Binary => format!("{}.bin", self.config.name),
+        }
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        match self.config.format {
+            OutputFormat::Json => serde_json::to_writer_pretty(
+                &mut writer,
+                &serde_json::json!({
+                    "name": self.config.name,
+                    "committed": self.config.committed,
+                    "values": self.values,
+                }),
+            )?,
+            OutputFormat::Binary => {
+                for value in &self.values {
+                    writer.write_all(&value.to_le_bytes())?;
+                }
+            }
+        }
+
+        writer.flush()
+    }
+}
+
+/// The output channels shared by `wasm_output` and the output channel plugin.
+#[derive(Default)]
+pub struct OutputChannels {
+    channels: Vec<OutputChannel>,
+    selected: Option<usize>,
+}
+
+impl OutputChannels {
+    fn configure(&mut self, configs: Vec<OutputChannelConfig>) {
+        self.channels = configs
+            .into_iter()
+            .map(|config| OutputChannel {
+                config,
+                values: vec![],
+            })
+            .collect();
+        self.selected = None;
+    }
+
+    fn select(&mut self, id: u64, eid: usize) -> Result<(), ExecutionError> {
+        let index = self
+            .channels
+            .iter()
+            .position(|channel| channel.config.id == id)
+            .ok_or(ExecutionError::UnknownOutputChannel(id, eid))?;
+
+        self.selected = Some(index);
+
+        Ok(())
+    }
+
+    fn push(&mut self, value: u64, eid: usize) -> Result<(), ExecutionError> {
+        let channel = self
+            .selected
+            .map(|index| &mut self.channels[index])
+            .ok_or(ExecutionError::OutputChannelNotSelected(eid))?;
+
+        if channel.config.committed {
+            return Err(ExecutionError::OutputChannelCommitted(
+                channel.config.name.clone(),
+                eid,
+            ));
+        }
+
+        channel.values.push(value);
+
+        Ok(())
+    }
+
+    /// Record the value of `wasm_output` to the selected channel, the values are not recorded to
+    /// any channel if no channel is selected.
+    pub(crate) fn record_output(&mut self, value: u64, eid: usize) -> Result<(), ExecutionError> {
+        let Some(index) = self.selected else {
+            return Ok(());
+        };
+        let channel = &mut self.channels[index];
+
+        if !channel.config.committed {
+            return Err(ExecutionError::OutputChannelNotCommitted(
+                channel.config.name.clone(),
+                eid,
+            ));
+        }
+
+        channel.values.push(value);
+
+        Ok(())
+    }
+
+    pub fn channels(&self) -> &[OutputChannel] {
+        &self.channels
+    }
+}
+
+struct Context {
+    channels: Rc<RefCell<OutputChannels>>,
+}
+
+impl ForeignContext for Context {}
+
+/// Register `wasm_output_channel` and `wasm_output_channel_push`, it should be called after
+/// `register_wasm_input_foreign` since the committed channels are written by `wasm_output`.
+pub fn register_output_channel_foreign(env: &mut HostEnv, configs: Vec<OutputChannelConfig>) {
+    let channels = env
+        .internal_env
+        .get_context_of_plugin(HostPlugin::HostInput)
+        .borrow()
+        .downcast_ref::<WasmInputContext>()
+        .expect("wasm input plugin should be registered before the output channel plugin")
+        .output_channels
+        .clone();
+    channels.borrow_mut().configure(configs);
+
+    let output_channel_plugin = env
+        .external_env
+        .register_plugin("output channel plugin", Box::new(Context { channels }));
+
+    let select = Rc::new(
+        |observer: &Observer, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
+            let context = context.downcast_mut::<Context>().unwrap();
+            let id: u64 = args.nth(0);
+
+            context
+                .channels
+                .borrow_mut()
+                .select(id, observer.current_eid())
+                .map(|_| None)
+        },
+    );
+
+    let push = Rc::new(
+        |observer: &Observer, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
+            let context = context.downcast_mut::<Context>().unwrap();
+            let value: u64 = args.nth(0);
+
+            context
+                .channels
+                .borrow_mut()
+                .push(value, observer.current_eid())
+                .map(|_| None)
+        },
+    );
+
+    env.external_env.register_function(
+        "wasm_output_channel",
+        OUTPUT_CHANNEL_SELECT,
+        ExternalHostCallSignature::Argument,
+        output_channel_plugin.clone(),
+        select,
+    );
+
+    env.external_env.register_function(
+        "wasm_output_channel_push",
+        OUTPUT_CHANNEL_PUSH,
+        ExternalHostCallSignature::Argument,
+        output_channel_plugin,
+        push,
+    );
+}
@@ -3,11 +3,14 @@ use halo2_proofs::plonk::Error;
 use halo2_proofs::plonk::Expression;
 use halo2_proofs::plonk::VirtualCells;
 use num_bigint::BigUint;
+use specs::etable::EventTableEntry;
 use specs::itable::OpcodeClass;
 use specs::itable::OPCODE_CLASS_SHIFT;
 use specs::mtable::LocationType;
 use specs::step::StepInfo;
+use specs::trap::TrapCode;
 
+use crate::circuits::cell::AllocatedBitCell;
 use crate::circuits::cell::AllocatedU64Cell;
 use crate::circuits::cell::AllocatedUnlimitedCell;
 use crate::circuits::cell::CellExpression;
@@ -30,6 +33,8 @@ pub struct ETableRequireHelperTableConfig<F: FieldExt> {
 
     cond: AllocatedU64Cell<F>,
     cond_inv: AllocatedUnlimitedCell<F>,
+    // The require fails and traps, only allowed by the trap circuit.
+    is_failed: AllocatedBitCell<F>,
 
     memory_table_lookup_read_stack: AllocatedMemoryTableLookupReadCell<F>,
 }
@@ -54,10 +59,17 @@ impl<F: FieldExt> EventTableForeignCallConfigBuilder<F> for ETableRequireHelperT
     ) -> Box<dyn EventTableOpcodeConfig<F>> {
         let cond = allocator.alloc_u64_cell();
         let cond_inv = allocator.alloc_unlimited_cell();
+        let is_failed = allocator.alloc_bit_cell();
 
         constraint_builder.push(
-            "require: cond is not zero",
-            Box::new(move |meta| vec![(cond.expr(meta) * cond_inv.expr(meta) - constant_from!(1))]),
+            "require: cond is not zero unless failed",
+            Box::new(move |meta| {
+                vec![
+                    cond.expr(meta) * cond_inv.expr(meta) - constant_from!(1)
+                        + is_failed.expr(meta),
+                    is_failed.expr(meta) * cond.expr(meta),
+                ]
+            }),
         );
 
         let eid = common_config.eid_cell;
@@ -78,6 +90,7 @@ impl<F: FieldExt> EventTableForeignCallConfigBuilder<F> for ETableRequireHelperT
             plugin_index: self.index,
             cond,
             cond_inv,
+            is_failed,
             memory_table_lookup_read_stack,
         })
     }
@@ -105,6 +118,7 @@ impl<F: FieldExt> EventTableOpcodeConfig<F> for ETableRequireHelperTableConfig<F
                 if cond != 0 {
                     self.cond_inv.assign(ctx, step.field_helper.invert(cond))?;
                 }
+                self.is_failed.assign_bool(ctx, cond == 0)?;
 
                 self.memory_table_lookup_read_stack.assign(
                     ctx,
@@ -127,4 +141,25 @@ impl<F: FieldExt> EventTableOpcodeConfig<F> for ETableRequireHelperTableConfig<F
     fn sp_diff(&self, _meta: &mut VirtualCells<'_, F>) -> Option<Expression<F>> {
         Some(constant_from!(1))
     }
+
+    fn input_index_increase(
+        &self,
+        meta: &mut VirtualCells<'_, F>,
+        _common_config: &EventTableCommonConfig<F>,
+    ) -> Option<Expression<F>> {
+        // The trap code of a failed require is exposed as a public instance.
+        Some(self.is_failed.expr(meta))
+    }
+
+    fn is_host_public_input(&self, entry: &EventTableEntry) -> bool {
+        TrapCode::of_step(entry).is_some()
+    }
+
+    fn is_trap(&self, meta: &mut VirtualCells<'_, F>) -> Option<Expression<F>> {
+        Some(self.is_failed.expr(meta))
+    }
+
+    fn trap_code(&self, meta: &mut VirtualCells<'_, F>) -> Option<Expression<F>> {
+        Some(self.is_failed.expr(meta) * constant_from!(TrapCode::RequireFailed as u64))
+    }
 }
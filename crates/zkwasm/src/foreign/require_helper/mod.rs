@@ -3,6 +3,7 @@ use std::rc::Rc;
 use specs::host_function::HostPlugin;
 use specs::types::ValueType;
 
+use crate::error::ExecutionError;
 use crate::runtime::host::host_env::HostEnv;
 use crate::runtime::host::ForeignContext;
 use crate::runtime::monitor::observer::Observer;
@@ -14,18 +15,14 @@ impl ForeignContext for Context {}
 
 pub fn register_require_foreign(env: &mut HostEnv) {
     let require = Rc::new(
-        |_observer: &Observer, _context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
+        |observer: &Observer, _context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
             let cond: u32 = args.nth(0);
 
             if cond == 0 {
-                panic!(
-                    "require is not satisfied, which is a \
-                false assertion in the wasm code. Please check \
-                the logic of your image or input."
-                )
+                return Err(ExecutionError::RequireFailed(observer.current_eid()));
             }
 
-            None
+            Ok(None)
         },
     );
 
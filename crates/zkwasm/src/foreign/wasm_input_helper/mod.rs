@@ -1,6 +1,7 @@
 pub mod circuits;
 pub mod etable_op_configure;
 pub mod runtime;
+pub mod source;
 pub mod test;
 
 enum Op {
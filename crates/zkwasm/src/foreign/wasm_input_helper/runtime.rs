@@ -1,45 +1,63 @@
-use std::collections::VecDeque;
+use std::cell::RefCell;
+use std::io;
 use std::rc::Rc;
 
 use specs::host_function::HostPlugin;
 use specs::types::ValueType;
 
+use crate::error::ExecutionError;
+use crate::foreign::output_helper::OutputChannel;
+use crate::foreign::output_helper::OutputChannels;
 use crate::runtime::host::host_env::HostEnv;
 use crate::runtime::host::ForeignContext;
 use crate::runtime::host::ForeignStatics;
 use crate::runtime::monitor::observer::Observer;
 
+use super::source::PrivateInputSource;
 use super::Op;
 
 pub struct Context {
     pub public_inputs: Vec<u64>,
-    pub private_inputs: VecDeque<u64>,
+    pub private_inputs: Box<dyn PrivateInputSource>,
     pub instance: Vec<u64>,
     pub output: Vec<u64>,
+    pub(crate) output_channels: Rc<RefCell<OutputChannels>>,
 }
 
 impl Context {
-    pub fn new(public_inputs: Vec<u64>, private_inputs: Vec<u64>) -> Self {
+    pub fn new(public_inputs: Vec<u64>, private_inputs: Box<dyn PrivateInputSource>) -> Self {
         Context {
             public_inputs,
-            private_inputs: private_inputs.into(),
+            private_inputs,
             instance: vec![],
             output: vec![],
+            output_channels: Rc::new(RefCell::new(OutputChannels::default())),
         }
     }
 
-    pub fn pop_public(&mut self) -> u64 {
+    pub fn pop_public(&mut self) -> Option<u64> {
         if self.public_inputs.is_empty() {
-            panic!("failed to read public input, please checkout your input");
+            return None;
         }
-        self.public_inputs.remove(0)
+        Some(self.public_inputs.remove(0))
     }
 
-    pub fn pop_private(&mut self) -> u64 {
-        if self.private_inputs.is_empty() {
-            panic!("failed to read private input, please checkout your input");
+    /// `eid` is the eid of the call to report the failure with the byte offset of the source.
+    pub fn pop_private(&mut self, eid: usize) -> Result<u64, ExecutionError> {
+        let offset = self.private_inputs.offset();
+
+        match self.private_inputs.next_word() {
+            Ok(Some(value)) => Ok(value),
+            Ok(None) => Err(ExecutionError::PrivateInputExhausted(eid, offset)),
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => {
+                Err(ExecutionError::PrivateInputTruncated(eid, offset))
+            }
+            Err(err) => Err(ExecutionError::PrivateInputUnreadable(
+                eid,
+                offset,
+                err.to_string(),
+            )),
         }
-        self.private_inputs.pop_front().unwrap()
     }
 
     fn push_public(&mut self, value: u64) {
@@ -51,20 +69,29 @@ impl Context {
         self.output.push(value);
     }
 
-    pub fn wasm_input(&mut self, arg: i32) -> u64 {
-        assert!(arg == 0 || arg == 1);
-
-        if arg == 1 {
-            let value = self.pop_public();
-            self.push_public(value);
-            value
-        } else {
-            self.pop_private()
+    /// `eid` is the eid of the call to report the exhausted inputs.
+    pub fn wasm_input(&mut self, arg: i32, eid: usize) -> Result<u64, ExecutionError> {
+        match arg {
+            0 => self.pop_private(eid),
+            1 => {
+                let value = self
+                    .pop_public()
+                    .ok_or(ExecutionError::PublicInputExhausted(eid))?;
+                self.push_public(value);
+                Ok(value)
+            }
+            _ => Err(ExecutionError::InvalidInputKind(arg, eid)),
         }
     }
 
-    pub fn wasm_output(&mut self, value: u64) {
+    /// `eid` is the eid of the call to report the output to a channel which is not committed.
+    pub fn wasm_output(&mut self, value: u64, eid: usize) -> Result<(), ExecutionError> {
+        self.output_channels
+            .borrow_mut()
+            .record_output(value, eid)?;
         self.push_output(value);
+
+        Ok(())
     }
 }
 
@@ -80,31 +107,36 @@ impl ForeignContext for Context {
     fn expose_outputs(&self) -> Vec<u64> {
         self.output.clone()
     }
+
+    fn expose_output_channels(&self) -> Vec<OutputChannel> {
+        self.output_channels.borrow().channels().to_vec()
+    }
 }
 
 pub fn register_wasm_input_foreign(
     env: &mut HostEnv,
     public_inputs: Vec<u64>,
-    private_inputs: Vec<u64>,
+    private_inputs: Box<dyn PrivateInputSource>,
 ) {
     let wasm_input = Rc::new(
-        |_observer: &Observer, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
+        |observer: &Observer, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
             let context = context.downcast_mut::<Context>().unwrap();
             let arg: i32 = args.nth(0);
-            let input = context.wasm_input(arg);
 
-            Some(wasmi::RuntimeValue::I64(input as i64))
+            context
+                .wasm_input(arg, observer.current_eid())
+                .map(|input| Some(wasmi::RuntimeValue::I64(input as i64)))
         },
     );
 
     let wasm_output = Rc::new(
-        |_observer: &Observer, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
+        |observer: &Observer, context: &mut dyn ForeignContext, args: wasmi::RuntimeArgs| {
             let context = context.downcast_mut::<Context>().unwrap();
 
             let value: i64 = args.nth(0);
-            context.wasm_output(value as u64);
-
-            None
+            context
+                .wasm_output(value as u64, observer.current_eid())
+                .map(|_| None)
         },
     );
 
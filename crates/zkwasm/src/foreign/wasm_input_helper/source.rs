@@ -0,0 +1,121 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::io::Read;
+use std::path::Path;
+
+/// The source of the private inputs, consumed lazily by `wasm_input(0)`.
+pub trait PrivateInputSource {
+    /// Read the next word, `Ok(None)` if the source is exhausted. A source ending in the middle
+    /// of a word returns an error of `io::ErrorKind::UnexpectedEof`.
+    fn next_word(&mut self) -> io::Result<Option<u64>>;
+
+    /// The number of bytes of the words read so far.
+    fn offset(&self) -> u64;
+}
+
+/// Private inputs materialized in memory.
+pub struct InMemorySource {
+    words: VecDeque<u64>,
+    consumed: u64,
+}
+
+impl InMemorySource {
+    pub fn new(words: Vec<u64>) -> Self {
+        InMemorySource {
+            words: words.into(),
+            consumed: 0,
+        }
+    }
+}
+
+impl PrivateInputSource for InMemorySource {
+    fn next_word(&mut self) -> io::Result<Option<u64>> {
+        let word = self.words.pop_front();
+
+        if word.is_some() {
+            self.consumed += 1;
+        }
+
+        Ok(word)
+    }
+
+    fn offset(&self) -> u64 {
+        self.consumed * 8
+    }
+}
+
+impl From<Vec<u64>> for Box<dyn PrivateInputSource> {
+    fn from(words: Vec<u64>) -> Self {
+        Box::new(InMemorySource::new(words))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+/// Private inputs read from a stream of bytes, every 8 bytes are a word.
+pub struct ReaderSource<R: Read> {
+    reader: R,
+    endianness: Endianness,
+    offset: u64,
+}
+
+impl<R: Read> ReaderSource<R> {
+    pub fn new(reader: R, endianness: Endianness) -> Self {
+        ReaderSource {
+            reader,
+            endianness,
+            offset: 0,
+        }
+    }
+}
+
+impl ReaderSource<BufReader<File>> {
+    pub fn open<P: AsRef<Path>>(path: P, endianness: Endianness) -> io::Result<Self> {
+        Ok(Self::new(BufReader::new(File::open(path)?), endianness))
+    }
+}
+
+/// Private inputs read from a file, see `ReaderSource::open`.
+pub type FileSource = ReaderSource<BufReader<File>>;
+
+impl<R: Read> PrivateInputSource for ReaderSource<R> {
+    fn next_word(&mut self) -> io::Result<Option<u64>> {
+        let mut bytes = [0u8; 8];
+        let mut read = 0;
+
+        while read < bytes.len() {
+            match self.reader.read(&mut bytes[read..]) {
+                Ok(0) => break,
+                Ok(n) => read += n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+
+        match read {
+            0 => Ok(None),
+            8 => {
+                self.offset += 8;
+
+                Ok(Some(match self.endianness {
+                    Endianness::Little => u64::from_le_bytes(bytes),
+                    Endianness::Big => u64::from_be_bytes(bytes),
+                }))
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("the source ends after {} byte(s) of a word", read),
+            )),
+        }
+    }
+
+    fn offset(&self) -> u64 {
+        self.offset
+    }
+}
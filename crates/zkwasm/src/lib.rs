@@ -7,13 +7,13 @@
 
 pub mod checksum;
 pub mod circuits;
+pub mod debugger;
 pub mod error;
 pub mod foreign;
 pub mod loader;
+pub mod profile;
 pub mod runtime;
 
-mod profile;
-
 #[cfg(test)]
 pub mod test;
 
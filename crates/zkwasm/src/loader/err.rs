@@ -1,10 +1,67 @@
 use std::fmt::Display;
 
-#[derive(Debug)]
+use parity_wasm::elements::ValueType;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PreCheckErr {
     ZkmainNotExists,
     ZkmainIsNotFunction,
-    // ZkmainTypeNotMatch,
+    /// zkmain is invoked without arguments.
+    ZkmainTypeNotMatch {
+        params: Vec<ValueType>,
+        results: Vec<ValueType>,
+    },
+    UnsupportedInstruction {
+        function_index: u32,
+        function_name: Option<String>,
+        /// Index of the instruction in the function body.
+        offset: usize,
+        opcode: String,
+    },
+    /// The initial memory of the image exceeds the pages supported by the circuit of size K.
+    InitialPagesExceedLimit {
+        initial_pages: u32,
+        maximal_pages: u32,
+        k: u32,
+    },
+}
+
+impl Display for PreCheckErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PreCheckErr::ZkmainNotExists => write!(f, "zkmain is not exported"),
+            PreCheckErr::ZkmainIsNotFunction => write!(f, "the export zkmain is not a function"),
+            PreCheckErr::ZkmainTypeNotMatch { params, results } => write!(
+                f,
+                "zkmain should take no parameters, but its type is {:?} -> {:?}",
+                params, results
+            ),
+            PreCheckErr::UnsupportedInstruction {
+                function_index,
+                function_name,
+                offset,
+                opcode,
+            } => write!(
+                f,
+                "unsupported instruction {} at offset {} of function {}{}",
+                opcode,
+                offset,
+                function_index,
+                function_name
+                    .as_ref()
+                    .map_or(String::new(), |name| format!("({})", name))
+            ),
+            PreCheckErr::InitialPagesExceedLimit {
+                initial_pages,
+                maximal_pages,
+                k,
+            } => write!(
+                f,
+                "initial memory pages({}) exceed the limit({}). Current K is {}, consider increasing the circuit size K.",
+                initial_pages, maximal_pages, k
+            ),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -12,12 +69,23 @@ pub enum RuntimeErr {}
 
 #[derive(Debug)]
 pub enum Error {
-    PreCheck(PreCheckErr),
+    PreCheck(Vec<PreCheckErr>),
     // Runtime(RuntimeErr),
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            Error::PreCheck(errs) => {
+                writeln!(f, "Precheck failed with {} error(s):", errs.len())?;
+                for err in errs {
+                    writeln!(f, "  - {}", err)?;
+                }
+
+                Ok(())
+            }
+        }
     }
 }
+
+impl std::error::Error for Error {}
@@ -29,7 +29,8 @@ use self::slice::Slices;
 pub use specs::TraceBackend;
 pub use wasmi::Module;
 
-mod err;
+pub mod err;
+mod precheck;
 pub mod slice;
 
 const ENTRY: &str = "zkmain";
@@ -42,38 +43,36 @@ pub struct ZkWasmLoader {
     pub k: u32,
     entry: String,
     env: HostEnv,
+    allow_trap: bool,
 }
 
 impl ZkWasmLoader {
-    pub fn parse_module(image: &Vec<u8>) -> Result<Module> {
-        fn precheck(_module: &Module) -> Result<()> {
-            #[allow(dead_code)]
-            fn check_zkmain_exists(module: &Module) -> Result<()> {
-                use parity_wasm::elements::Internal;
-
-                let export = module.module().export_section().unwrap();
-
-                if let Some(entry) = export.entries().iter().find(|entry| entry.field() == ENTRY) {
-                    match entry.internal() {
-                        Internal::Function(_fid) => Ok(()),
-                        _ => Err(anyhow!(Error::PreCheck(PreCheckErr::ZkmainIsNotFunction))),
-                    }
-                } else {
-                    Err(anyhow!(Error::PreCheck(PreCheckErr::ZkmainNotExists)))
-                }
-            }
-
-            #[cfg(not(test))]
-            check_zkmain_exists(_module)?;
-            // TODO: check the signature of zkmain function.
-            // TODO: check the relation between maximal pages and K.
-            // TODO: check the instructions of phantom functions.
-            // TODO: check phantom functions exists.
-            // TODO: check if instructions are supported.
-
-            Ok(())
-        }
+    /// Check whether the module can be proved by a circuit of size `k`.
+    ///
+    /// All problems are collected instead of stopping at the first one.
+    pub fn precheck(module: &Module, k: u32) -> Vec<PreCheckErr> {
+        Self::precheck_with_entry(module, k, ENTRY)
+    }
+
+    /// `precheck` with the entry `entry` instead of `zkmain`.
+    pub(crate) fn precheck_with_entry(module: &Module, k: u32, entry: &str) -> Vec<PreCheckErr> {
+        let mut errs = vec![];
+
+        errs.append(&mut precheck::check_zkmain(module, entry));
+        errs.append(&mut precheck::check_maximal_pages(module, k));
+        errs.append(&mut precheck::check_instructions(module));
+        // TODO: check the instructions of phantom functions.
+        // TODO: check phantom functions exists.
+
+        errs
+    }
+
+    pub fn parse_module(image: &Vec<u8>, k: u32) -> Result<Module> {
+        Self::parse_module_with_entry(image, k, ENTRY)
+    }
 
+    /// `parse_module` with the entry `entry` instead of `zkmain`.
+    pub(crate) fn parse_module_with_entry(image: &[u8], k: u32, entry: &str) -> Result<Module> {
         let mut module = Module::from_buffer(image)?;
         if let Ok(parity_module) = module.module().clone().parse_names() {
             module.module = parity_module;
@@ -81,7 +80,10 @@ impl ZkWasmLoader {
             warn!("Failed to parse name section of the wasm binary.");
         }
 
-        precheck(&module)?;
+        let errs = Self::precheck_with_entry(&module, k, entry);
+        if !errs.is_empty() {
+            return Err(anyhow!(Error::PreCheck(errs)));
+        }
 
         Ok(module)
     }
@@ -108,11 +110,19 @@ impl ZkWasmLoader {
             k,
             entry: ENTRY.to_string(),
             env,
+            allow_trap: false,
         };
 
         Ok(loader)
     }
 
+    /// Terminate the execution with the trap instead of failing if it traps by `unreachable`
+    /// or a failed `require`, so that the trap can be proved. The trap is returned in
+    /// `ExecutionResult::trap` and its code is appended to the public instances.
+    pub fn set_allow_trap(&mut self, allow_trap: bool) {
+        self.allow_trap = allow_trap;
+    }
+
     #[cfg(test)]
     pub(crate) fn set_entry(&mut self, entry: String) {
         self.entry = entry;
@@ -125,7 +135,7 @@ impl ZkWasmLoader {
         compiled_module: CompiledImage<NotStartedModuleRef<'_>>,
         monitor: &mut dyn WasmiMonitor,
     ) -> Result<ExecutionResult<RuntimeValue>> {
-        compiled_module.run(monitor, self.env)
+        compiled_module.run(monitor, self.env, self.allow_trap)
     }
 
     #[deprecated]
@@ -0,0 +1,191 @@
+use parity_wasm::elements::External;
+use parity_wasm::elements::ImportCountType;
+use parity_wasm::elements::Instruction;
+use parity_wasm::elements::Internal;
+use parity_wasm::elements::Type;
+use wasmi::Module;
+
+use crate::circuits::image_table::compute_maximal_pages;
+
+use super::err::PreCheckErr;
+
+fn function_name(module: &Module, function_index: u32) -> Option<String> {
+    module
+        .module()
+        .names_section()
+        .and_then(|names| names.functions())
+        .and_then(|functions| functions.names().get(function_index))
+        .cloned()
+}
+
+/// Check the export `entry`, which is `zkmain` unless the loader is configured otherwise.
+pub(super) fn check_zkmain(module: &Module, entry: &str) -> Vec<PreCheckErr> {
+    let module = module.module();
+
+    let export = module.export_section().and_then(|export| {
+        export
+            .entries()
+            .iter()
+            .find(|export| export.field() == entry)
+    });
+
+    let fid = match export.map(|export| export.internal()) {
+        Some(Internal::Function(fid)) => *fid,
+        Some(_) => return vec![PreCheckErr::ZkmainIsNotFunction],
+        None => return vec![PreCheckErr::ZkmainNotExists],
+    };
+
+    let imported_functions = module.import_section().map_or(vec![], |import| {
+        import
+            .entries()
+            .iter()
+            .filter_map(|entry| match entry.external() {
+                External::Function(type_ref) => Some(*type_ref),
+                _ => None,
+            })
+            .collect()
+    });
+
+    let type_ref = if (fid as usize) < imported_functions.len() {
+        Some(imported_functions[fid as usize])
+    } else {
+        module.function_section().and_then(|functions| {
+            functions
+                .entries()
+                .get(fid as usize - imported_functions.len())
+                .map(|func| func.type_ref())
+        })
+    };
+
+    let func_type = type_ref.and_then(|type_ref| {
+        module
+            .type_section()
+            .and_then(|types| types.types().get(type_ref as usize))
+    });
+
+    match func_type {
+        Some(Type::Function(func_type)) if !func_type.params().is_empty() => {
+            vec![PreCheckErr::ZkmainTypeNotMatch {
+                params: func_type.params().to_vec(),
+                results: func_type.results().to_vec(),
+            }]
+        }
+        _ => vec![],
+    }
+}
+
+pub(super) fn check_maximal_pages(module: &Module, k: u32) -> Vec<PreCheckErr> {
+    let module = module.module();
+
+    let imported_memory = module.import_section().and_then(|import| {
+        import
+            .entries()
+            .iter()
+            .find_map(|entry| match entry.external() {
+                External::Memory(memory) => Some(memory.limits().initial()),
+                _ => None,
+            })
+    });
+    let defined_memory = module
+        .memory_section()
+        .and_then(|memory| memory.entries().first())
+        .map(|memory| memory.limits().initial());
+
+    let maximal_pages = compute_maximal_pages(k);
+
+    match imported_memory.or(defined_memory) {
+        Some(initial_pages) if initial_pages > maximal_pages => {
+            vec![PreCheckErr::InitialPagesExceedLimit {
+                initial_pages,
+                maximal_pages,
+                k,
+            }]
+        }
+        _ => vec![],
+    }
+}
+
+fn is_supported(instruction: &Instruction) -> bool {
+    use Instruction::*;
+
+    #[allow(unreachable_patterns)]
+    match instruction {
+        // Control
+        Unreachable
+        | Nop
+        | Block(_)
+        | Loop(_)
+        | If(_)
+        | Else
+        | End
+        | Br(_)
+        | BrIf(_)
+        | BrTable(_)
+        | Return
+        | Call(_)
+        | CallIndirect(_, _) => true,
+        // Parametric
+        Drop | Select => true,
+        // Variable
+        GetLocal(_) | SetLocal(_) | TeeLocal(_) | GetGlobal(_) | SetGlobal(_) => true,
+        // Memory
+        I32Load(..) | I64Load(..) | F32Load(..) | F64Load(..) | I32Load8S(..) | I32Load8U(..)
+        | I32Load16S(..) | I32Load16U(..) | I64Load8S(..) | I64Load8U(..) | I64Load16S(..)
+        | I64Load16U(..) | I64Load32S(..) | I64Load32U(..) | I32Store(..) | I64Store(..)
+        | F32Store(..) | F64Store(..) | I32Store8(..) | I32Store16(..) | I64Store8(..)
+        | I64Store16(..) | I64Store32(..) | CurrentMemory(_) | GrowMemory(_) => true,
+        // Const
+        I32Const(_) | I64Const(_) | F32Const(_) | F64Const(_) => true,
+        // Integer
+        I32Eqz | I32Eq | I32Ne | I32LtS | I32LtU | I32GtS | I32GtU | I32LeS | I32LeU | I32GeS
+        | I32GeU | I64Eqz | I64Eq | I64Ne | I64LtS | I64LtU | I64GtS | I64GtU | I64LeS | I64LeU
+        | I64GeS | I64GeU | I32Clz | I32Ctz | I32Popcnt | I32Add | I32Sub | I32Mul | I32DivS
+        | I32DivU | I32RemS | I32RemU | I32And | I32Or | I32Xor | I32Shl | I32ShrS | I32ShrU
+        | I32Rotl | I32Rotr | I64Clz | I64Ctz | I64Popcnt | I64Add | I64Sub | I64Mul | I64DivS
+        | I64DivU | I64RemS | I64RemU | I64And | I64Or | I64Xor | I64Shl | I64ShrS | I64ShrU
+        | I64Rotl | I64Rotr | I32WrapI64 | I64ExtendSI32 | I64ExtendUI32 | SignExt(_) => true,
+        // Float, the loads, stores and consts of floats are handled as the ones of integers.
+        F32Eq | F32Ne | F32Lt | F32Gt | F32Le | F32Ge | F64Eq | F64Ne | F64Lt | F64Gt | F64Le
+        | F64Ge | F32Abs | F32Neg | F32Copysign | F64Abs | F64Neg | F64Copysign
+        | I32ReinterpretF32 | I64ReinterpretF64 | F32ReinterpretI32 | F64ReinterpretI64 => {
+            cfg!(feature = "float")
+        }
+        // Float arithmetic, rounding and conversions have no circuit yet.
+        F32Ceil | F32Floor | F32Trunc | F32Nearest | F32Sqrt | F32Add | F32Sub | F32Mul
+        | F32Div | F32Min | F32Max | F64Ceil | F64Floor | F64Trunc | F64Nearest | F64Sqrt
+        | F64Add | F64Sub | F64Mul | F64Div | F64Min | F64Max | I32TruncSF32 | I32TruncUF32
+        | I32TruncSF64 | I32TruncUF64 | I64TruncSF32 | I64TruncUF32 | I64TruncSF64
+        | I64TruncUF64 | F32ConvertSI32 | F32ConvertUI32 | F32ConvertSI64 | F32ConvertUI64
+        | F32DemoteF64 | F64ConvertSI32 | F64ConvertUI32 | F64ConvertSI64 | F64ConvertUI64
+        | F64PromoteF32 => false,
+        // Extensions enabled by other crates in the dependency graph, e.g. atomics, simd
+        // and bulk memory, have no circuit.
+        _ => false,
+    }
+}
+
+pub(super) fn check_instructions(module: &Module) -> Vec<PreCheckErr> {
+    let parity_module = module.module();
+    let imported_functions = parity_module.import_count(ImportCountType::Function) as u32;
+
+    let mut errs = vec![];
+
+    if let Some(code) = parity_module.code_section() {
+        for (index, body) in code.bodies().iter().enumerate() {
+            let function_index = imported_functions + index as u32;
+
+            for (offset, instruction) in body.code().elements().iter().enumerate() {
+                if !is_supported(instruction) {
+                    errs.push(PreCheckErr::UnsupportedInstruction {
+                        function_index,
+                        function_name: function_name(module, function_index),
+                        offset,
+                        opcode: instruction.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    errs
+}
@@ -11,7 +11,9 @@ use specs::jtable::FrameTable;
 use specs::jtable::InheritedFrameTable;
 use specs::slice::FrameTableSlice;
 use specs::slice::Slice;
+use specs::slice::SliceState;
 use specs::state::InitializationState;
+use specs::trace::TraceError;
 use specs::TableBackend;
 use specs::Tables;
 use std::collections::VecDeque;
@@ -19,6 +21,7 @@ use std::sync::Arc;
 
 use crate::circuits::ZkWasmCircuit;
 use crate::error::BuildingCircuitError;
+use crate::error::MockTestError;
 use crate::runtime::state::UpdateInitMemoryTable;
 use crate::runtime::state::UpdateInitializationState;
 
@@ -43,6 +46,10 @@ pub struct Slices<F: FieldExt> {
     context_input_table: Arc<Vec<u64>>,
     context_output_table: Arc<Vec<u64>>,
 
+    // The index of the next slice, trivial slices included.
+    index: usize,
+    slice_state_writer: Option<Box<dyn Fn(usize, &SliceState) -> Result<(), TraceError>>>,
+
     _marker: std::marker::PhantomData<F>,
 }
 
@@ -84,27 +91,71 @@ impl<F: FieldExt> Slices<F> {
             context_input_table: tables.execution_tables.context_input_table.into(),
             context_output_table: tables.execution_tables.context_output_table.into(),
 
+            index: 0,
+            slice_state_writer: None,
+
             _marker: std::marker::PhantomData,
         })
     }
 
-    pub fn mock_test_all(self, instances: Vec<F>) -> anyhow::Result<()> {
-        use halo2_proofs::dev::MockProver;
+    /// Call `writer` with the post state of each slice once the slice is built.
+    pub fn with_slice_state_writer(
+        mut self,
+        writer: impl Fn(usize, &SliceState) -> Result<(), TraceError> + 'static,
+    ) -> Self {
+        self.slice_state_writer = Some(Box::new(writer));
+        self
+    }
 
-        let k = self.k;
-        let iter = self;
+    /*
+     * Skip the first `index` slices(trivial slices included) without building them.
+     * state: the post state of slice `index - 1`, it's only required if a non-trivial slice is skipped.
+     */
+    pub fn resume_from(
+        mut self,
+        index: usize,
+        state: Option<SliceState>,
+    ) -> Result<Self, BuildingCircuitError> {
+        let skipped_trivial_slices = usize::min(index, self.padding);
+        let skipped_slices = index - skipped_trivial_slices;
+
+        if skipped_slices > self.etables.len() {
+            return Err(BuildingCircuitError::ResumeIndexOutOfRange(
+                index,
+                self.padding + self.etables.len(),
+            ));
+        }
 
-        for slice in iter {
-            match slice? {
-                ZkWasmCircuit::Ongoing(circuit) => {
-                    let prover = MockProver::run(k, &circuit, vec![instances.clone()])?;
-                    assert_eq!(prover.verify(), Ok(()));
-                }
-                ZkWasmCircuit::LastSliceCircuit(circuit) => {
-                    let prover = MockProver::run(k, &circuit, vec![instances.clone()])?;
-                    assert_eq!(prover.verify(), Ok(()));
-                }
-            }
+        self.padding -= skipped_trivial_slices;
+
+        if skipped_slices > 0 {
+            let state = state.ok_or(BuildingCircuitError::MissingSliceState(index - 1, index))?;
+
+            self.etables.drain(..skipped_slices);
+            self.frame_table.drain(..skipped_slices);
+            self.external_host_call_table.drain(..skipped_slices);
+
+            self.imtable = Arc::new(InitMemoryTable::new(state.post_imtable));
+            self.initialization_state = Arc::new(state.post_initialization_state);
+        }
+
+        self.index = index;
+
+        Ok(self)
+    }
+
+    /// Write the post state of every slice by the slice state writer, the circuits are not built.
+    pub fn write_slice_states(mut self) -> Result<(), BuildingCircuitError> {
+        while let Some(slice) = self.next_slice() {
+            slice?;
+        }
+
+        Ok(())
+    }
+
+    pub fn mock_test_all(self, instances: Vec<F>) -> Result<(), MockTestError> {
+        for slice in self {
+            slice?.mock_test(instances.clone())?;
         }
 
         Ok(())
@@ -112,8 +163,8 @@ impl<F: FieldExt> Slices<F> {
 }
 
 impl<F: FieldExt> Slices<F> {
-    // create a circuit slice with all entries disabled.
-    fn trivial_slice(&mut self) -> Result<ZkWasmCircuit<F>, BuildingCircuitError> {
+    // create a slice with all entries disabled.
+    fn trivial_slice(&mut self) -> Slice {
         self.padding -= 1;
 
         let frame_table = Arc::new(FrameTableSlice {
@@ -121,7 +172,7 @@ impl<F: FieldExt> Slices<F> {
             called: CalledFrameTable::default(),
         });
 
-        let slice = Slice {
+        Slice {
             itable: self.itable.clone(),
             br_table: self.br_table.clone(),
             elem_table: self.elem_table.clone(),
@@ -143,27 +194,38 @@ impl<F: FieldExt> Slices<F> {
             context_output_table: self.context_output_table.clone(),
 
             is_last_slice: false,
-        };
-
-        ZkWasmCircuit::new(self.k, slice)
+        }
     }
-}
-
-impl<F: FieldExt> Iterator for Slices<F> {
-    type Item = Result<ZkWasmCircuit<F>, BuildingCircuitError>;
 
-    fn next(&mut self) -> Option<Self::Item> {
+    // The next slice, its post state is written by the slice state writer.
+    fn next_slice(&mut self) -> Option<Result<Slice, BuildingCircuitError>> {
         if self.etables.is_empty() {
             return None;
         }
 
-        if self.padding > 0 {
-            return Some(self.trivial_slice());
+        let slice = if self.padding > 0 {
+            self.trivial_slice()
+        } else {
+            match self.non_trivial_slice() {
+                Ok(slice) => slice,
+                Err(err) => return Some(Err(err)),
+            }
+        };
+
+        if let Some(writer) = self.slice_state_writer.as_ref() {
+            if let Err(err) = writer(self.index, &slice.post_state()) {
+                return Some(Err(err.into()));
+            }
         }
+        self.index += 1;
+
+        Some(Ok(slice))
+    }
 
+    fn non_trivial_slice(&mut self) -> Result<Slice, BuildingCircuitError> {
         let etable = match self.etables.pop_front().unwrap() {
             TableBackend::Memory(etable) => etable,
-            TableBackend::Json(path) => EventTable::read(&path).unwrap(),
+            TableBackend::Json(path) => EventTable::read(&path)?,
         };
 
         let post_imtable = Arc::new(self.imtable.update_init_memory_table(&etable));
@@ -171,10 +233,8 @@ impl<F: FieldExt> Iterator for Slices<F> {
             let next_event_entry = if let Some(next_event_table) = self.etables.front() {
                 match next_event_table {
                     TableBackend::Memory(etable) => etable.entries().first().cloned(),
-                    TableBackend::Json(path) => {
-                        let etable = EventTable::read(path).unwrap();
-                        etable.entries().first().cloned()
-                    }
+                    // Only the first entry is required, avoid loading the whole table.
+                    TableBackend::Json(path) => EventTable::stream(path)?.next().transpose()?,
                 }
             } else {
                 None
@@ -189,21 +249,19 @@ impl<F: FieldExt> Iterator for Slices<F> {
 
         let frame_table = match self.frame_table.pop_front().unwrap() {
             TableBackend::Memory(frame_table) => frame_table,
-            TableBackend::Json(path) => FrameTable::read(&path).unwrap(),
+            TableBackend::Json(path) => FrameTable::read(&path)?,
         }
         .into();
 
-        let post_inherited_frame_table = self.frame_table.front().map_or(
-            Arc::new(InheritedFrameTable::default()),
-            |frame_table| {
-                let post_inherited_frame_table = match frame_table {
-                    TableBackend::Memory(frame_table) => frame_table.inherited.clone(),
-                    TableBackend::Json(path) => FrameTable::read(path).unwrap().inherited,
-                };
-
-                Arc::new((*post_inherited_frame_table).clone().try_into().unwrap())
-            },
-        );
+        let post_inherited_frame_table = match self.frame_table.front() {
+            Some(TableBackend::Memory(frame_table)) => {
+                Arc::new((*frame_table.inherited).clone().try_into().unwrap())
+            }
+            Some(TableBackend::Json(path)) => {
+                Arc::new(FrameTable::read_inherited(path)?.try_into().unwrap())
+            }
+            None => Arc::new(InheritedFrameTable::default()),
+        };
 
         let external_host_call_table = self.external_host_call_table.pop_front().unwrap();
 
@@ -234,8 +292,16 @@ impl<F: FieldExt> Iterator for Slices<F> {
         self.imtable = post_imtable;
         self.initialization_state = post_initialization_state;
 
-        let circuit = ZkWasmCircuit::new(self.k, slice);
+        Ok(slice)
+    }
+}
+
+impl<F: FieldExt> Iterator for Slices<F> {
+    type Item = Result<ZkWasmCircuit<F>, BuildingCircuitError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let slice = self.next_slice()?;
 
-        Some(circuit)
+        Some(slice.and_then(|slice| ZkWasmCircuit::new(self.k, slice)))
     }
 }
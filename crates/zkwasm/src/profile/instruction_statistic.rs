@@ -1,110 +1,99 @@
-use log::debug;
+use serde::Serialize;
+use specs::etable::EventTable;
+use specs::etable::EventTableEntry;
+use specs::itable::InstructionTable;
 use specs::itable::OpcodeClass;
 use specs::mtable::AccessType;
-use specs::mtable::MemoryTableEntry;
+use specs::trace::TraceError;
+use specs::TableBackend;
 use specs::Tables;
 use std::collections::BTreeMap;
-use std::fmt::Debug;
 
+use crate::circuits::etable::EVENT_TABLE_ENTRY_ROWS;
 use crate::runtime::memory_event_of_step;
 
-pub trait InstructionStatistic {
-    fn profile_instruction(&self);
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct OpcodeStatistic {
+    pub count: usize,
+    pub memory_entries: usize,
+    pub reads: usize,
+    pub writes: usize,
+    pub jops: u64,
+    /// Rows of the event table occupied by the instructions.
+    pub estimated_rows: usize,
 }
 
-impl InstructionStatistic for Tables {
-    fn profile_instruction(&self) {
-        struct Counter {
-            counter: usize,
-            mentries: Vec<MemoryTableEntry>,
-        }
+impl OpcodeStatistic {
+    fn add(&mut self, other: &OpcodeStatistic) {
+        self.count += other.count;
+        self.memory_entries += other.memory_entries;
+        self.reads += other.reads;
+        self.writes += other.writes;
+        self.jops += other.jops;
+        self.estimated_rows += other.estimated_rows;
+    }
+}
 
-        let mut map = BTreeMap::<OpcodeClass, Counter>::new();
-        for entry in self.execution_tables.etable.entries() {
-            let mut mentries = memory_event_of_step(entry);
+/// Statistic of the executed instructions of all slices, grouped by the opcode class.
+#[derive(Debug, Default, Clone)]
+pub struct InstructionStatistic {
+    pub opcodes: BTreeMap<OpcodeClass, OpcodeStatistic>,
+}
 
-            let opcode = &entry
-                .get_instruction(&self.compilation_tables.itable)
-                .opcode;
+impl InstructionStatistic {
+    fn record(&mut self, itable: &InstructionTable, entry: &EventTableEntry) {
+        let opcode_class: OpcodeClass = (&entry.get_instruction(itable).opcode).into();
+        let memory_entries = memory_event_of_step(entry);
 
-            if let Some(counter) = map.get_mut(&opcode.into()) {
-                counter.counter += 1;
-                counter.mentries.append(&mut mentries);
-            } else {
-                map.insert(
-                    opcode.into(),
-                    Counter {
-                        counter: 1,
-                        mentries,
-                    },
-                );
-            }
-        }
+        let statistic = self.opcodes.entry(opcode_class).or_default();
+        statistic.count += 1;
+        statistic.memory_entries += memory_entries.len();
+        statistic.reads += memory_entries
+            .iter()
+            .filter(|entry| entry.atype == AccessType::Read)
+            .count();
+        statistic.writes += memory_entries
+            .iter()
+            .filter(|entry| entry.atype == AccessType::Write)
+            .count();
+        statistic.jops += opcode_class.jops();
+        statistic.estimated_rows += EVENT_TABLE_ENTRY_ROWS as usize;
+    }
 
-        let total_mentries: usize = {
-            let a = map
-                .values()
-                .map(|counter| counter.mentries.len())
-                .collect::<Vec<_>>();
-            a.iter().sum()
-        };
+    pub fn total(&self) -> OpcodeStatistic {
+        let mut total = OpcodeStatistic::default();
+        for statistic in self.opcodes.values() {
+            total.add(statistic);
+        }
+        total
+    }
+}
 
-        debug!(
-            "etable entries: {}",
-            self.execution_tables.etable.entries().len()
-        );
-        debug!("mtable entries: {}", total_mentries);
+pub trait InstructionStatistics {
+    /// The event tables backed by files are streamed instead of being loaded.
+    fn instruction_statistic(&self) -> Result<InstructionStatistic, TraceError>;
+}
 
-        struct Summary {
-            counter: usize,
-            mentries: usize,
-            total_mentries: usize,
-            write_counter: usize,
-            read_counter: usize,
-        }
+impl InstructionStatistics for Tables {
+    fn instruction_statistic(&self) -> Result<InstructionStatistic, TraceError> {
+        let itable = &self.compilation_tables.itable;
+        let mut statistic = InstructionStatistic::default();
 
-        impl Debug for Summary {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                write!(
-                    f,
-                    "{{ counter: {}, mentries: {}({:.2}%), write: {}({:.2}%), read: {}({:.2}%) }}",
-                    self.counter,
-                    self.mentries,
-                    self.mentries as f64 / self.total_mentries as f64 * 100f64,
-                    self.write_counter,
-                    self.write_counter as f64 / self.total_mentries as f64 * 100f64,
-                    self.read_counter,
-                    self.read_counter as f64 / self.total_mentries as f64 * 100f64,
-                )
+        for etable in self.execution_tables.etable.iter() {
+            match etable {
+                TableBackend::Memory(etable) => {
+                    for entry in etable.entries() {
+                        statistic.record(itable, entry);
+                    }
+                }
+                TableBackend::Json(path) => {
+                    for entry in EventTable::stream(path)? {
+                        statistic.record(itable, &entry?);
+                    }
+                }
             }
         }
 
-        let summary = map
-            .into_iter()
-            .map(|(inst, counter)| {
-                (
-                    inst,
-                    Summary {
-                        counter: counter.counter,
-                        mentries: counter.mentries.len(),
-                        total_mentries,
-                        write_counter: counter
-                            .mentries
-                            .iter()
-                            .filter(|entry| entry.atype == AccessType::Write)
-                            .collect::<Vec<_>>()
-                            .len(),
-                        read_counter: counter
-                            .mentries
-                            .iter()
-                            .filter(|entry| entry.atype == AccessType::Read)
-                            .collect::<Vec<_>>()
-                            .len(),
-                    },
-                )
-            })
-            .collect::<BTreeMap<_, _>>();
-
-        debug!("{:?}", summary);
+        Ok(statistic)
     }
 }
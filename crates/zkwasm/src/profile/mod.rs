@@ -1,8 +1,10 @@
-//use instruction_statistic::InstructionStatistic;
+use log::debug;
 use specs::Tables;
 
 mod helper;
-// mod instruction_statistic;
+pub mod instruction_statistic;
+
+use instruction_statistic::InstructionStatistics;
 
 pub trait Profiler {
     fn profile_tables(&self);
@@ -10,6 +12,14 @@ pub trait Profiler {
 
 impl Profiler for Tables {
     fn profile_tables(&self) {
-        //self.profile_instruction();
+        match self.instruction_statistic() {
+            Ok(statistic) => {
+                for (opcode_class, statistic) in statistic.opcodes.iter() {
+                    debug!("{:?}: {:?}", opcode_class, statistic);
+                }
+                debug!("total: {:?}", statistic.total());
+            }
+            Err(err) => debug!("failed to profile tables: {}", err),
+        }
     }
 }
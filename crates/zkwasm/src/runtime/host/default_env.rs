@@ -6,8 +6,11 @@ use zkwasm_host_circuits::host::db::TreeDB;
 
 use crate::foreign::context::runtime::register_context_foreign;
 use crate::foreign::log_helper::register_log_foreign;
+use crate::foreign::output_helper::register_output_channel_foreign;
+use crate::foreign::output_helper::OutputChannelConfig;
 use crate::foreign::require_helper::register_require_foreign;
 use crate::foreign::wasm_input_helper::runtime::register_wasm_input_foreign;
+use crate::foreign::wasm_input_helper::source::PrivateInputSource;
 use crate::runtime::monitor::plugins::table::Command;
 use crate::runtime::monitor::plugins::table::Event;
 use crate::runtime::monitor::plugins::table::FlushStrategy;
@@ -18,14 +21,16 @@ use super::HostEnvBuilder;
 pub struct ExecutionArg {
     /// Public inputs for `wasm_input(1)`
     pub public_inputs: Vec<u64>,
-    /// Private inputs for `wasm_input(0)`
-    pub private_inputs: Vec<u64>,
+    /// Private inputs for `wasm_input(0)`, read lazily from the source
+    pub private_inputs: Box<dyn PrivateInputSource>,
     /// Context inputs for `wasm_read_context()`
     pub context_inputs: Vec<u64>,
     /// indexed witness context
     pub indexed_witness: Rc<RefCell<HashMap<u64, Vec<u64>>>>,
     /// db src
     pub tree_db: Option<Rc<RefCell<dyn TreeDB>>>,
+    /// Named output channels for `wasm_output_channel()`
+    pub output_channels: Vec<OutputChannelConfig>,
 }
 
 pub struct DefaultHostEnvBuilder {
@@ -49,7 +54,8 @@ impl FlushStrategy for DefaultFlushStrategy {
 impl HostEnvBuilder for DefaultHostEnvBuilder {
     fn create_env_without_value(&self) -> HostEnv {
         let mut env = HostEnv::new(self.k);
-        register_wasm_input_foreign(&mut env, vec![], vec![]);
+        register_wasm_input_foreign(&mut env, vec![], vec![].into());
+        register_output_channel_foreign(&mut env, vec![]);
         register_require_foreign(&mut env);
         register_log_foreign(&mut env);
         register_context_foreign(&mut env, vec![]);
@@ -61,6 +67,7 @@ impl HostEnvBuilder for DefaultHostEnvBuilder {
     fn create_env(&self, arg: ExecutionArg) -> HostEnv {
         let mut env = HostEnv::new(self.k);
         register_wasm_input_foreign(&mut env, arg.public_inputs, arg.private_inputs);
+        register_output_channel_foreign(&mut env, arg.output_channels);
         register_require_foreign(&mut env);
         register_log_foreign(&mut env);
         register_context_foreign(&mut env, arg.context_inputs);
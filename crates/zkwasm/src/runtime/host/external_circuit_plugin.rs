@@ -7,6 +7,7 @@ use wasmi::ModuleImportResolver;
 use wasmi::RuntimeArgs;
 use wasmi::RuntimeValue;
 
+use crate::error::ExecutionError;
 use crate::runtime::monitor::observer::Observer;
 
 use super::ForeignContext;
@@ -18,7 +19,13 @@ pub(super) struct ForeignOp {
     pub op_index: usize,
     pub sig: ExternalHostCallSignature,
     pub plugin: Rc<ForeignPlugin>,
-    pub cb: Rc<dyn Fn(&Observer, &mut dyn ForeignContext, RuntimeArgs) -> Option<RuntimeValue>>,
+    pub cb: Rc<
+        dyn Fn(
+            &Observer,
+            &mut dyn ForeignContext,
+            RuntimeArgs,
+        ) -> Result<Option<RuntimeValue>, ExecutionError>,
+    >,
 }
 
 pub struct ExternalCircuitEnv {
@@ -53,7 +60,13 @@ impl ExternalCircuitEnv {
         op_index: usize,
         sig: ExternalHostCallSignature,
         plugin: Rc<ForeignPlugin>,
-        cb: Rc<dyn Fn(&Observer, &mut dyn ForeignContext, RuntimeArgs) -> Option<RuntimeValue>>,
+        cb: Rc<
+            dyn Fn(
+                &Observer,
+                &mut dyn ForeignContext,
+                RuntimeArgs,
+            ) -> Result<Option<RuntimeValue>, ExecutionError>,
+        >,
     ) {
         assert!(!*self.finalized.borrow());
 
@@ -74,13 +87,25 @@ impl ExternalCircuitEnv {
             let plugin_name = &v.plugin.name;
 
             if !m.contains_key(plugin_name) {
-                if let Some(stat) = (v.plugin.ctx).as_ref().borrow().get_statics(k) {
+                if let Some(stat) = v.plugin.get_statics(k) {
                     m.insert(plugin_name.to_string(), stat);
                 }
             }
         }
         m
     }
+
+    /// The plugins of the registered functions. They share the contexts with the env, so the
+    /// statics could be queried for any K after the env is consumed by the execution.
+    pub fn plugins(&self) -> Vec<Rc<ForeignPlugin>> {
+        let mut plugins: Vec<Rc<ForeignPlugin>> = vec![];
+        for v in self.functions.values() {
+            if !plugins.iter().any(|plugin| plugin.name == v.plugin.name) {
+                plugins.push(v.plugin.clone());
+            }
+        }
+        plugins
+    }
 }
 
 impl ModuleImportResolver for ExternalCircuitEnv {
@@ -179,7 +179,8 @@ impl Externals for ExecEnv {
                 #[cfg(feature = "profile")]
                 let start = Instant::now();
 
-                let r = cb(&self.observer.borrow(), ctx, args);
+                let r =
+                    cb(&self.observer.borrow(), ctx, args).map_err(|err| Trap::Host(Box::new(err)));
 
                 #[cfg(feature = "profile")]
                 let duration = start.elapsed();
@@ -191,7 +192,7 @@ impl Externals for ExecEnv {
                     .and_modify(|d| *d += duration.as_millis())
                     .or_insert(duration.as_millis());
 
-                Ok(r)
+                r
             }
             None => unreachable!(),
         }
@@ -2,6 +2,7 @@ use self::default_env::ExecutionArg;
 use self::host_env::HostEnv;
 use downcast_rs::impl_downcast;
 use downcast_rs::Downcast;
+use serde::Serialize;
 use specs::external_host_call_table::ExternalHostCallSignature;
 use specs::host_function::HostFunctionDesc;
 use std::cell::RefCell;
@@ -12,6 +13,8 @@ use wasmi::Signature;
 
 use super::monitor::observer::Observer;
 use super::monitor::plugins::table::FlushStrategy;
+use crate::error::ExecutionError;
+use crate::foreign::output_helper::OutputChannel;
 
 pub mod default_env;
 pub mod external_circuit_plugin;
@@ -42,7 +45,7 @@ impl MatchForeignOpSignature for ExternalHostCallSignature {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ForeignStatics {
     pub used_round: usize,
     pub max_round: usize,
@@ -77,6 +80,10 @@ pub trait ForeignContext: Downcast {
     fn expose_context_outputs(&self) -> Vec<u64> {
         unreachable!()
     }
+
+    fn expose_output_channels(&self) -> Vec<OutputChannel> {
+        unreachable!()
+    }
 }
 impl_downcast!(ForeignContext);
 
@@ -85,10 +92,28 @@ pub struct ForeignPlugin {
     ctx: Rc<RefCell<Box<dyn ForeignContext>>>,
 }
 
+impl ForeignPlugin {
+    pub fn get_statics(&self, k: u32) -> Option<ForeignStatics> {
+        self.ctx.borrow().get_statics(k)
+    }
+
+    /// Calls `f` with the context if it's a `T`, so the host application could query the state
+    /// of a plugin after the execution.
+    pub fn with_context<T: ForeignContext, R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        self.ctx.borrow().downcast_ref::<T>().map(f)
+    }
+}
+
 #[derive(Clone)]
 struct HostFunctionExecutionEnv {
     ctx: Rc<RefCell<Box<dyn ForeignContext>>>,
-    cb: Rc<dyn Fn(&Observer, &mut dyn ForeignContext, RuntimeArgs) -> Option<RuntimeValue>>,
+    cb: Rc<
+        dyn Fn(
+            &Observer,
+            &mut dyn ForeignContext,
+            RuntimeArgs,
+        ) -> Result<Option<RuntimeValue>, ExecutionError>,
+    >,
 }
 
 #[derive(Clone)]
@@ -8,8 +8,10 @@ use specs::mtable::LocationType;
 use specs::mtable::MemoryTableEntry;
 use specs::mtable::VarType;
 use specs::step::StepInfo;
+use specs::trap::TrapCode;
 
 use crate::foreign::context::ContextOutput;
+use crate::foreign::output_helper::OutputChannel;
 
 use self::host::ForeignStatics;
 use self::wasmi_interpreter::WasmiRuntime;
@@ -34,6 +36,11 @@ pub struct ExecutionResult<R> {
     pub guest_statics: usize, // total instructions used in guest circuits
     pub outputs: Vec<u64>,
     pub context_outputs: ContextOutput,
+    /// The values of the output channels configured by `ExecutionArg::output_channels`.
+    pub output_channels: Vec<OutputChannel>,
+    /// The trap terminating the execution if it's run with the trap mode of the loader, the
+    /// trap code is the last public instance.
+    pub trap: Option<TrapCode>,
 }
 
 impl<R> ExecutionResult<R> {
@@ -342,6 +349,7 @@ pub fn memory_event_of_step(event: &EventTableEntry) -> Vec<MemoryTableEntry> {
             ops
         }
         StepInfo::Drop { .. } => vec![],
+        StepInfo::Unreachable => vec![],
         StepInfo::Select {
             val1,
             val2,
@@ -933,6 +941,62 @@ pub fn memory_event_of_step(event: &EventTableEntry) -> Vec<MemoryTableEntry> {
             &[*value as u64],
             &[*result as u64],
         ),
+
+        StepInfo::FloatUnaryOp {
+            vtype,
+            operand,
+            result,
+            ..
+        } => mem_op_from_stack_only_step(
+            sp_before_execution,
+            eid,
+            vtype.into(),
+            vtype.into(),
+            &[*operand],
+            &[*result],
+        ),
+        StepInfo::FloatBinOp {
+            vtype,
+            left,
+            right,
+            value,
+            ..
+        } => mem_op_from_stack_only_step(
+            sp_before_execution,
+            eid,
+            vtype.into(),
+            vtype.into(),
+            &[*right, *left],
+            &[*value],
+        ),
+        StepInfo::FloatComp {
+            vtype,
+            left,
+            right,
+            value,
+            ..
+        } => mem_op_from_stack_only_step(
+            sp_before_execution,
+            eid,
+            vtype.into(),
+            VarType::I32,
+            &[*right, *left],
+            &[*value as u64],
+        ),
+        StepInfo::FloatConversion {
+            from,
+            to,
+            value,
+            result,
+            ..
+        } => mem_op_from_stack_only_step(
+            sp_before_execution,
+            eid,
+            from.into(),
+            to.into(),
+            &[*value],
+            &[*result],
+        ),
     }
 }
 
@@ -1,6 +1,7 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use specs::trap::TrapCode;
 use wasmi::monitor::Monitor;
 
 use self::observer::Observer;
@@ -12,4 +13,12 @@ pub mod table_monitor;
 
 pub trait WasmiMonitor: Monitor {
     fn expose_observer(&self) -> Rc<RefCell<Observer>>;
+
+    /// Called before the execution in the trap mode of the loader.
+    fn enable_trap(&mut self) {}
+
+    /// Called if the execution is terminated by `trap` in the trap mode of the loader.
+    fn terminate_with_trap(&mut self, _trap: TrapCode) -> anyhow::Result<()> {
+        Ok(())
+    }
 }
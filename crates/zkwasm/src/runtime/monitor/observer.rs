@@ -3,3 +3,11 @@ pub struct Observer {
     pub counter: usize,
     pub is_in_phantom: bool,
 }
+
+impl Observer {
+    /// eid of the executing instruction, which is the host call when it's queried by a host
+    /// function.
+    pub fn current_eid(&self) -> usize {
+        self.counter + 1
+    }
+}
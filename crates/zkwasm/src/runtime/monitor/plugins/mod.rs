@@ -1,3 +1,4 @@
 pub mod phantom;
+pub mod profiler;
 pub mod statistic;
 pub mod table;
@@ -0,0 +1,410 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::Write;
+
+use serde::Serialize;
+use wasmi::func::FuncInstanceInternal;
+use wasmi::isa::DropKeep;
+use wasmi::isa::Instruction;
+use wasmi::isa::Keep;
+use wasmi::isa::Target;
+use wasmi::monitor::Monitor;
+use wasmi::runner::effective_address;
+use wasmi::runner::FromValueInternal;
+use wasmi::runner::FunctionContext;
+use wasmi::runner::InstructionOutcome;
+use wasmi::runner::ValueStack;
+use wasmi::RuntimeValue;
+use wasmi::Trap;
+
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+pub struct ProfileCounter {
+    pub instructions: u64,
+    /// Estimated memory table entries, i.e. the accesses of stack, globals and linear memory.
+    pub memory_ops: u64,
+    pub host_calls: u64,
+}
+
+impl ProfileCounter {
+    fn add(&mut self, other: &ProfileCounter) {
+        self.instructions += other.instructions;
+        self.memory_ops += other.memory_ops;
+        self.host_calls += other.host_calls;
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionProfile {
+    pub fid: u32,
+    pub name: Option<String>,
+    pub calls: u64,
+    /// Including the callees.
+    pub inclusive: ProfileCounter,
+    pub exclusive: ProfileCounter,
+}
+
+struct CallNode {
+    // `None` for the root, which is the caller of the entry function.
+    fid: Option<u32>,
+    parent: usize,
+    children: HashMap<u32, usize>,
+    calls: u64,
+    counter: ProfileCounter,
+}
+
+impl CallNode {
+    fn new(fid: Option<u32>, parent: usize) -> Self {
+        CallNode {
+            fid,
+            parent,
+            children: HashMap::default(),
+            calls: 0,
+            counter: ProfileCounter::default(),
+        }
+    }
+}
+
+/// Record the call tree of the guest, attributing the instructions to the call stack.
+///
+/// Instructions of phantom functions are not filtered by the plugin itself, the monitor
+/// should skip the hooks while running in a phantom function.
+pub struct ProfilerPlugin {
+    function_names: HashMap<u32, String>,
+    nodes: Vec<CallNode>,
+    current: usize,
+    // Blocks of linear memory accessed by the current load/store instruction.
+    accessed_blocks: u64,
+}
+
+impl Default for ProfilerPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProfilerPlugin {
+    pub fn new() -> Self {
+        Self {
+            function_names: HashMap::default(),
+            nodes: vec![CallNode::new(None, 0)],
+            current: 0,
+            accessed_blocks: 0,
+        }
+    }
+
+    fn enter(&mut self, fid: u32) {
+        let next = self.nodes.len();
+        let child = *self.nodes[self.current].children.entry(fid).or_insert(next);
+
+        if child == next {
+            self.nodes.push(CallNode::new(Some(fid), self.current));
+        }
+
+        self.nodes[child].calls += 1;
+        self.current = child;
+    }
+
+    fn leave(&mut self) {
+        self.current = self.nodes[self.current].parent;
+    }
+
+    fn name_of(&self, fid: u32) -> String {
+        self.function_names
+            .get(&fid)
+            .cloned()
+            .unwrap_or_else(|| format!("func[{}]", fid))
+    }
+
+    /// Profiles of the executed functions, the most expensive function comes first.
+    pub fn functions(&self) -> Vec<FunctionProfile> {
+        let mut functions: HashMap<u32, FunctionProfile> = HashMap::new();
+
+        for node in self.nodes.iter() {
+            let fid = match node.fid {
+                Some(fid) => fid,
+                None => continue,
+            };
+
+            let function = functions.entry(fid).or_insert_with(|| FunctionProfile {
+                fid,
+                name: self.function_names.get(&fid).cloned(),
+                calls: 0,
+                inclusive: ProfileCounter::default(),
+                exclusive: ProfileCounter::default(),
+            });
+            function.calls += node.calls;
+            function.exclusive.add(&node.counter);
+        }
+
+        for node in self.nodes.iter() {
+            // A recursive function is counted once for each node.
+            let mut visited = HashSet::new();
+            let mut ancestor = node;
+
+            while let Some(fid) = ancestor.fid {
+                if visited.insert(fid) {
+                    functions
+                        .get_mut(&fid)
+                        .unwrap()
+                        .inclusive
+                        .add(&node.counter);
+                }
+                ancestor = &self.nodes[ancestor.parent];
+            }
+        }
+
+        let mut functions = functions.into_values().collect::<Vec<_>>();
+        functions.sort_by(|a, b| {
+            b.exclusive
+                .instructions
+                .cmp(&a.exclusive.instructions)
+                .then(a.fid.cmp(&b.fid))
+        });
+        functions
+    }
+
+    /// Write the instructions of each call stack in the folded format, e.g.
+    /// `zkmain;foo;bar 42`, which is accepted by `flamegraph.pl` and `inferno`.
+    pub fn write_folded(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        for (index, node) in self.nodes.iter().enumerate() {
+            if node.counter.instructions == 0 {
+                continue;
+            }
+
+            let mut stack = vec![];
+            let mut ancestor = index;
+            while let Some(fid) = self.nodes[ancestor].fid {
+                stack.push(self.name_of(fid));
+                ancestor = self.nodes[ancestor].parent;
+            }
+            stack.reverse();
+
+            writeln!(writer, "{} {}", stack.join(";"), node.counter.instructions)?;
+        }
+
+        Ok(())
+    }
+}
+
+// The offset, the size in bytes and whether it's a store of load/store instructions.
+fn memory_access(instruction: &Instruction) -> Option<(u32, u32, bool)> {
+    match *instruction {
+        Instruction::I32Load8S(offset)
+        | Instruction::I32Load8U(offset)
+        | Instruction::I64Load8S(offset)
+        | Instruction::I64Load8U(offset) => Some((offset, 1, false)),
+        Instruction::I32Load16S(offset)
+        | Instruction::I32Load16U(offset)
+        | Instruction::I64Load16S(offset)
+        | Instruction::I64Load16U(offset) => Some((offset, 2, false)),
+        Instruction::I32Load(offset)
+        | Instruction::F32Load(offset)
+        | Instruction::I64Load32S(offset)
+        | Instruction::I64Load32U(offset) => Some((offset, 4, false)),
+        Instruction::I64Load(offset) | Instruction::F64Load(offset) => Some((offset, 8, false)),
+        Instruction::I32Store8(offset) | Instruction::I64Store8(offset) => Some((offset, 1, true)),
+        Instruction::I32Store16(offset) | Instruction::I64Store16(offset) => {
+            Some((offset, 2, true))
+        }
+        Instruction::I32Store(offset)
+        | Instruction::F32Store(offset)
+        | Instruction::I64Store32(offset) => Some((offset, 4, true)),
+        Instruction::I64Store(offset) | Instruction::F64Store(offset) => Some((offset, 8, true)),
+        _ => None,
+    }
+}
+
+fn keep(drop_keep: DropKeep) -> u64 {
+    matches!(drop_keep.keep, Keep::Single(_)) as u64
+}
+
+// Accesses of stack and globals, follows `memory_event_of_step` and assumes branches are taken.
+fn stack_ops(instruction: &Instruction) -> u64 {
+    match *instruction {
+        Instruction::Br(Target { drop_keep, .. }) | Instruction::Return(drop_keep) => {
+            keep(drop_keep) * 2
+        }
+        Instruction::BrIfEqz(Target { drop_keep, .. })
+        | Instruction::BrIfNez(Target { drop_keep, .. }) => 1 + keep(drop_keep) * 2,
+        Instruction::BrTable(..) | Instruction::CallIndirect(..) => 1,
+        Instruction::Unreachable | Instruction::Drop | Instruction::Call(..) => 0,
+        Instruction::Select(..) => 4,
+        Instruction::CurrentMemory
+        | Instruction::I32Const(..)
+        | Instruction::I64Const(..)
+        | Instruction::F32Const(..)
+        | Instruction::F64Const(..) => 1,
+        Instruction::I32Eq
+        | Instruction::I32Ne
+        | Instruction::I32LtS
+        | Instruction::I32LtU
+        | Instruction::I32GtS
+        | Instruction::I32GtU
+        | Instruction::I32LeS
+        | Instruction::I32LeU
+        | Instruction::I32GeS
+        | Instruction::I32GeU
+        | Instruction::I64Eq
+        | Instruction::I64Ne
+        | Instruction::I64LtS
+        | Instruction::I64LtU
+        | Instruction::I64GtS
+        | Instruction::I64GtU
+        | Instruction::I64LeS
+        | Instruction::I64LeU
+        | Instruction::I64GeS
+        | Instruction::I64GeU
+        | Instruction::I32Add
+        | Instruction::I32Sub
+        | Instruction::I32Mul
+        | Instruction::I32DivS
+        | Instruction::I32DivU
+        | Instruction::I32RemS
+        | Instruction::I32RemU
+        | Instruction::I32And
+        | Instruction::I32Or
+        | Instruction::I32Xor
+        | Instruction::I32Shl
+        | Instruction::I32ShrS
+        | Instruction::I32ShrU
+        | Instruction::I32Rotl
+        | Instruction::I32Rotr
+        | Instruction::I64Add
+        | Instruction::I64Sub
+        | Instruction::I64Mul
+        | Instruction::I64DivS
+        | Instruction::I64DivU
+        | Instruction::I64RemS
+        | Instruction::I64RemU
+        | Instruction::I64And
+        | Instruction::I64Or
+        | Instruction::I64Xor
+        | Instruction::I64Shl
+        | Instruction::I64ShrS
+        | Instruction::I64ShrU
+        | Instruction::I64Rotl
+        | Instruction::I64Rotr
+        | Instruction::F32Eq
+        | Instruction::F32Ne
+        | Instruction::F32Lt
+        | Instruction::F32Gt
+        | Instruction::F32Le
+        | Instruction::F32Ge
+        | Instruction::F64Eq
+        | Instruction::F64Ne
+        | Instruction::F64Lt
+        | Instruction::F64Gt
+        | Instruction::F64Le
+        | Instruction::F64Ge
+        | Instruction::F32Add
+        | Instruction::F32Sub
+        | Instruction::F32Mul
+        | Instruction::F32Div
+        | Instruction::F32Min
+        | Instruction::F32Max
+        | Instruction::F32Copysign
+        | Instruction::F64Add
+        | Instruction::F64Sub
+        | Instruction::F64Mul
+        | Instruction::F64Div
+        | Instruction::F64Min
+        | Instruction::F64Max
+        | Instruction::F64Copysign => 3,
+        // Locals, globals, loads, stores, memory.grow and the unary operators read one value
+        // and write one value.
+        _ => 2,
+    }
+}
+
+impl Monitor for ProfilerPlugin {
+    fn register_module(
+        &mut self,
+        module: &parity_wasm::elements::Module,
+        _module_ref: &wasmi::ModuleRef,
+        _entry: &str,
+    ) -> Result<(), wasmi::Error> {
+        if let Some(functions) = module.names_section().and_then(|names| names.functions()) {
+            self.function_names = functions
+                .names()
+                .iter()
+                .map(|(fid, name)| (fid, name.clone()))
+                .collect();
+        }
+
+        Ok(())
+    }
+
+    fn invoke_instruction_pre_hook(
+        &mut self,
+        value_stack: &ValueStack,
+        _function_context: &FunctionContext,
+        instruction: &Instruction,
+    ) {
+        self.accessed_blocks = match memory_access(instruction) {
+            Some((offset, size, is_store)) => {
+                let raw_address = <u32>::from_value_internal(if is_store {
+                    *value_stack.pick(2)
+                } else {
+                    *value_stack.top()
+                });
+
+                // A block is read and then written by a store.
+                let blocks = match effective_address(offset, raw_address) {
+                    Ok(address) if address % 8 + size > 8 => 2,
+                    _ => 1,
+                };
+
+                if is_store {
+                    blocks * 2
+                } else {
+                    blocks
+                }
+            }
+            None => 0,
+        };
+    }
+
+    fn invoke_instruction_post_hook(
+        &mut self,
+        fid: u32,
+        _iid: u32,
+        _sp: u32,
+        _allocated_memory_pages: u32,
+        _value_stack: &ValueStack,
+        _function_context: &FunctionContext,
+        instruction: &Instruction,
+        outcome: &InstructionOutcome,
+    ) -> Result<(), Trap> {
+        // The entry function and the start function are called by the root.
+        if self.current == 0 {
+            self.enter(fid);
+        }
+
+        let accessed_blocks = std::mem::take(&mut self.accessed_blocks);
+
+        let counter = &mut self.nodes[self.current].counter;
+        counter.instructions += 1;
+        counter.memory_ops += stack_ops(instruction) + accessed_blocks;
+
+        match outcome {
+            InstructionOutcome::ExecuteCall(func_ref) => match func_ref.as_internal() {
+                FuncInstanceInternal::Internal { index, .. } => self.enter(*index as u32),
+                _ => {
+                    let signature = func_ref.signature();
+
+                    // Arguments are read from the stack and the result is written back.
+                    counter.memory_ops +=
+                        signature.params().len() as u64 + signature.return_type().is_some() as u64;
+                }
+            },
+            InstructionOutcome::Return(..) => self.leave(),
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn invoke_call_host_post_hook(&mut self, _return_value: Option<RuntimeValue>) {
+        self.nodes[self.current].counter.host_calls += 1;
+    }
+}
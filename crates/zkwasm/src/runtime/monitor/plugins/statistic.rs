@@ -93,8 +93,11 @@ impl Monitor for StatisticPlugin {
                             // Call wasm_input host function
                             self.observer.borrow_mut().counter += 1;
                             // Convert if needed
-                            self.observer.borrow_mut().counter +=
-                                (!matches!(t, ValueType::I64)) as usize;
+                            self.observer.borrow_mut().counter += match t {
+                                ValueType::I64 => 0,
+                                ValueType::I32 | ValueType::F64 => 1,
+                                ValueType::F32 => 2,
+                            };
                         }
                         // Return
                         self.observer.borrow_mut().counter += 1;
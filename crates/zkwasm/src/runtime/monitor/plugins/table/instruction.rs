@@ -4,6 +4,10 @@ use specs::itable::BinOp;
 use specs::itable::BitOp;
 use specs::itable::BrTarget;
 use specs::itable::ConversionOp;
+use specs::itable::FloatBinOp;
+use specs::itable::FloatConversionOp;
+use specs::itable::FloatRelOp;
+use specs::itable::FloatUnaryOp;
 use specs::itable::Opcode;
 use specs::itable::RelOp;
 use specs::itable::ShiftOp;
@@ -53,8 +57,14 @@ impl PhantomFunction {
 
             instructions.push(Instruction::Call(wasm_input_function_idx));
 
-            if sig.return_type() != Some(wasmi::ValueType::I64) {
-                instructions.push(Instruction::I32WrapI64);
+            match sig.return_type() {
+                Some(wasmi::ValueType::I32) => instructions.push(Instruction::I32WrapI64),
+                Some(wasmi::ValueType::F32) => {
+                    instructions.push(Instruction::I32WrapI64);
+                    instructions.push(Instruction::F32ReinterpretI32);
+                }
+                Some(wasmi::ValueType::F64) => instructions.push(Instruction::F64ReinterpretI64),
+                _ => (),
             }
         }
 
@@ -71,6 +81,115 @@ impl PhantomFunction {
     }
 }
 
+fn float_unary_op(instruction: &Instruction) -> Option<(FloatUnaryOp, specs::types::ValueType)> {
+    use specs::types::ValueType::*;
+
+    let op = match instruction {
+        Instruction::F32Abs => (FloatUnaryOp::Abs, F32),
+        Instruction::F32Neg => (FloatUnaryOp::Neg, F32),
+        Instruction::F32Ceil => (FloatUnaryOp::Ceil, F32),
+        Instruction::F32Floor => (FloatUnaryOp::Floor, F32),
+        Instruction::F32Trunc => (FloatUnaryOp::Trunc, F32),
+        Instruction::F32Nearest => (FloatUnaryOp::Nearest, F32),
+        Instruction::F32Sqrt => (FloatUnaryOp::Sqrt, F32),
+        Instruction::F64Abs => (FloatUnaryOp::Abs, F64),
+        Instruction::F64Neg => (FloatUnaryOp::Neg, F64),
+        Instruction::F64Ceil => (FloatUnaryOp::Ceil, F64),
+        Instruction::F64Floor => (FloatUnaryOp::Floor, F64),
+        Instruction::F64Trunc => (FloatUnaryOp::Trunc, F64),
+        Instruction::F64Nearest => (FloatUnaryOp::Nearest, F64),
+        Instruction::F64Sqrt => (FloatUnaryOp::Sqrt, F64),
+        _ => return None,
+    };
+
+    Some(op)
+}
+
+fn float_bin_op(instruction: &Instruction) -> Option<(FloatBinOp, specs::types::ValueType)> {
+    use specs::types::ValueType::*;
+
+    let op = match instruction {
+        Instruction::F32Add => (FloatBinOp::Add, F32),
+        Instruction::F32Sub => (FloatBinOp::Sub, F32),
+        Instruction::F32Mul => (FloatBinOp::Mul, F32),
+        Instruction::F32Div => (FloatBinOp::Div, F32),
+        Instruction::F32Min => (FloatBinOp::Min, F32),
+        Instruction::F32Max => (FloatBinOp::Max, F32),
+        Instruction::F32Copysign => (FloatBinOp::Copysign, F32),
+        Instruction::F64Add => (FloatBinOp::Add, F64),
+        Instruction::F64Sub => (FloatBinOp::Sub, F64),
+        Instruction::F64Mul => (FloatBinOp::Mul, F64),
+        Instruction::F64Div => (FloatBinOp::Div, F64),
+        Instruction::F64Min => (FloatBinOp::Min, F64),
+        Instruction::F64Max => (FloatBinOp::Max, F64),
+        Instruction::F64Copysign => (FloatBinOp::Copysign, F64),
+        _ => return None,
+    };
+
+    Some(op)
+}
+
+fn float_rel_op(instruction: &Instruction) -> Option<(FloatRelOp, specs::types::ValueType)> {
+    use specs::types::ValueType::*;
+
+    let op = match instruction {
+        Instruction::F32Eq => (FloatRelOp::Eq, F32),
+        Instruction::F32Ne => (FloatRelOp::Ne, F32),
+        Instruction::F32Lt => (FloatRelOp::Lt, F32),
+        Instruction::F32Gt => (FloatRelOp::Gt, F32),
+        Instruction::F32Le => (FloatRelOp::Le, F32),
+        Instruction::F32Ge => (FloatRelOp::Ge, F32),
+        Instruction::F64Eq => (FloatRelOp::Eq, F64),
+        Instruction::F64Ne => (FloatRelOp::Ne, F64),
+        Instruction::F64Lt => (FloatRelOp::Lt, F64),
+        Instruction::F64Gt => (FloatRelOp::Gt, F64),
+        Instruction::F64Le => (FloatRelOp::Le, F64),
+        Instruction::F64Ge => (FloatRelOp::Ge, F64),
+        _ => return None,
+    };
+
+    Some(op)
+}
+
+fn float_conversion_op(
+    instruction: &Instruction,
+) -> Option<(
+    FloatConversionOp,
+    specs::types::ValueType,
+    specs::types::ValueType,
+)> {
+    use specs::types::ValueType::*;
+    use FloatConversionOp::*;
+
+    let op = match instruction {
+        Instruction::I32TruncSF32 => (TruncSigned, F32, I32),
+        Instruction::I32TruncUF32 => (TruncUnsigned, F32, I32),
+        Instruction::I32TruncSF64 => (TruncSigned, F64, I32),
+        Instruction::I32TruncUF64 => (TruncUnsigned, F64, I32),
+        Instruction::I64TruncSF32 => (TruncSigned, F32, I64),
+        Instruction::I64TruncUF32 => (TruncUnsigned, F32, I64),
+        Instruction::I64TruncSF64 => (TruncSigned, F64, I64),
+        Instruction::I64TruncUF64 => (TruncUnsigned, F64, I64),
+        Instruction::F32ConvertSI32 => (ConvertSigned, I32, F32),
+        Instruction::F32ConvertUI32 => (ConvertUnsigned, I32, F32),
+        Instruction::F32ConvertSI64 => (ConvertSigned, I64, F32),
+        Instruction::F32ConvertUI64 => (ConvertUnsigned, I64, F32),
+        Instruction::F32DemoteF64 => (Demote, F64, F32),
+        Instruction::F64ConvertSI32 => (ConvertSigned, I32, F64),
+        Instruction::F64ConvertUI32 => (ConvertUnsigned, I32, F64),
+        Instruction::F64ConvertSI64 => (ConvertSigned, I64, F64),
+        Instruction::F64ConvertUI64 => (ConvertUnsigned, I64, F64),
+        Instruction::F64PromoteF32 => (Promote, F32, F64),
+        Instruction::I32ReinterpretF32 => (Reinterpret, F32, I32),
+        Instruction::I64ReinterpretF64 => (Reinterpret, F64, I64),
+        Instruction::F32ReinterpretI32 => (Reinterpret, I32, F32),
+        Instruction::F64ReinterpretI64 => (Reinterpret, I64, F64),
+        _ => return None,
+    };
+
+    Some(op)
+}
+
 pub(super) trait InstructionIntoOpcode {
     fn into_opcode<'a>(self, function_mapping: &impl Fn(u32) -> &'a FuncDesc) -> Opcode;
 }
@@ -184,8 +303,16 @@ impl<'a> InstructionIntoOpcode for wasmi::isa::Instruction<'a> {
                 vtype: VarType::I64,
                 size: MemoryReadSize::I64,
             },
-            Instruction::F32Load(_) => todo!(),
-            Instruction::F64Load(_) => todo!(),
+            Instruction::F32Load(offset) => Opcode::Load {
+                offset,
+                vtype: VarType::I32,
+                size: MemoryReadSize::U32,
+            },
+            Instruction::F64Load(offset) => Opcode::Load {
+                offset,
+                vtype: VarType::I64,
+                size: MemoryReadSize::I64,
+            },
             Instruction::I32Load8S(offset) => Opcode::Load {
                 offset,
                 vtype: VarType::I32,
@@ -246,8 +373,16 @@ impl<'a> InstructionIntoOpcode for wasmi::isa::Instruction<'a> {
                 vtype: VarType::I64,
                 size: MemoryStoreSize::Byte64,
             },
-            Instruction::F32Store(_) => todo!(),
-            Instruction::F64Store(_) => todo!(),
+            Instruction::F32Store(offset) => Opcode::Store {
+                offset,
+                vtype: VarType::I32,
+                size: MemoryStoreSize::Byte32,
+            },
+            Instruction::F64Store(offset) => Opcode::Store {
+                offset,
+                vtype: VarType::I64,
+                size: MemoryStoreSize::Byte64,
+            },
             Instruction::I32Store8(offset) => Opcode::Store {
                 offset,
                 vtype: VarType::I32,
@@ -283,8 +418,14 @@ impl<'a> InstructionIntoOpcode for wasmi::isa::Instruction<'a> {
                 vtype: VarType::I64,
                 value: v as u64,
             },
-            Instruction::F32Const(_) => todo!(),
-            Instruction::F64Const(_) => todo!(),
+            Instruction::F32Const(v) => Opcode::Const {
+                vtype: VarType::I32,
+                value: v as u64,
+            },
+            Instruction::F64Const(v) => Opcode::Const {
+                vtype: VarType::I64,
+                value: v,
+            },
             Instruction::I32Eqz => Opcode::Test {
                 class: TestOp::Eqz,
                 vtype: VarType::I32,
@@ -373,18 +514,21 @@ impl<'a> InstructionIntoOpcode for wasmi::isa::Instruction<'a> {
                 class: RelOp::UnsignedGe,
                 vtype: VarType::I64,
             },
-            Instruction::F32Eq => todo!(),
-            Instruction::F32Ne => todo!(),
-            Instruction::F32Lt => todo!(),
-            Instruction::F32Gt => todo!(),
-            Instruction::F32Le => todo!(),
-            Instruction::F32Ge => todo!(),
-            Instruction::F64Eq => todo!(),
-            Instruction::F64Ne => todo!(),
-            Instruction::F64Lt => todo!(),
-            Instruction::F64Gt => todo!(),
-            Instruction::F64Le => todo!(),
-            Instruction::F64Ge => todo!(),
+            Instruction::F32Eq
+            | Instruction::F32Ne
+            | Instruction::F32Lt
+            | Instruction::F32Gt
+            | Instruction::F32Le
+            | Instruction::F32Ge
+            | Instruction::F64Eq
+            | Instruction::F64Ne
+            | Instruction::F64Lt
+            | Instruction::F64Gt
+            | Instruction::F64Le
+            | Instruction::F64Ge => {
+                let (class, vtype) = float_rel_op(&self).unwrap();
+                Opcode::FloatRel { class, vtype }
+            }
             Instruction::I32Clz => Opcode::Unary {
                 class: UnaryOp::Clz,
                 vtype: VarType::I32,
@@ -529,65 +673,74 @@ impl<'a> InstructionIntoOpcode for wasmi::isa::Instruction<'a> {
                 class: ShiftOp::Rotr,
                 vtype: VarType::I64,
             },
-            Instruction::F32Abs => todo!(),
-            Instruction::F32Neg => todo!(),
-            Instruction::F32Ceil => todo!(),
-            Instruction::F32Floor => todo!(),
-            Instruction::F32Trunc => todo!(),
-            Instruction::F32Nearest => todo!(),
-            Instruction::F32Sqrt => todo!(),
-            Instruction::F32Add => todo!(),
-            Instruction::F32Sub => todo!(),
-            Instruction::F32Mul => todo!(),
-            Instruction::F32Div => todo!(),
-            Instruction::F32Min => todo!(),
-            Instruction::F32Max => todo!(),
-            Instruction::F32Copysign => todo!(),
-            Instruction::F64Abs => todo!(),
-            Instruction::F64Neg => todo!(),
-            Instruction::F64Ceil => todo!(),
-            Instruction::F64Floor => todo!(),
-            Instruction::F64Trunc => todo!(),
-            Instruction::F64Nearest => todo!(),
-            Instruction::F64Sqrt => todo!(),
-            Instruction::F64Add => todo!(),
-            Instruction::F64Sub => todo!(),
-            Instruction::F64Mul => todo!(),
-            Instruction::F64Div => todo!(),
-            Instruction::F64Min => todo!(),
-            Instruction::F64Max => todo!(),
-            Instruction::F64Copysign => todo!(),
+            Instruction::F32Abs
+            | Instruction::F32Neg
+            | Instruction::F32Ceil
+            | Instruction::F32Floor
+            | Instruction::F32Trunc
+            | Instruction::F32Nearest
+            | Instruction::F32Sqrt
+            | Instruction::F64Abs
+            | Instruction::F64Neg
+            | Instruction::F64Ceil
+            | Instruction::F64Floor
+            | Instruction::F64Trunc
+            | Instruction::F64Nearest
+            | Instruction::F64Sqrt => {
+                let (class, vtype) = float_unary_op(&self).unwrap();
+                Opcode::FloatUnary { class, vtype }
+            }
+            Instruction::F32Add
+            | Instruction::F32Sub
+            | Instruction::F32Mul
+            | Instruction::F32Div
+            | Instruction::F32Min
+            | Instruction::F32Max
+            | Instruction::F32Copysign
+            | Instruction::F64Add
+            | Instruction::F64Sub
+            | Instruction::F64Mul
+            | Instruction::F64Div
+            | Instruction::F64Min
+            | Instruction::F64Max
+            | Instruction::F64Copysign => {
+                let (class, vtype) = float_bin_op(&self).unwrap();
+                Opcode::FloatBin { class, vtype }
+            }
             Instruction::I32WrapI64 => Opcode::Conversion {
                 class: ConversionOp::I32WrapI64,
             },
-            Instruction::I32TruncSF32 => todo!(),
-            Instruction::I32TruncUF32 => todo!(),
-            Instruction::I32TruncSF64 => todo!(),
-            Instruction::I32TruncUF64 => todo!(),
             Instruction::I64ExtendSI32 => Opcode::Conversion {
                 class: ConversionOp::I64ExtendI32s,
             },
             Instruction::I64ExtendUI32 => Opcode::Conversion {
                 class: ConversionOp::I64ExtendI32u,
             },
-            Instruction::I64TruncSF32 => todo!(),
-            Instruction::I64TruncUF32 => todo!(),
-            Instruction::I64TruncSF64 => todo!(),
-            Instruction::I64TruncUF64 => todo!(),
-            Instruction::F32ConvertSI32 => todo!(),
-            Instruction::F32ConvertUI32 => todo!(),
-            Instruction::F32ConvertSI64 => todo!(),
-            Instruction::F32ConvertUI64 => todo!(),
-            Instruction::F32DemoteF64 => todo!(),
-            Instruction::F64ConvertSI32 => todo!(),
-            Instruction::F64ConvertUI32 => todo!(),
-            Instruction::F64ConvertSI64 => todo!(),
-            Instruction::F64ConvertUI64 => todo!(),
-            Instruction::F64PromoteF32 => todo!(),
-            Instruction::I32ReinterpretF32 => todo!(),
-            Instruction::I64ReinterpretF64 => todo!(),
-            Instruction::F32ReinterpretI32 => todo!(),
-            Instruction::F64ReinterpretI64 => todo!(),
+            Instruction::I32TruncSF32
+            | Instruction::I32TruncUF32
+            | Instruction::I32TruncSF64
+            | Instruction::I32TruncUF64
+            | Instruction::I64TruncSF32
+            | Instruction::I64TruncUF32
+            | Instruction::I64TruncSF64
+            | Instruction::I64TruncUF64
+            | Instruction::F32ConvertSI32
+            | Instruction::F32ConvertUI32
+            | Instruction::F32ConvertSI64
+            | Instruction::F32ConvertUI64
+            | Instruction::F32DemoteF64
+            | Instruction::F64ConvertSI32
+            | Instruction::F64ConvertUI32
+            | Instruction::F64ConvertSI64
+            | Instruction::F64ConvertUI64
+            | Instruction::F64PromoteF32
+            | Instruction::I32ReinterpretF32
+            | Instruction::I64ReinterpretF64
+            | Instruction::F32ReinterpretI32
+            | Instruction::F64ReinterpretI64 => {
+                let (class, from, to) = float_conversion_op(&self).unwrap();
+                Opcode::FloatConversion { class, from, to }
+            }
             Instruction::I32Extend8S => Opcode::Conversion {
                 class: ConversionOp::I32Extend8S,
             },
@@ -706,6 +859,19 @@ pub(super) enum RunInstructionTracePre {
         val2: u64,
         cond: u64,
     },
+
+    FloatUnaryOp {
+        operand: u64,
+        vtype: specs::types::ValueType,
+    },
+    FloatBinOp {
+        left: u64,
+        right: u64,
+        vtype: specs::types::ValueType,
+    },
+    FloatConversion {
+        value: u64,
+    },
 }
 
 pub(super) fn run_instruction_pre(
@@ -761,12 +927,15 @@ pub(super) fn run_instruction_pre(
         }),
 
         isa::Instruction::I32Load(offset)
+        | isa::Instruction::F32Load(offset)
         | isa::Instruction::I32Load8S(offset)
         | isa::Instruction::I32Load8U(offset)
         | isa::Instruction::I32Load16S(offset)
         | isa::Instruction::I32Load16U(offset) => {
             let load_size = match *instructions {
-                isa::Instruction::I32Load(..) => MemoryReadSize::U32,
+                isa::Instruction::I32Load(..) | isa::Instruction::F32Load(..) => {
+                    MemoryReadSize::U32
+                }
                 isa::Instruction::I32Load8S(..) => MemoryReadSize::S8,
                 isa::Instruction::I32Load8U(..) => MemoryReadSize::U8,
                 isa::Instruction::I32Load16S(..) => MemoryReadSize::S16,
@@ -786,6 +955,7 @@ pub(super) fn run_instruction_pre(
             })
         }
         isa::Instruction::I64Load(offset)
+        | isa::Instruction::F64Load(offset)
         | isa::Instruction::I64Load8S(offset)
         | isa::Instruction::I64Load8U(offset)
         | isa::Instruction::I64Load16S(offset)
@@ -793,7 +963,9 @@ pub(super) fn run_instruction_pre(
         | isa::Instruction::I64Load32S(offset)
         | isa::Instruction::I64Load32U(offset) => {
             let load_size = match *instructions {
-                isa::Instruction::I64Load(..) => MemoryReadSize::I64,
+                isa::Instruction::I64Load(..) | isa::Instruction::F64Load(..) => {
+                    MemoryReadSize::I64
+                }
                 isa::Instruction::I64Load8S(..) => MemoryReadSize::S8,
                 isa::Instruction::I64Load8U(..) => MemoryReadSize::U8,
                 isa::Instruction::I64Load16S(..) => MemoryReadSize::S16,
@@ -814,12 +986,15 @@ pub(super) fn run_instruction_pre(
             })
         }
         isa::Instruction::I32Store(offset)
+        | isa::Instruction::F32Store(offset)
         | isa::Instruction::I32Store8(offset)
         | isa::Instruction::I32Store16(offset) => {
             let store_size = match *instructions {
                 isa::Instruction::I32Store8(_) => MemoryStoreSize::Byte8,
                 isa::Instruction::I32Store16(_) => MemoryStoreSize::Byte16,
-                isa::Instruction::I32Store(_) => MemoryStoreSize::Byte32,
+                isa::Instruction::I32Store(_) | isa::Instruction::F32Store(_) => {
+                    MemoryStoreSize::Byte32
+                }
                 _ => unreachable!(),
             };
 
@@ -865,11 +1040,14 @@ pub(super) fn run_instruction_pre(
             })
         }
         isa::Instruction::I64Store(offset)
+        | isa::Instruction::F64Store(offset)
         | isa::Instruction::I64Store8(offset)
         | isa::Instruction::I64Store16(offset)
         | isa::Instruction::I64Store32(offset) => {
             let store_size = match *instructions {
-                isa::Instruction::I64Store(..) => MemoryStoreSize::Byte64,
+                isa::Instruction::I64Store(..) | isa::Instruction::F64Store(..) => {
+                    MemoryStoreSize::Byte64
+                }
                 isa::Instruction::I64Store8(..) => MemoryStoreSize::Byte8,
                 isa::Instruction::I64Store16(..) => MemoryStoreSize::Byte16,
                 isa::Instruction::I64Store32(..) => MemoryStoreSize::Byte32,
@@ -925,6 +1103,8 @@ pub(super) fn run_instruction_pre(
 
         isa::Instruction::I32Const(_) => None,
         isa::Instruction::I64Const(_) => None,
+        isa::Instruction::F32Const(_) => None,
+        isa::Instruction::F64Const(_) => None,
 
         isa::Instruction::I32Eqz => Some(RunInstructionTracePre::I32Single(
             <_>::from_value_internal(*value_stack.pick(1)),
@@ -1039,6 +1219,98 @@ pub(super) fn run_instruction_pre(
             value: <_>::from_value_internal(*value_stack.pick(1)),
         }),
 
+        isa::Instruction::F32Abs
+        | isa::Instruction::F32Neg
+        | isa::Instruction::F32Ceil
+        | isa::Instruction::F32Floor
+        | isa::Instruction::F32Trunc
+        | isa::Instruction::F32Nearest
+        | isa::Instruction::F32Sqrt
+        | isa::Instruction::F64Abs
+        | isa::Instruction::F64Neg
+        | isa::Instruction::F64Ceil
+        | isa::Instruction::F64Floor
+        | isa::Instruction::F64Trunc
+        | isa::Instruction::F64Nearest
+        | isa::Instruction::F64Sqrt => {
+            let (_, vtype) = float_unary_op(instructions).unwrap();
+
+            Some(RunInstructionTracePre::FloatUnaryOp {
+                operand: from_value_internal_to_u64_with_typ(vtype.into(), *value_stack.pick(1)),
+                vtype,
+            })
+        }
+        isa::Instruction::F32Add
+        | isa::Instruction::F32Sub
+        | isa::Instruction::F32Mul
+        | isa::Instruction::F32Div
+        | isa::Instruction::F32Min
+        | isa::Instruction::F32Max
+        | isa::Instruction::F32Copysign
+        | isa::Instruction::F64Add
+        | isa::Instruction::F64Sub
+        | isa::Instruction::F64Mul
+        | isa::Instruction::F64Div
+        | isa::Instruction::F64Min
+        | isa::Instruction::F64Max
+        | isa::Instruction::F64Copysign => {
+            let (_, vtype) = float_bin_op(instructions).unwrap();
+
+            Some(RunInstructionTracePre::FloatBinOp {
+                left: from_value_internal_to_u64_with_typ(vtype.into(), *value_stack.pick(2)),
+                right: from_value_internal_to_u64_with_typ(vtype.into(), *value_stack.pick(1)),
+                vtype,
+            })
+        }
+        isa::Instruction::F32Eq
+        | isa::Instruction::F32Ne
+        | isa::Instruction::F32Lt
+        | isa::Instruction::F32Gt
+        | isa::Instruction::F32Le
+        | isa::Instruction::F32Ge
+        | isa::Instruction::F64Eq
+        | isa::Instruction::F64Ne
+        | isa::Instruction::F64Lt
+        | isa::Instruction::F64Gt
+        | isa::Instruction::F64Le
+        | isa::Instruction::F64Ge => {
+            let (_, vtype) = float_rel_op(instructions).unwrap();
+
+            Some(RunInstructionTracePre::FloatBinOp {
+                left: from_value_internal_to_u64_with_typ(vtype.into(), *value_stack.pick(2)),
+                right: from_value_internal_to_u64_with_typ(vtype.into(), *value_stack.pick(1)),
+                vtype,
+            })
+        }
+        isa::Instruction::I32TruncSF32
+        | isa::Instruction::I32TruncUF32
+        | isa::Instruction::I32TruncSF64
+        | isa::Instruction::I32TruncUF64
+        | isa::Instruction::I64TruncSF32
+        | isa::Instruction::I64TruncUF32
+        | isa::Instruction::I64TruncSF64
+        | isa::Instruction::I64TruncUF64
+        | isa::Instruction::F32ConvertSI32
+        | isa::Instruction::F32ConvertUI32
+        | isa::Instruction::F32ConvertSI64
+        | isa::Instruction::F32ConvertUI64
+        | isa::Instruction::F32DemoteF64
+        | isa::Instruction::F64ConvertSI32
+        | isa::Instruction::F64ConvertUI32
+        | isa::Instruction::F64ConvertSI64
+        | isa::Instruction::F64ConvertUI64
+        | isa::Instruction::F64PromoteF32
+        | isa::Instruction::I32ReinterpretF32
+        | isa::Instruction::I64ReinterpretF64
+        | isa::Instruction::F32ReinterpretI32
+        | isa::Instruction::F64ReinterpretI64 => {
+            let (_, from, _) = float_conversion_op(instructions).unwrap();
+
+            Some(RunInstructionTracePre::FloatConversion {
+                value: from_value_internal_to_u64_with_typ(from.into(), *value_stack.pick(1)),
+            })
+        }
+
         _ => {
             println!("{:?}", *instructions);
             unimplemented!()
@@ -1330,11 +1602,13 @@ impl TablePlugin {
             }
 
             isa::Instruction::I32Load(..)
+            | isa::Instruction::F32Load(..)
             | isa::Instruction::I32Load8U(..)
             | isa::Instruction::I32Load8S(..)
             | isa::Instruction::I32Load16U(..)
             | isa::Instruction::I32Load16S(..)
             | isa::Instruction::I64Load(..)
+            | isa::Instruction::F64Load(..)
             | isa::Instruction::I64Load8U(..)
             | isa::Instruction::I64Load8S(..)
             | isa::Instruction::I64Load16U(..)
@@ -1392,9 +1666,11 @@ impl TablePlugin {
                 }
             }
             isa::Instruction::I32Store(..)
+            | isa::Instruction::F32Store(..)
             | isa::Instruction::I32Store8(..)
             | isa::Instruction::I32Store16(..)
             | isa::Instruction::I64Store(..)
+            | isa::Instruction::F64Store(..)
             | isa::Instruction::I64Store8(..)
             | isa::Instruction::I64Store16(..)
             | isa::Instruction::I64Store32(..) => {
@@ -1465,6 +1741,12 @@ impl TablePlugin {
 
             isa::Instruction::I32Const(value) => StepInfo::I32Const { value },
             isa::Instruction::I64Const(value) => StepInfo::I64Const { value },
+            isa::Instruction::F32Const(value) => StepInfo::I32Const {
+                value: value as i32,
+            },
+            isa::Instruction::F64Const(value) => StepInfo::I64Const {
+                value: value as i64,
+            },
 
             isa::Instruction::I32Eqz => {
                 if let RunInstructionTracePre::I32Single(value) = current_event.unwrap() {
@@ -2182,6 +2464,136 @@ impl TablePlugin {
                 }
             }
 
+            isa::Instruction::F32Abs
+            | isa::Instruction::F32Neg
+            | isa::Instruction::F32Ceil
+            | isa::Instruction::F32Floor
+            | isa::Instruction::F32Trunc
+            | isa::Instruction::F32Nearest
+            | isa::Instruction::F32Sqrt
+            | isa::Instruction::F64Abs
+            | isa::Instruction::F64Neg
+            | isa::Instruction::F64Ceil
+            | isa::Instruction::F64Floor
+            | isa::Instruction::F64Trunc
+            | isa::Instruction::F64Nearest
+            | isa::Instruction::F64Sqrt => {
+                if let RunInstructionTracePre::FloatUnaryOp { operand, vtype } =
+                    current_event.unwrap()
+                {
+                    let (class, _) = float_unary_op(instructions).unwrap();
+
+                    StepInfo::FloatUnaryOp {
+                        class,
+                        vtype,
+                        operand,
+                        result: from_value_internal_to_u64_with_typ(
+                            vtype.into(),
+                            *value_stack.top(),
+                        ),
+                    }
+                } else {
+                    unreachable!()
+                }
+            }
+            isa::Instruction::F32Add
+            | isa::Instruction::F32Sub
+            | isa::Instruction::F32Mul
+            | isa::Instruction::F32Div
+            | isa::Instruction::F32Min
+            | isa::Instruction::F32Max
+            | isa::Instruction::F32Copysign
+            | isa::Instruction::F64Add
+            | isa::Instruction::F64Sub
+            | isa::Instruction::F64Mul
+            | isa::Instruction::F64Div
+            | isa::Instruction::F64Min
+            | isa::Instruction::F64Max
+            | isa::Instruction::F64Copysign => {
+                if let RunInstructionTracePre::FloatBinOp { left, right, vtype } =
+                    current_event.unwrap()
+                {
+                    let (class, _) = float_bin_op(instructions).unwrap();
+
+                    StepInfo::FloatBinOp {
+                        class,
+                        vtype,
+                        left,
+                        right,
+                        value: from_value_internal_to_u64_with_typ(
+                            vtype.into(),
+                            *value_stack.top(),
+                        ),
+                    }
+                } else {
+                    unreachable!()
+                }
+            }
+            isa::Instruction::F32Eq
+            | isa::Instruction::F32Ne
+            | isa::Instruction::F32Lt
+            | isa::Instruction::F32Gt
+            | isa::Instruction::F32Le
+            | isa::Instruction::F32Ge
+            | isa::Instruction::F64Eq
+            | isa::Instruction::F64Ne
+            | isa::Instruction::F64Lt
+            | isa::Instruction::F64Gt
+            | isa::Instruction::F64Le
+            | isa::Instruction::F64Ge => {
+                if let RunInstructionTracePre::FloatBinOp { left, right, vtype } =
+                    current_event.unwrap()
+                {
+                    let (class, _) = float_rel_op(instructions).unwrap();
+
+                    StepInfo::FloatComp {
+                        class,
+                        vtype,
+                        left,
+                        right,
+                        value: <_>::from_value_internal(*value_stack.top()),
+                    }
+                } else {
+                    unreachable!()
+                }
+            }
+            isa::Instruction::I32TruncSF32
+            | isa::Instruction::I32TruncUF32
+            | isa::Instruction::I32TruncSF64
+            | isa::Instruction::I32TruncUF64
+            | isa::Instruction::I64TruncSF32
+            | isa::Instruction::I64TruncUF32
+            | isa::Instruction::I64TruncSF64
+            | isa::Instruction::I64TruncUF64
+            | isa::Instruction::F32ConvertSI32
+            | isa::Instruction::F32ConvertUI32
+            | isa::Instruction::F32ConvertSI64
+            | isa::Instruction::F32ConvertUI64
+            | isa::Instruction::F32DemoteF64
+            | isa::Instruction::F64ConvertSI32
+            | isa::Instruction::F64ConvertUI32
+            | isa::Instruction::F64ConvertSI64
+            | isa::Instruction::F64ConvertUI64
+            | isa::Instruction::F64PromoteF32
+            | isa::Instruction::I32ReinterpretF32
+            | isa::Instruction::I64ReinterpretF64
+            | isa::Instruction::F32ReinterpretI32
+            | isa::Instruction::F64ReinterpretI64 => {
+                if let RunInstructionTracePre::FloatConversion { value } = current_event.unwrap() {
+                    let (class, from, to) = float_conversion_op(instructions).unwrap();
+
+                    StepInfo::FloatConversion {
+                        class,
+                        from,
+                        to,
+                        value,
+                        result: from_value_internal_to_u64_with_typ(to.into(), *value_stack.top()),
+                    }
+                } else {
+                    unreachable!()
+                }
+            }
+
             _ => {
                 println!("{:?}", instructions);
                 unimplemented!()
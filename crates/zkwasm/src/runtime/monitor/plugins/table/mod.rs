@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::sync::Arc;
 
 use parity_wasm::elements::External;
@@ -11,12 +12,15 @@ use specs::host_function::HostFunctionDesc;
 use specs::host_function::HostPlugin;
 use specs::imtable::InitMemoryTable;
 use specs::imtable::InitMemoryTableEntry;
+use specs::itable::FloatConversionOp;
 use specs::itable::InstructionTable;
 use specs::itable::InstructionTableInternal;
 use specs::mtable::LocationType;
 use specs::mtable::VarType;
 use specs::state::InitializationState;
 use specs::step::StepInfo;
+use specs::trace::TraceError;
+use specs::trap::TrapCode;
 use specs::types::FunctionType;
 use specs::types::ValueType;
 use specs::CompilationTable;
@@ -35,6 +39,7 @@ use wasmi::monitor::Monitor;
 use wasmi::runner::from_value_internal_to_u64_with_typ;
 use wasmi::runner::FunctionContext;
 use wasmi::runner::InstructionOutcome;
+use wasmi::runner::ValueInternal;
 use wasmi::runner::ValueStack;
 use wasmi::Error;
 use wasmi::FuncRef;
@@ -115,6 +120,17 @@ pub struct TablePlugin {
     module_ref: Option<wasmi::ModuleRef>,
     unresolved_event: Option<RunInstructionTracePre>,
     unresolved_host_call: Option<EventTableEntry>,
+
+    // The functions invoked by the host, the start function goes before the entry.
+    invoked_fids: VecDeque<u32>,
+    // The positions below are only tracked in the trap mode.
+    allow_trap: bool,
+    // (fid, iid) of the next traced instruction and of the return address of each frame, they
+    // locate the `unreachable` which isn't passed to the post hook since it traps.
+    next_position: Option<(u32, u32)>,
+    return_positions: Vec<(u32, u32)>,
+    // (sp, allocated memory pages) before the `unreachable`.
+    unresolved_unreachable: Option<(u32, u32)>,
 }
 
 impl TablePlugin {
@@ -150,6 +166,12 @@ impl TablePlugin {
             module_ref: None,
             unresolved_event: None,
             unresolved_host_call: None,
+
+            invoked_fids: VecDeque::new(),
+            allow_trap: false,
+            next_position: None,
+            return_positions: vec![],
+            unresolved_unreachable: None,
         }
     }
 
@@ -191,11 +213,11 @@ impl TablePlugin {
         }
     }
 
-    pub fn into_tables(self) -> Tables {
+    pub fn into_tables(self) -> Result<Tables, TraceError> {
         let compilation_tables = self.into_compilation_table();
-        let slices = self.host_transaction.finalized();
+        let slices = self.host_transaction.finalized()?;
 
-        Tables {
+        Ok(Tables {
             compilation_tables,
             execution_tables: ExecutionTable {
                 etable: slices.etable,
@@ -204,7 +226,7 @@ impl TablePlugin {
                 context_input_table: self.context_input_table,
                 context_output_table: self.context_output_table,
             },
-        }
+        })
     }
 }
 
@@ -303,7 +325,10 @@ impl TablePlugin {
 
             iid += 1;
 
-            if callee_sig.return_type() != Some(wasmi::ValueType::I64) {
+            if matches!(
+                callee_sig.return_type(),
+                Some(wasmi::ValueType::I32) | Some(wasmi::ValueType::F32)
+            ) {
                 self.append_log(
                     fid,
                     iid,
@@ -318,6 +343,33 @@ impl TablePlugin {
 
                 iid += 1;
             }
+
+            if let Some(return_type @ (wasmi::ValueType::F32 | wasmi::ValueType::F64)) =
+                callee_sig.return_type()
+            {
+                let (from, to) = if return_type == wasmi::ValueType::F32 {
+                    (ValueType::I32, ValueType::F32)
+                } else {
+                    (ValueType::I64, ValueType::F64)
+                };
+
+                self.append_log(
+                    fid,
+                    iid,
+                    current_sp + 1,
+                    allocated_memory_pages,
+                    last_jump_eid,
+                    StepInfo::FloatConversion {
+                        class: FloatConversionOp::Reinterpret,
+                        from,
+                        to,
+                        value: keep_value.unwrap(),
+                        result: keep_value.unwrap(),
+                    },
+                );
+
+                iid += 1;
+            }
         }
 
         self.append_log(
@@ -373,9 +425,11 @@ impl Monitor for TablePlugin {
                     .push_static_entry(start_idx, *zkmain_idx as u32, 0);
 
                 self.start_fid = Some(start_idx);
+                self.invoked_fids.push_back(start_idx);
             } else {
                 self.start_fid = Some(*zkmain_idx as u32);
             }
+            self.invoked_fids.push_back(*zkmain_idx as u32);
         }
 
         {
@@ -476,12 +530,17 @@ impl Monitor for TablePlugin {
 
         // register globals
         for (global_idx, global_ref) in module_ref.globals().iter().enumerate() {
+            let vtype: VarType = global_ref.value_type().into_elements().into();
+
             self.init_memory_table.push(InitMemoryTableEntry {
                 is_mutable: global_ref.is_mutable(),
                 ltype: LocationType::Global,
                 offset: global_idx as u32,
-                vtype: global_ref.value_type().into_elements().into(),
-                value: global_ref.get().into(),
+                vtype,
+                value: from_value_internal_to_u64_with_typ(
+                    vtype,
+                    ValueInternal::from(global_ref.get()),
+                ),
                 eid: 0,
             });
         }
@@ -580,6 +639,11 @@ impl Monitor for TablePlugin {
 
     fn invoke_exported_function_pre_hook(&mut self) {
         self.last_jump_eid.push(0);
+
+        let fid = self.invoked_fids.pop_front();
+        if self.allow_trap {
+            self.next_position = fid.map(|fid| (fid, 0));
+        }
     }
 
     fn invoke_instruction_pre_hook(
@@ -593,6 +657,14 @@ impl Monitor for TablePlugin {
             return;
         }
         self.unresolved_event = run_instruction_pre(value_stack, function_context, instruction);
+
+        if self.allow_trap && matches!(instruction, Instruction::Unreachable) {
+            let allocated_memory_pages = function_context
+                .memory()
+                .map_or(0, |memory| memory.current_size().0 as u32);
+
+            self.unresolved_unreachable = Some((value_stack.len() as u32, allocated_memory_pages));
+        }
     }
 
     fn invoke_instruction_post_hook(
@@ -607,6 +679,24 @@ impl Monitor for TablePlugin {
         outcome: &InstructionOutcome,
     ) -> Result<(), Trap> {
         if !self.phantom_helper.is_in_phantom_function() {
+            if self.allow_trap {
+                self.next_position = match outcome {
+                    InstructionOutcome::RunNextInstruction => Some((fid, iid + 1)),
+                    InstructionOutcome::Branch(target) => Some((fid, target.dst_pc)),
+                    InstructionOutcome::ExecuteCall(func_ref) => match func_ref.as_internal() {
+                        FuncInstanceInternal::Internal { index, .. }
+                            if !self.phantom_helper.is_phantom_function(*index as u32) =>
+                        {
+                            self.return_positions.push((fid, iid + 1));
+
+                            Some((*index as u32, 0))
+                        }
+                        _ => Some((fid, iid + 1)),
+                    },
+                    InstructionOutcome::Return(..) => self.return_positions.pop(),
+                };
+            }
+
             let current_event = self.unresolved_event.take();
 
             let step_info = self.run_instruction_post(
@@ -753,3 +843,66 @@ impl Monitor for TablePlugin {
         );
     }
 }
+
+impl TablePlugin {
+    pub(in crate::runtime::monitor) fn enable_trap(&mut self) {
+        self.allow_trap = true;
+    }
+
+    /// Record the step raising `trap` as the last step of the trace, the step is traced before
+    /// the trap but it's not logged since the execution doesn't continue.
+    pub(in crate::runtime::monitor) fn terminate_with_trap(
+        &mut self,
+        trap: TrapCode,
+    ) -> anyhow::Result<()> {
+        if !self.allow_trap {
+            anyhow::bail!("The trap {:?} is raised out of the trap mode.", trap);
+        }
+        if self.phantom_helper.is_in_phantom_function() {
+            anyhow::bail!("The trap is raised in a phantom function which is not traced.");
+        }
+
+        match trap {
+            TrapCode::Unreachable => {
+                let (sp, allocated_memory_pages) =
+                    self.unresolved_unreachable.take().ok_or_else(|| {
+                        anyhow::anyhow!("The trap is not raised by an `unreachable`.")
+                    })?;
+                let (fid, iid) = self
+                    .next_position
+                    .ok_or_else(|| anyhow::anyhow!("The `unreachable` is not located."))?;
+                let last_jump_eid = *self
+                    .last_jump_eid
+                    .last()
+                    .ok_or_else(|| anyhow::anyhow!("The trap is raised out of any function."))?;
+
+                self.append_log(
+                    fid,
+                    iid,
+                    sp,
+                    allocated_memory_pages,
+                    last_jump_eid,
+                    StepInfo::Unreachable,
+                );
+            }
+            TrapCode::RequireFailed => {
+                let event = self
+                    .unresolved_host_call
+                    .take()
+                    .filter(|event| TrapCode::of_step(event) == Some(TrapCode::RequireFailed))
+                    .ok_or_else(|| anyhow::anyhow!("The trap is not raised by a `require`."))?;
+
+                self.append_log(
+                    event.fid,
+                    event.iid,
+                    event.sp,
+                    event.allocated_memory_pages,
+                    event.last_jump_eid,
+                    event.step_info,
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -6,6 +6,7 @@ use specs::etable::EventTableEntry;
 use specs::external_host_call_table::ExternalHostCallTable;
 use specs::jtable::FrameTable;
 use specs::step::StepInfo;
+use specs::trace::TraceError;
 use specs::TableBackend;
 use specs::TraceBackend;
 
@@ -41,18 +42,18 @@ impl Slices {
         }
     }
 
-    fn push(&mut self, slice: Slice) {
+    fn push(&mut self, slice: Slice) -> Result<(), TraceError> {
         let (etable, frame_table) = match &self.backend {
             TraceBackend::File {
                 event_table_writer,
                 frame_table_writer,
             } => {
                 let etable =
-                    TableBackend::Json(event_table_writer(self.etable.len(), &slice.etable));
+                    TableBackend::Json(event_table_writer(self.etable.len(), &slice.etable)?);
                 let frame_table = TableBackend::Json(frame_table_writer(
                     self.frame_table.len(),
                     &slice.frame_table,
-                ));
+                )?);
 
                 (etable, frame_table)
             }
@@ -68,6 +69,8 @@ impl Slices {
         self.frame_table.push(frame_table);
         self.external_host_call_table
             .push(slice.external_host_call_table);
+
+        Ok(())
     }
 }
 
@@ -106,6 +109,9 @@ pub(super) struct HostTransaction {
     started: BTreeMap<TransactionId, Checkpoint>,
     controller: Box<dyn FlushStrategy>,
     host_is_full: bool,
+    // the first error of writing a slice, the transaction is driven by the monitor hooks
+    // which cannot fail, so the error is reported once the transaction is finalized.
+    write_error: Option<TraceError>,
 
     pub(crate) slice_builder: SliceBuilder,
 }
@@ -126,6 +132,7 @@ impl HostTransaction {
             started: BTreeMap::new(),
             controller,
             host_is_full: false,
+            write_error: None,
         }
     }
 
@@ -177,7 +184,9 @@ impl HostTransaction {
             let committed_logs = logs.drain(0..rollback);
 
             let slice = self.slice_builder.build(committed_logs.collect());
-            self.slices.push(slice);
+            if let Err(err) = self.slices.push(slice) {
+                self.write_error.get_or_insert(err);
+            }
         }
 
         {
@@ -194,10 +203,13 @@ impl HostTransaction {
         }
     }
 
-    pub(super) fn finalized(mut self) -> Slices {
+    pub(super) fn finalized(mut self) -> Result<Slices, TraceError> {
         self.abort();
 
-        self.slices
+        match self.write_error {
+            Some(err) => Err(err),
+            None => Ok(self.slices),
+        }
     }
 }
 
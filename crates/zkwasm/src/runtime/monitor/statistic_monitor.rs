@@ -17,11 +17,13 @@ use wasmi::ValueType;
 use crate::runtime::host::host_env::HostEnv;
 
 use super::observer::Observer;
+use super::plugins::profiler::ProfilerPlugin;
 use super::plugins::statistic::StatisticPlugin;
 use super::WasmiMonitor;
 
 pub struct StatisticMonitor {
     statistic_plugin: StatisticPlugin,
+    profiler_plugin: Option<ProfilerPlugin>,
 }
 
 impl StatisticMonitor {
@@ -35,8 +37,26 @@ impl StatisticMonitor {
 
         Self {
             statistic_plugin: StatisticPlugin::new(phantom_regex, wasm_input, instruction_limit),
+            profiler_plugin: None,
         }
     }
+
+    /// Profile the call graph of the guest during the execution.
+    pub fn with_profiler(mut self) -> Self {
+        self.profiler_plugin = Some(ProfilerPlugin::new());
+        self
+    }
+
+    pub fn profiler(&self) -> Option<&ProfilerPlugin> {
+        self.profiler_plugin.as_ref()
+    }
+
+    fn is_in_phantom(&self) -> bool {
+        self.statistic_plugin
+            .expose_observer()
+            .borrow()
+            .is_in_phantom
+    }
 }
 
 impl Monitor for StatisticMonitor {
@@ -49,6 +69,10 @@ impl Monitor for StatisticMonitor {
         self.statistic_plugin
             .register_module(module, module_ref, entry)?;
 
+        if let Some(profiler_plugin) = self.profiler_plugin.as_mut() {
+            profiler_plugin.register_module(module, module_ref, entry)?;
+        }
+
         Ok(())
     }
 
@@ -67,6 +91,16 @@ impl Monitor for StatisticMonitor {
             function_context,
             instruction,
         );
+
+        if !self.is_in_phantom() {
+            if let Some(profiler_plugin) = self.profiler_plugin.as_mut() {
+                profiler_plugin.invoke_instruction_pre_hook(
+                    value_stack,
+                    function_context,
+                    instruction,
+                );
+            }
+        }
     }
 
     fn invoke_instruction_post_hook(
@@ -80,6 +114,8 @@ impl Monitor for StatisticMonitor {
         instruction: &Instruction,
         outcome: &InstructionOutcome,
     ) -> Result<(), Trap> {
+        let was_in_phantom = self.is_in_phantom();
+
         self.statistic_plugin.invoke_instruction_post_hook(
             fid,
             iid,
@@ -91,12 +127,35 @@ impl Monitor for StatisticMonitor {
             outcome,
         )?;
 
+        // The call of a phantom function and its return are kept, so that the profiler sees
+        // the phantom function as a leaf.
+        if !was_in_phantom || !self.is_in_phantom() {
+            if let Some(profiler_plugin) = self.profiler_plugin.as_mut() {
+                profiler_plugin.invoke_instruction_post_hook(
+                    fid,
+                    iid,
+                    sp,
+                    allocated_memory_pages,
+                    value_stack,
+                    function_context,
+                    instruction,
+                    outcome,
+                )?;
+            }
+        }
+
         Ok(())
     }
 
     fn invoke_call_host_post_hook(&mut self, return_value: Option<RuntimeValue>) {
         self.statistic_plugin
             .invoke_call_host_post_hook(return_value);
+
+        if !self.is_in_phantom() {
+            if let Some(profiler_plugin) = self.profiler_plugin.as_mut() {
+                profiler_plugin.invoke_call_host_post_hook(return_value);
+            }
+        }
     }
 }
 
@@ -2,6 +2,8 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use parity_wasm::elements::Module;
+use specs::trace::TraceError;
+use specs::trap::TrapCode;
 use specs::CompilationTable;
 use specs::Tables;
 use specs::TraceBackend;
@@ -62,7 +64,7 @@ impl TableMonitor {
         self.table_plugin.into_compilation_table()
     }
 
-    pub fn into_tables(self) -> Tables {
+    pub fn into_tables(self) -> Result<Tables, TraceError> {
         self.table_plugin.into_tables()
     }
 }
@@ -148,4 +150,12 @@ impl WasmiMonitor for TableMonitor {
     fn expose_observer(&self) -> Rc<RefCell<Observer>> {
         self.statistic_plugin.expose_observer()
     }
+
+    fn enable_trap(&mut self) {
+        self.table_plugin.enable_trap();
+    }
+
+    fn terminate_with_trap(&mut self, trap: TrapCode) -> anyhow::Result<()> {
+        self.table_plugin.terminate_with_trap(trap)
+    }
 }
@@ -7,6 +7,7 @@ use specs::imtable::InitMemoryTableEntry;
 use specs::mtable::AccessType;
 use specs::state::InitializationState;
 use specs::step::StepInfo;
+use specs::trap::TrapCode;
 
 use super::memory_event_of_step;
 
@@ -87,6 +88,11 @@ impl UpdateInitializationState for InitializationState<u32> {
                     }
                 }
             }
+
+            // The trap code is exposed following the public inputs and outputs.
+            if TrapCode::of_step(entry).is_some() {
+                host_public_inputs += 1;
+            }
         }
 
         let post_initialization_state = if let Some(next_entry) = next_event_entry {
@@ -104,6 +110,25 @@ impl UpdateInitializationState for InitializationState<u32> {
                 initial_memory_pages: next_entry.allocated_memory_pages,
                 maximal_memory_pages: configure_table.maximal_memory_pages,
             }
+        } else if let Some(trap) = execution_table.entries().last().and_then(TrapCode::of_step) {
+            let last_entry = execution_table.entries().last().unwrap();
+
+            // The trap doesn't leave the frame, the state follows the trap step.
+            InitializationState {
+                eid: last_entry.eid + 1,
+                fid: last_entry.fid,
+                iid: last_entry.iid + 1,
+                frame_id: last_entry.last_jump_eid,
+                // The condition of the failed require is popped.
+                sp: last_entry.sp + (trap == TrapCode::RequireFailed) as u32,
+
+                host_public_inputs,
+                context_in_index,
+                context_out_index,
+
+                initial_memory_pages: last_entry.allocated_memory_pages,
+                maximal_memory_pages: configure_table.maximal_memory_pages,
+            }
         } else {
             let last_entry = execution_table.entries().last().unwrap();
 
@@ -1,10 +1,13 @@
 use anyhow::Result;
 use specs::host_function::HostPlugin;
+use specs::trap::TrapCode;
 use wasmi::monitor::Monitor;
 use wasmi::ImportResolver;
 use wasmi::ModuleInstance;
 use wasmi::RuntimeValue;
+use wasmi::Trap;
 
+use crate::error::ExecutionError;
 use crate::foreign::context::ContextOutput;
 
 use super::host::host_env::ExecEnv;
@@ -13,8 +16,60 @@ use super::monitor::WasmiMonitor;
 use super::CompiledImage;
 use super::ExecutionResult;
 
+/// Surface the `ExecutionError` raised by a host function instead of the trap wrapping it.
+fn into_execution_error(err: impl Into<wasmi::Error>) -> anyhow::Error {
+    let err = err.into();
+    let execution_error = err
+        .as_host_error()
+        .and_then(|err| err.downcast_ref::<ExecutionError>())
+        .cloned();
+
+    match execution_error {
+        Some(execution_error) => execution_error.into(),
+        None => err.into(),
+    }
+}
+
+/// The trap which can be proved, other traps fail the execution.
+fn trap_code(err: &wasmi::Error) -> Option<TrapCode> {
+    if let wasmi::Error::Trap(Trap::Code(wasmi::TrapCode::Unreachable)) = err {
+        return Some(TrapCode::Unreachable);
+    }
+
+    match err
+        .as_host_error()
+        .and_then(|err| err.downcast_ref::<ExecutionError>())
+    {
+        Some(ExecutionError::RequireFailed(_)) => Some(TrapCode::RequireFailed),
+        _ => None,
+    }
+}
+
+/// Terminate the trace with the trap raising `err` if `allow_trap` and the trap can be proved.
+fn terminate_with_trap(
+    err: wasmi::Error,
+    monitor: &mut dyn WasmiMonitor,
+    allow_trap: bool,
+) -> Result<TrapCode> {
+    match trap_code(&err) {
+        Some(trap) if allow_trap => {
+            monitor.terminate_with_trap(trap)?;
+
+            Ok(trap)
+        }
+        _ => Err(into_execution_error(err)),
+    }
+}
+
 pub trait Execution<R> {
-    fn run(self, monitor: &mut dyn WasmiMonitor, externals: HostEnv) -> Result<ExecutionResult<R>>;
+    /// `allow_trap`: terminate the trace with the trap instead of failing if the execution
+    /// traps by `unreachable` or a failed `require`.
+    fn run(
+        self,
+        monitor: &mut dyn WasmiMonitor,
+        externals: HostEnv,
+        allow_trap: bool,
+    ) -> Result<ExecutionResult<R>>;
 }
 
 impl Execution<RuntimeValue> for CompiledImage<wasmi::NotStartedModuleRef<'_>> {
@@ -22,34 +77,58 @@ impl Execution<RuntimeValue> for CompiledImage<wasmi::NotStartedModuleRef<'_>> {
         self,
         monitor: &mut dyn WasmiMonitor,
         externals: HostEnv,
+        allow_trap: bool,
     ) -> Result<ExecutionResult<RuntimeValue>> {
         let mut exec_env = ExecEnv {
             host_env: externals,
             observer: monitor.expose_observer(),
         };
-        let instance = self
-            .instance
-            .run_start_tracer(&mut exec_env, monitor)
-            .unwrap();
 
-        let result = instance.invoke_export_trace(&self.entry, &[], &mut exec_env, monitor)?;
+        if allow_trap {
+            monitor.enable_trap();
+        }
+
+        let (result, trap) = match self.instance.run_start_tracer(&mut exec_env, monitor) {
+            Ok(instance) => {
+                match instance.invoke_export_trace(&self.entry, &[], &mut exec_env, monitor) {
+                    Ok(result) => (result, None),
+                    Err(err) => (
+                        None,
+                        Some(terminate_with_trap(err.into(), monitor, allow_trap)?),
+                    ),
+                }
+            }
+            Err(err) => (
+                None,
+                Some(terminate_with_trap(err.into(), monitor, allow_trap)?),
+            ),
+        };
 
         let host_statics = exec_env
             .host_env
             .external_env
             .get_statics(exec_env.host_env.k);
-        let public_inputs_and_outputs = exec_env
+        let mut public_inputs_and_outputs = exec_env
             .host_env
             .internal_env
             .get_context_of_plugin(HostPlugin::HostInput)
             .borrow()
             .expose_public_inputs_and_outputs();
+        if let Some(trap) = trap {
+            public_inputs_and_outputs.push(trap.into());
+        }
         let outputs = exec_env
             .host_env
             .internal_env
             .get_context_of_plugin(HostPlugin::HostInput)
             .borrow()
             .expose_outputs();
+        let output_channels = exec_env
+            .host_env
+            .internal_env
+            .get_context_of_plugin(HostPlugin::HostInput)
+            .borrow()
+            .expose_output_channels();
         let context_outputs = ContextOutput(
             exec_env
                 .host_env
@@ -66,6 +145,8 @@ impl Execution<RuntimeValue> for CompiledImage<wasmi::NotStartedModuleRef<'_>> {
             public_inputs_and_outputs,
             outputs,
             context_outputs,
+            output_channels,
+            trap,
         })
     }
 }
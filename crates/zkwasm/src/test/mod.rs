@@ -1,23 +1,116 @@
 use crate::loader::slice::Slices;
+use crate::loader::Module;
 use crate::loader::ZkWasmLoader;
 use crate::runtime::host::default_env::DefaultHostEnvBuilder;
 use crate::runtime::host::default_env::ExecutionArg;
+use crate::runtime::host::host_env::HostEnv;
 use crate::runtime::host::HostEnvBuilder;
 use crate::runtime::monitor::table_monitor::TableMonitor;
+use crate::runtime::monitor::WasmiMonitor;
+use crate::runtime::ExecutionResult;
 
 use anyhow::Result;
 use halo2_proofs::pairing::bn256::Fr;
 use specs::TraceBackend;
 use wabt::wat2wasm_with_features;
 use wabt::Features;
+use wasmi::RuntimeValue;
 
 mod test_wasm_instructions;
 
 mod spec;
+mod test_debugger;
+mod test_diagnostic;
+mod test_estimate;
+mod test_execution_error;
+mod test_instruction_statistic;
+mod test_output_channel;
+mod test_profiler;
 mod test_rlp;
 #[cfg(feature = "continuation")]
 mod test_rlp_slice;
+mod test_precheck;
 mod test_start;
+mod test_trap;
+
+/// An execution argument without any input, tests could override the fields with
+/// `ExecutionArg { public_inputs, ..empty_arg() }`.
+pub(crate) fn empty_arg() -> ExecutionArg {
+    ExecutionArg {
+        public_inputs: vec![],
+        private_inputs: vec![].into(),
+        context_inputs: vec![],
+        indexed_witness: Default::default(),
+        tree_db: None,
+        output_channels: vec![],
+    }
+}
+
+/// Run the function with `arg`, the monitor is created by `create_monitor` from the env. Returns
+/// the module, the monitor and the result of the execution.
+pub(crate) fn run_with_monitor<M: WasmiMonitor>(
+    k: u32,
+    wasm: &[u8],
+    function_name: &str,
+    arg: ExecutionArg,
+    allow_trap: bool,
+    create_monitor: impl FnOnce(&DefaultHostEnvBuilder, &HostEnv) -> M,
+) -> Result<(Module, M, ExecutionResult<RuntimeValue>)> {
+    let module = ZkWasmLoader::parse_module_with_entry(wasm, k, function_name)?;
+    let env_builder = DefaultHostEnvBuilder::new(k);
+
+    let env = env_builder.create_env(arg);
+    let mut monitor = create_monitor(&env_builder, &env);
+    let mut loader = ZkWasmLoader::new(k, env)?;
+    loader.set_entry(function_name.to_string());
+    loader.set_allow_trap(allow_trap);
+
+    let runner = loader.compile(&module, &mut monitor)?;
+    let result = loader.run(runner, &mut monitor)?;
+
+    Ok((module, monitor, result))
+}
+
+/// Run the function with `arg` and generate trace in memory, see `run_with_monitor`.
+pub(crate) fn run_with_env(
+    k: u32,
+    wasm: &[u8],
+    function_name: &str,
+    arg: ExecutionArg,
+    allow_trap: bool,
+) -> Result<(Module, TableMonitor, ExecutionResult<RuntimeValue>)> {
+    run_with_monitor(
+        k,
+        wasm,
+        function_name,
+        arg,
+        allow_trap,
+        |env_builder, env| {
+            TableMonitor::new(
+                k,
+                env_builder.create_flush_strategy(),
+                &[],
+                TraceBackend::Memory,
+                env,
+            )
+        },
+    )
+}
+
+/// Run the function with `arg` and generate trace in memory, returns the result of the execution
+/// with the slices of the trace to be tested by the mock prover.
+pub(crate) fn run_to_slices(
+    k: u32,
+    wasm: &[u8],
+    function_name: &str,
+    arg: ExecutionArg,
+    allow_trap: bool,
+) -> Result<(ExecutionResult<RuntimeValue>, Slices<Fr>)> {
+    let (_, monitor, result) = run_with_env(k, wasm, function_name, arg, allow_trap)?;
+    let slices = Slices::new(k, monitor.into_tables()?, None)?;
+
+    Ok((result, slices))
+}
 
 /// Run the function and generate trace, then test circuit with mock prover.
 pub fn test_circuit_with_env(
@@ -27,32 +120,20 @@ pub fn test_circuit_with_env(
     public_inputs: Vec<u64>,
     private_inputs: Vec<u64>,
 ) -> Result<()> {
-    let module = ZkWasmLoader::parse_module(&wasm)?;
-    let env_builder = DefaultHostEnvBuilder::new(k);
-
-    let env = env_builder.create_env(ExecutionArg {
-        public_inputs,
-        private_inputs,
-        context_inputs: vec![],
-        indexed_witness: Default::default(),
-        tree_db: None,
-    });
-    let mut monitor = TableMonitor::new(
+    let (execution_result, slices) = run_to_slices(
         k,
-        env_builder.create_flush_strategy(),
-        &vec![],
-        TraceBackend::Memory,
-        &env,
-    );
-    let mut loader = ZkWasmLoader::new(k, env)?;
-    loader.set_entry(function_name);
-
-    let runner = loader.compile(&module, &mut monitor)?;
-
-    let execution_result = loader.run(runner, &mut monitor)?;
+        &wasm,
+        &function_name,
+        ExecutionArg {
+            public_inputs,
+            private_inputs: private_inputs.into(),
+            ..empty_arg()
+        },
+        false,
+    )?;
     let instances: Vec<Fr> = execution_result.public_inputs_and_outputs();
 
-    Slices::new(k, monitor.into_tables(), None)?.mock_test_all(instances)?;
+    slices.mock_test_all(instances)?;
 
     Ok(())
 }
@@ -0,0 +1,81 @@
+mod tests {
+    use crate::circuits::config::MIN_K;
+    use crate::debugger::function_names;
+    use crate::debugger::Breakpoint;
+    use crate::debugger::Debugger;
+    use crate::test::empty_arg;
+    use crate::test::run_with_env;
+
+    use wabt::Wat2Wasm;
+
+    fn debugger() -> Debugger {
+        let textual_repr = r#"
+        (module
+            (memory $0 1)
+
+            (func $add (param i32 i32) (result i32)
+                (local.get 0)
+                (local.get 1)
+                (i32.add)
+            )
+
+            (func $test (export "test")
+                (i32.const 8)
+                (i32.const 1)
+                (i32.const 2)
+                (call $add)
+                (i32.store)
+            )
+        )
+        "#;
+
+        let wasm = Wat2Wasm::new()
+            .write_debug_names(true)
+            .convert(textual_repr)
+            .expect("failed to parse wat")
+            .as_ref()
+            .to_vec();
+        let (module, monitor, _) = run_with_env(MIN_K, &wasm, "test", empty_arg(), false).unwrap();
+
+        Debugger::new(&monitor.into_tables().unwrap(), function_names(&module)).unwrap()
+    }
+
+    #[test]
+    fn test_debugger_break_on_function() {
+        let mut debugger = debugger();
+
+        debugger.add_breakpoint(Breakpoint::Function("add".to_string()));
+        assert_eq!(
+            debugger.continue_forward(),
+            Some(&Breakpoint::Function("add".to_string()))
+        );
+
+        let call_stack = debugger.call_stack();
+        assert_eq!(call_stack.len(), 2);
+        assert_eq!(debugger.function_name(call_stack[0].fid), Some("add"));
+        assert_eq!(debugger.function_name(call_stack[1].fid), Some("test"));
+
+        // The arguments of add are on the top of the stack.
+        assert!(debugger.value_stack().starts_with(&[2, 1, 8]));
+    }
+
+    #[test]
+    fn test_debugger_break_on_memory() {
+        let mut debugger = debugger();
+
+        debugger.add_breakpoint(Breakpoint::Memory(8));
+        assert_eq!(debugger.continue_forward(), Some(&Breakpoint::Memory(8)));
+        assert_eq!(debugger.memory(8), None);
+
+        assert!(debugger.step_forward());
+        assert_eq!(debugger.memory(8), Some(3));
+
+        assert!(debugger.step_backward());
+        assert_eq!(debugger.memory(8), None);
+
+        let first_eid = debugger.current().unwrap().eid - debugger.position() as u32;
+        assert!(debugger.seek(first_eid));
+        assert_eq!(debugger.position(), 0);
+        assert!(debugger.value_stack().is_empty());
+    }
+}
@@ -0,0 +1,64 @@
+mod tests {
+    use std::sync::Arc;
+
+    use crate::circuits::config::MIN_K;
+    use crate::circuits::diagnostic::FailureLocation;
+    use crate::circuits::ZkWasmCircuit;
+    use crate::error::MockTestError;
+    use crate::test::empty_arg;
+    use crate::test::run_to_slices;
+
+    use halo2_proofs::pairing::bn256::Fr;
+    use specs::etable::EventTable;
+
+    #[test]
+    fn test_mock_test_locates_tampered_step() {
+        let textual_repr = r#"
+        (module
+            (func (export "test")
+                (i32.const 1)
+                (i32.const 2)
+                (i32.add)
+                (drop)
+            )
+        )
+        "#;
+
+        let wasm = wabt::wat2wasm(textual_repr).expect("failed to parse wat");
+        let (result, mut slices) = run_to_slices(MIN_K, &wasm, "test", empty_arg(), false).unwrap();
+        let instances: Vec<Fr> = result.public_inputs_and_outputs();
+
+        let mut circuit = slices.next().unwrap().unwrap();
+
+        // Grow the memory pages without `memory.grow`.
+        let slice = match &mut circuit {
+            ZkWasmCircuit::Ongoing(circuit) => &mut circuit.slice,
+            ZkWasmCircuit::LastSliceCircuit(circuit) => &mut circuit.slice,
+            ZkWasmCircuit::TrapSliceCircuit(circuit) => &mut circuit.slice,
+        };
+        let mut entries = slice.etable.entries().clone();
+        entries[2].allocated_memory_pages += 1;
+        let expected = vec![(1, entries[1].eid), (2, entries[2].eid)];
+        slice.etable = Arc::new(EventTable::new(entries));
+
+        let failures = match circuit.mock_test(instances) {
+            Err(MockTestError::Unsatisfied(failures)) => failures,
+            _ => panic!("the tampered step should be rejected"),
+        };
+
+        // The pages change from the step before the tampered one and to the step after it.
+        let mut located = failures
+            .iter()
+            .map(|failure| match &failure.location {
+                FailureLocation::EventTable {
+                    index,
+                    step: Some(step),
+                } => (*index, step.eid),
+                _ => panic!("{} is not located in the event table", failure),
+            })
+            .collect::<Vec<_>>();
+        located.sort();
+        located.dedup();
+        assert_eq!(located, expected);
+    }
+}
@@ -0,0 +1,78 @@
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::circuits::config::MIN_K;
+    use crate::circuits::estimate::RowEstimate;
+    use crate::circuits::ZkWasmCircuit;
+    use crate::loader::slice::Slices;
+    use crate::runtime::memory_event_of_step;
+    use crate::test::empty_arg;
+    use crate::test::run_with_env;
+
+    use halo2_proofs::pairing::bn256::Fr;
+    use specs::mtable::AccessType;
+
+    #[test]
+    fn test_estimate_matches_slice() {
+        let textual_repr = r#"
+        (module
+            (memory $0 1)
+            (global $g (mut i32) (i32.const 0))
+
+            (func $inc (param i32) (result i32)
+                (local.get 0)
+                (i32.const 1)
+                (i32.add)
+            )
+
+            (func (export "test")
+                (i32.const 0)
+                (i32.const 1)
+                (i32.store)
+                (i32.const 8)
+                (i32.const 0)
+                (i32.load)
+                (call $inc)
+                (i64.extend_i32_u)
+                (i64.store)
+                (global.set $g (i32.const 2))
+            )
+        )
+        "#;
+
+        let wasm = wabt::wat2wasm(textual_repr).expect("failed to parse wat");
+        let (_, monitor, _) = run_with_env(MIN_K, &wasm, "test", empty_arg(), false).unwrap();
+        let tables = monitor.into_tables().unwrap();
+
+        let estimate = RowEstimate::from_tables(&tables).unwrap();
+        let candidate = estimate.candidate(MIN_K, &HashMap::default());
+        assert!(candidate.bottlenecks.is_empty());
+        assert_eq!(candidate.slices, Some(1));
+        assert_eq!(
+            estimate.minimal_k(MIN_K..MIN_K + 1, |_| HashMap::default()),
+            Some(MIN_K)
+        );
+
+        let circuit = Slices::<Fr>::new(MIN_K, tables, None)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        let slice = match &circuit {
+            ZkWasmCircuit::Ongoing(circuit) => &circuit.slice,
+            ZkWasmCircuit::LastSliceCircuit(circuit) => &circuit.slice,
+            ZkWasmCircuit::TrapSliceCircuit(circuit) => &circuit.slice,
+        };
+
+        assert_eq!(estimate.etable_entries, slice.etable.entries().len());
+        assert_eq!(
+            estimate.mtable_entries,
+            slice
+                .create_memory_table(memory_event_of_step)
+                .entries()
+                .iter()
+                .filter(|entry| entry.atype != AccessType::Read)
+                .count()
+        );
+    }
+}
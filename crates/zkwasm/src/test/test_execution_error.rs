@@ -0,0 +1,189 @@
+mod tests {
+    use std::io::Cursor;
+
+    use crate::circuits::config::MIN_K;
+    use crate::error::ExecutionError;
+    use crate::foreign::wasm_input_helper::source::Endianness;
+    use crate::foreign::wasm_input_helper::source::PrivateInputSource;
+    use crate::foreign::wasm_input_helper::source::ReaderSource;
+    use crate::runtime::host::default_env::ExecutionArg;
+    use crate::runtime::monitor::table_monitor::TableMonitor;
+    use crate::test::empty_arg;
+    use crate::test::run_with_env;
+    use crate::test::run_with_monitor;
+
+    use specs::etable::EventTable;
+    use specs::jtable::FrameTable;
+    use specs::trace::TraceError;
+    use specs::TraceBackend;
+
+    fn run(
+        textual_repr: &str,
+        private_inputs: Box<dyn PrivateInputSource>,
+        context_inputs: Vec<u64>,
+    ) -> ExecutionError {
+        let wasm = wabt::wat2wasm(textual_repr).expect("failed to parse wat");
+        let err = run_with_env(
+            MIN_K,
+            &wasm,
+            "test",
+            ExecutionArg {
+                private_inputs,
+                context_inputs,
+                ..empty_arg()
+            },
+            false,
+        )
+        .err()
+        .expect("the execution should be trapped");
+
+        err.downcast::<ExecutionError>()
+            .expect("the trap should be an execution error")
+    }
+
+    #[test]
+    fn test_public_input_exhausted() {
+        let textual_repr = r#"
+        (module
+            (import "env" "wasm_input" (func $wasm_input (param i32) (result i64)))
+            (func (export "test")
+                (call $wasm_input (i32.const 1))
+                (drop)
+            )
+        )
+        "#;
+
+        assert!(matches!(
+            run(textual_repr, vec![].into(), vec![]),
+            // The call to wasm_input follows i32.const.
+            ExecutionError::PublicInputExhausted(2)
+        ));
+    }
+
+    #[test]
+    fn test_invalid_input_kind() {
+        let textual_repr = r#"
+        (module
+            (import "env" "wasm_input" (func $wasm_input (param i32) (result i64)))
+            (func (export "test")
+                (call $wasm_input (i32.const 2))
+                (drop)
+            )
+        )
+        "#;
+
+        assert!(matches!(
+            run(textual_repr, vec![].into(), vec![]),
+            ExecutionError::InvalidInputKind(2, 2)
+        ));
+    }
+
+    const READ_TWO_PRIVATE_INPUTS: &str = r#"
+        (module
+            (import "env" "wasm_input" (func $wasm_input (param i32) (result i64)))
+            (func (export "test")
+                (call $wasm_input (i32.const 0))
+                (drop)
+                (call $wasm_input (i32.const 0))
+                (drop)
+            )
+        )
+        "#;
+
+    #[test]
+    fn test_private_input_exhausted() {
+        assert!(matches!(
+            run(READ_TWO_PRIVATE_INPUTS, vec![1].into(), vec![]),
+            ExecutionError::PrivateInputExhausted(5, 8)
+        ));
+    }
+
+    #[test]
+    fn test_private_input_truncated() {
+        let source = ReaderSource::new(Cursor::new(vec![1u8; 12]), Endianness::Little);
+
+        assert!(matches!(
+            run(READ_TWO_PRIVATE_INPUTS, Box::new(source), vec![]),
+            ExecutionError::PrivateInputTruncated(5, 8)
+        ));
+    }
+
+    #[test]
+    fn test_require_failed() {
+        let textual_repr = r#"
+        (module
+            (import "env" "require" (func $require (param i32)))
+            (func (export "test")
+                (call $require (i32.const 1))
+                (call $require (i32.const 0))
+            )
+        )
+        "#;
+
+        assert!(matches!(
+            run(textual_repr, vec![].into(), vec![]),
+            ExecutionError::RequireFailed(_)
+        ));
+    }
+
+    #[test]
+    fn test_context_underflow() {
+        let textual_repr = r#"
+        (module
+            (import "env" "wasm_read_context" (func $wasm_read_context (result i64)))
+            (func (export "test")
+                (call $wasm_read_context)
+                (drop)
+                (call $wasm_read_context)
+                (drop)
+            )
+        )
+        "#;
+
+        assert!(matches!(
+            run(textual_repr, vec![].into(), vec![1]),
+            ExecutionError::ContextUnderflow(_)
+        ));
+    }
+
+    #[test]
+    fn test_trace_write_error() {
+        let textual_repr = r#"
+        (module
+            (func (export "test")
+                (drop (i32.const 0))
+            )
+        )
+        "#;
+
+        let wasm = wabt::wat2wasm(textual_repr).expect("failed to parse wat");
+        let (_, monitor, _) = run_with_monitor(
+            MIN_K,
+            &wasm,
+            "test",
+            empty_arg(),
+            false,
+            |env_builder, env| {
+                let backend = TraceBackend::File {
+                    event_table_writer: Box::new(|_, _: &EventTable| {
+                        Err(TraceError::Io(std::io::ErrorKind::PermissionDenied.into()))
+                    }),
+                    frame_table_writer: Box::new(|_, _: &FrameTable| {
+                        Err(TraceError::Io(std::io::ErrorKind::PermissionDenied.into()))
+                    }),
+                };
+
+                TableMonitor::new(
+                    MIN_K,
+                    env_builder.create_flush_strategy(),
+                    &[],
+                    backend,
+                    env,
+                )
+            },
+        )
+        .unwrap();
+
+        assert!(matches!(monitor.into_tables(), Err(TraceError::Io(_))));
+    }
+}
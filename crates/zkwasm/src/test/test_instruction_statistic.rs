@@ -0,0 +1,72 @@
+mod tests {
+    use crate::circuits::config::MIN_K;
+    use crate::profile::instruction_statistic::InstructionStatistics;
+    use crate::test::empty_arg;
+    use crate::test::run_with_env;
+
+    use specs::itable::OpcodeClass;
+    use specs::trace::TraceHeader;
+    use specs::Tables;
+
+    #[test]
+    fn test_instruction_statistic_of_both_backends() {
+        let textual_repr = r#"
+        (module
+            (memory $0 1)
+
+            (func (export "test")
+                (i32.const 0)
+                (i32.const 1)
+                (i32.store)
+                (i32.const 0)
+                (i32.load)
+                (drop)
+            )
+        )
+        "#;
+
+        let wasm = wabt::wat2wasm(textual_repr).expect("failed to parse wat");
+        let (_, monitor, result) = run_with_env(MIN_K, &wasm, "test", empty_arg(), false).unwrap();
+        let tables = monitor.into_tables().unwrap();
+
+        let statistic = tables.instruction_statistic().unwrap();
+        let total = statistic.total();
+        assert_eq!(total.count, result.guest_statics);
+        assert_eq!(statistic.opcodes[&OpcodeClass::Store].count, 1);
+        assert_eq!(statistic.opcodes[&OpcodeClass::Load].count, 1);
+        assert_eq!(statistic.opcodes[&OpcodeClass::Return].jops, 1);
+
+        let dir = std::env::temp_dir().join("zkwasm-test-instruction-statistic");
+        std::fs::create_dir_all(&dir).unwrap();
+        tables
+            .write(
+                &dir,
+                |slice| TraceHeader {
+                    k: MIN_K,
+                    image_checksum: String::new(),
+                    slice,
+                },
+                |slice| format!("etable.{}.trace", slice),
+                |slice| format!("frame_table.{}.trace", slice),
+                |slice| format!("external_host_table.{}.json", slice),
+            )
+            .unwrap();
+        let loaded = Tables::read(
+            &dir,
+            |slice| format!("etable.{}.trace", slice),
+            |slice| format!("frame_table.{}.trace", slice),
+            |slice| format!("external_host_table.{}.json", slice),
+        )
+        .unwrap();
+
+        let streamed = loaded.instruction_statistic().unwrap();
+        for (opcode_class, opcode) in statistic.opcodes.iter() {
+            let streamed = &streamed.opcodes[opcode_class];
+            assert_eq!(opcode.count, streamed.count);
+            assert_eq!(opcode.memory_entries, streamed.memory_entries);
+            assert_eq!(opcode.reads, streamed.reads);
+            assert_eq!(opcode.writes, streamed.writes);
+        }
+        assert_eq!(streamed.total().estimated_rows, total.estimated_rows);
+    }
+}
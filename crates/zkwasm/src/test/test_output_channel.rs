@@ -0,0 +1,138 @@
+mod tests {
+    use crate::circuits::config::MIN_K;
+    use crate::error::ExecutionError;
+    use crate::foreign::output_helper::OutputChannelConfig;
+    use crate::foreign::output_helper::OutputFormat;
+    use crate::runtime::host::default_env::ExecutionArg;
+    use crate::test::empty_arg;
+    use crate::test::run_to_slices;
+
+    use halo2_proofs::pairing::bn256::Fr;
+
+    const OUTPUTS: &str = r#"
+        (module
+            (import "env" "wasm_output" (func $wasm_output (param i64)))
+            (import "env" "wasm_output_channel" (func $wasm_output_channel (param i64)))
+            (import "env" "wasm_output_channel_push" (func $wasm_output_channel_push (param i64)))
+            (func (export "test")
+                (call $wasm_output (i64.const 1))
+                (call $wasm_output_channel (i64.const 1))
+                (call $wasm_output_channel_push (i64.const 2))
+                (call $wasm_output_channel_push (i64.const 3))
+                (call $wasm_output_channel (i64.const 2))
+                (call $wasm_output (i64.const 4))
+            )
+        )
+        "#;
+
+    /// The channel `events` with id 1 and the committed channel `results` with id 2.
+    fn channels_arg() -> ExecutionArg {
+        ExecutionArg {
+            output_channels: vec![
+                OutputChannelConfig {
+                    id: 1,
+                    name: "events".to_string(),
+                    committed: false,
+                    format: OutputFormat::Json,
+                },
+                OutputChannelConfig {
+                    id: 2,
+                    name: "results".to_string(),
+                    committed: true,
+                    format: OutputFormat::Binary,
+                },
+            ],
+            ..empty_arg()
+        }
+    }
+
+    fn execution_error(textual_repr: &str) -> ExecutionError {
+        let wasm = wabt::wat2wasm(textual_repr).expect("failed to parse wat");
+
+        run_to_slices(MIN_K, &wasm, "test", channels_arg(), false)
+            .err()
+            .expect("the execution should be trapped")
+            .downcast::<ExecutionError>()
+            .expect("the trap should be an execution error")
+    }
+
+    #[test]
+    fn test_output_channels() {
+        let wasm = wabt::wat2wasm(OUTPUTS).expect("failed to parse wat");
+        let (result, slices) = run_to_slices(MIN_K, &wasm, "test", channels_arg(), false).unwrap();
+        let instances: Vec<Fr> = result.public_inputs_and_outputs();
+
+        assert_eq!(result.output_channels[0].values, vec![2, 3]);
+        assert_eq!(result.output_channels[1].values, vec![4]);
+        // Only the values of `wasm_output` are committed.
+        assert_eq!(instances, vec![Fr::from(1), Fr::from(4)]);
+
+        slices.mock_test_all(instances).unwrap();
+    }
+
+    #[test]
+    fn test_committed_output_is_not_droppable() {
+        let wasm = wabt::wat2wasm(OUTPUTS).expect("failed to parse wat");
+        let (result, slices) = run_to_slices(MIN_K, &wasm, "test", channels_arg(), false).unwrap();
+        let mut instances: Vec<Fr> = result.public_inputs_and_outputs();
+
+        // Drop the value written to the committed channel `results`.
+        assert_eq!(instances.pop(), Some(Fr::from(4)));
+        assert!(slices.mock_test_all(instances).is_err());
+    }
+
+    #[test]
+    fn test_output_to_uncommitted_channel() {
+        let textual_repr = r#"
+        (module
+            (import "env" "wasm_output" (func $wasm_output (param i64)))
+            (import "env" "wasm_output_channel" (func $wasm_output_channel (param i64)))
+            (func (export "test")
+                (call $wasm_output_channel (i64.const 1))
+                (call $wasm_output (i64.const 1))
+            )
+        )
+        "#;
+
+        assert!(matches!(
+            execution_error(textual_repr),
+            ExecutionError::OutputChannelNotCommitted(name, _) if name == "events"
+        ));
+    }
+
+    #[test]
+    fn test_push_to_committed_channel() {
+        let textual_repr = r#"
+        (module
+            (import "env" "wasm_output_channel" (func $wasm_output_channel (param i64)))
+            (import "env" "wasm_output_channel_push" (func $wasm_output_channel_push (param i64)))
+            (func (export "test")
+                (call $wasm_output_channel (i64.const 2))
+                (call $wasm_output_channel_push (i64.const 1))
+            )
+        )
+        "#;
+
+        assert!(matches!(
+            execution_error(textual_repr),
+            ExecutionError::OutputChannelCommitted(name, _) if name == "results"
+        ));
+    }
+
+    #[test]
+    fn test_unknown_output_channel() {
+        let textual_repr = r#"
+        (module
+            (import "env" "wasm_output_channel" (func $wasm_output_channel (param i64)))
+            (func (export "test")
+                (call $wasm_output_channel (i64.const 3))
+            )
+        )
+        "#;
+
+        assert!(matches!(
+            execution_error(textual_repr),
+            ExecutionError::UnknownOutputChannel(3, _)
+        ));
+    }
+}
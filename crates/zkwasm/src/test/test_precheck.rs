@@ -0,0 +1,180 @@
+mod tests {
+    use crate::circuits::config::MIN_K;
+    use crate::circuits::image_table::compute_maximal_pages;
+    use crate::loader::err::PreCheckErr;
+    use crate::loader::ZkWasmLoader;
+
+    use parity_wasm::elements::ValueType;
+
+    #[test]
+    fn test_precheck_initial_pages() {
+        let maximal_pages = compute_maximal_pages(MIN_K);
+
+        let textual_repr = format!(
+            r#"
+            (module
+                (memory $0 {})
+                (func (export "zkmain"))
+            )
+            "#,
+            maximal_pages + 1
+        );
+
+        let wasm = wabt::wat2wasm(textual_repr).expect("failed to parse wat");
+        let module = wasmi::Module::from_buffer(&wasm).unwrap();
+
+        assert_eq!(
+            ZkWasmLoader::precheck(&module, MIN_K),
+            vec![PreCheckErr::InitialPagesExceedLimit {
+                initial_pages: maximal_pages + 1,
+                maximal_pages,
+                k: MIN_K,
+            }]
+        );
+        assert!(ZkWasmLoader::parse_module(&wasm, MIN_K).is_err());
+    }
+
+    #[test]
+    fn test_precheck_pass() {
+        let textual_repr = r#"
+            (module
+                (memory $0 1)
+                (func (export "zkmain")
+                    (i32.const 0)
+                    (f32.const 1.5)
+                    (f32.store offset=0)
+                )
+            )
+            "#;
+
+        let wasm = wabt::wat2wasm(textual_repr).expect("failed to parse wat");
+
+        assert!(ZkWasmLoader::parse_module(&wasm, MIN_K).is_ok());
+    }
+
+    #[test]
+    fn test_precheck_float_circuits() {
+        let textual_repr = r#"
+            (module
+                (func (export "zkmain")
+                    (f32.const 1.5)
+                    (f32.neg)
+                    (drop)
+                )
+            )
+            "#;
+
+        let wasm = wabt::wat2wasm(textual_repr).expect("failed to parse wat");
+
+        assert_eq!(
+            ZkWasmLoader::parse_module(&wasm, MIN_K).is_ok(),
+            cfg!(feature = "float")
+        );
+    }
+
+    #[test]
+    fn test_precheck_unconstrained_float() {
+        let textual_repr = r#"
+            (module
+                (func (export "zkmain")
+                    (f32.const 1.5)
+                    (f32.const 2.25)
+                    (f32.add)
+                    (i32.trunc_f32_s)
+                    (drop)
+                )
+            )
+            "#;
+
+        let wasm = wabt::wat2wasm(textual_repr).expect("failed to parse wat");
+        let module = wasmi::Module::from_buffer(&wasm).unwrap();
+
+        let errs = ZkWasmLoader::precheck(&module, MIN_K);
+        assert_eq!(errs.len(), 2);
+        assert!(matches!(
+            &errs[0],
+            PreCheckErr::UnsupportedInstruction {
+                function_index: 0,
+                offset: 2,
+                ..
+            }
+        ));
+        assert!(matches!(
+            &errs[1],
+            PreCheckErr::UnsupportedInstruction {
+                function_index: 0,
+                offset: 3,
+                ..
+            }
+        ));
+        assert!(ZkWasmLoader::parse_module(&wasm, MIN_K).is_err());
+    }
+
+    fn check_zkmain_of(textual_repr: &str) -> Vec<PreCheckErr> {
+        let wasm = wabt::wat2wasm(textual_repr).expect("failed to parse wat");
+        let module = wasmi::Module::from_buffer(&wasm).unwrap();
+
+        ZkWasmLoader::precheck(&module, MIN_K)
+    }
+
+    #[test]
+    fn test_precheck_zkmain() {
+        assert_eq!(
+            check_zkmain_of(r#"(module (func (export "zkmain")))"#),
+            vec![]
+        );
+        assert_eq!(
+            check_zkmain_of(r#"(module (func (export "test")))"#),
+            vec![PreCheckErr::ZkmainNotExists]
+        );
+        assert_eq!(
+            check_zkmain_of(r#"(module (memory (export "zkmain") 1))"#),
+            vec![PreCheckErr::ZkmainIsNotFunction]
+        );
+
+        // The configured entry is checked instead of `zkmain`.
+        let wasm = wabt::wat2wasm(r#"(module (func (export "test")))"#).unwrap();
+        let module = wasmi::Module::from_buffer(&wasm).unwrap();
+        assert_eq!(
+            ZkWasmLoader::precheck_with_entry(&module, MIN_K, "test"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_precheck_zkmain_type_not_match() {
+        let textual_repr = r#"
+            (module
+                (func (export "zkmain") (param i32) (result i64)
+                    (i64.const 0)
+                )
+            )
+            "#;
+
+        assert_eq!(
+            check_zkmain_of(textual_repr),
+            vec![PreCheckErr::ZkmainTypeNotMatch {
+                params: vec![ValueType::I32],
+                results: vec![ValueType::I64],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_precheck_imported_zkmain_type_not_match() {
+        let textual_repr = r#"
+            (module
+                (import "env" "wasm_input" (func $wasm_input (param i32) (result i64)))
+                (export "zkmain" (func $wasm_input))
+            )
+            "#;
+
+        assert_eq!(
+            check_zkmain_of(textual_repr),
+            vec![PreCheckErr::ZkmainTypeNotMatch {
+                params: vec![ValueType::I32],
+                results: vec![ValueType::I64],
+            }]
+        );
+    }
+}
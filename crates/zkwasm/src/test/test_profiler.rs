@@ -0,0 +1,76 @@
+mod tests {
+    use crate::circuits::config::MIN_K;
+    use crate::runtime::monitor::statistic_monitor::StatisticMonitor;
+    use crate::test::empty_arg;
+    use crate::test::run_with_monitor;
+
+    use wabt::Wat2Wasm;
+
+    #[test]
+    fn test_profiler_attributes_callees() {
+        let textual_repr = r#"
+        (module
+            (memory $0 1)
+
+            (func $add (param i32 i32) (result i32)
+                (local.get 0)
+                (local.get 1)
+                (i32.add)
+            )
+
+            (func $test (export "test")
+                (i32.const 8)
+                (i32.const 1)
+                (i32.const 2)
+                (call $add)
+                (i32.const 3)
+                (i32.const 4)
+                (call $add)
+                (i32.add)
+                (i32.store)
+            )
+        )
+        "#;
+
+        let wasm = Wat2Wasm::new()
+            .write_debug_names(true)
+            .convert(textual_repr)
+            .expect("failed to parse wat")
+            .as_ref()
+            .to_vec();
+        let (_, monitor, result) =
+            run_with_monitor(MIN_K, &wasm, "test", empty_arg(), false, |_, env| {
+                StatisticMonitor::new(&[], env, None).with_profiler()
+            })
+            .unwrap();
+
+        let functions = monitor.profiler().unwrap().functions();
+        let function = |name: &str| {
+            functions
+                .iter()
+                .find(|function| function.name.as_deref() == Some(name))
+                .unwrap()
+        };
+
+        let add = function("add");
+        assert_eq!(add.calls, 2);
+        assert_eq!(add.inclusive.instructions, add.exclusive.instructions);
+
+        let test = function("test");
+        assert_eq!(test.calls, 1);
+        assert_eq!(
+            test.inclusive.instructions,
+            test.exclusive.instructions + add.inclusive.instructions
+        );
+        assert_eq!(test.inclusive.instructions, result.guest_statics as u64);
+
+        let mut folded = vec![];
+        monitor
+            .profiler()
+            .unwrap()
+            .write_folded(&mut folded)
+            .unwrap();
+        let folded = String::from_utf8(folded).unwrap();
+        assert!(folded.lines().any(|line| line.starts_with("test;add ")));
+    }
+}
@@ -153,7 +153,7 @@ fn test_slices() -> Result<()> {
     let execution_result = loader.run(
         ExecutionArg {
             public_inputs,
-            private_inputs,
+            private_inputs: private_inputs.into(),
             context_inputs: vec![],
             context_outputs: ContextOutput::default(),
         },
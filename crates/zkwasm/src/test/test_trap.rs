@@ -0,0 +1,86 @@
+mod tests {
+    use crate::circuits::config::MIN_K;
+    use crate::runtime::host::default_env::ExecutionArg;
+    use crate::test::empty_arg;
+    use crate::test::run_to_slices;
+
+    use halo2_proofs::pairing::bn256::Fr;
+    use specs::trap::TrapCode;
+
+    #[test]
+    fn test_prove_unreachable() {
+        let textual_repr = r#"
+        (module
+            (import "env" "wasm_input" (func $wasm_input (param i32) (result i64)))
+            (import "env" "wasm_output" (func $wasm_output (param i64)))
+            (func $check (param i64)
+                (if (i64.eqz (local.get 0))
+                    (then unreachable)
+                )
+            )
+            (func (export "test")
+                (call $wasm_output (i64.const 3))
+                (call $check (call $wasm_input (i32.const 1)))
+            )
+        )
+        "#;
+
+        let wasm = wabt::wat2wasm(textual_repr).expect("failed to parse wat");
+        let arg = ExecutionArg {
+            public_inputs: vec![0],
+            ..empty_arg()
+        };
+        let (result, slices) = run_to_slices(MIN_K, &wasm, "test", arg, true).unwrap();
+        let instances: Vec<Fr> = result.public_inputs_and_outputs();
+
+        assert_eq!(result.trap, Some(TrapCode::Unreachable));
+        assert_eq!(
+            instances,
+            vec![
+                Fr::from(0),
+                Fr::from(3),
+                Fr::from(TrapCode::Unreachable as u64)
+            ]
+        );
+        slices.mock_test_all(instances).unwrap();
+    }
+
+    #[test]
+    fn test_prove_require_failed() {
+        let textual_repr = r#"
+        (module
+            (import "env" "require" (func $require (param i32)))
+            (func (export "test")
+                (call $require (i32.const 1))
+                (call $require (i32.const 0))
+            )
+        )
+        "#;
+
+        let wasm = wabt::wat2wasm(textual_repr).expect("failed to parse wat");
+        let (result, slices) = run_to_slices(MIN_K, &wasm, "test", empty_arg(), true).unwrap();
+
+        assert_eq!(result.trap, Some(TrapCode::RequireFailed));
+        slices
+            .mock_test_all(result.public_inputs_and_outputs())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_trap_code_is_constrained() {
+        let textual_repr = r#"
+        (module
+            (func (export "test")
+                unreachable
+            )
+        )
+        "#;
+
+        let wasm = wabt::wat2wasm(textual_repr).expect("failed to parse wat");
+        let (result, slices) = run_to_slices(MIN_K, &wasm, "test", empty_arg(), true).unwrap();
+        let mut instances: Vec<Fr> = result.public_inputs_and_outputs();
+
+        *instances.last_mut().unwrap() = Fr::from(TrapCode::RequireFailed as u64);
+        assert!(slices.mock_test_all(instances).is_err());
+    }
+}
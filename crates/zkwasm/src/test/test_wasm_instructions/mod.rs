@@ -10,6 +10,8 @@ mod op_call_host;
 mod op_call_indirect;
 mod op_const;
 mod op_conversion;
+#[cfg(feature = "float")]
+mod op_float;
 mod op_global_get;
 mod op_global_set;
 mod op_load;
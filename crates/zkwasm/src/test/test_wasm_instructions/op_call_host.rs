@@ -55,7 +55,7 @@ fn test_call_host_external() {
                     let value: u64 = args.nth(0);
                     context.acc += value;
 
-                    None
+                    Ok(None)
                 },
             ),
         );
@@ -68,7 +68,7 @@ fn test_call_host_external() {
                 |_obs, context: &mut dyn ForeignContext, _args: wasmi::RuntimeArgs| {
                     let context = context.downcast_mut::<Context>().unwrap();
 
-                    Some(wasmi::RuntimeValue::I64(context.acc as i64))
+                    Ok(Some(wasmi::RuntimeValue::I64(context.acc as i64)))
                 },
             ),
         );
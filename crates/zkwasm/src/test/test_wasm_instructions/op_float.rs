@@ -0,0 +1,236 @@
+use crate::circuits::config::MIN_K;
+use crate::loader::slice::Slices;
+use crate::test::empty_arg;
+use crate::test::run_with_env;
+use crate::test::test_circuit_noexternal;
+
+use specs::step::StepInfo;
+use specs::TableBackend;
+
+#[test]
+fn test_float_unary() {
+    let textual_repr = r#"
+        (module
+            (func (export "test")
+                (f32.const -1.5)
+                (f32.abs)
+                (drop)
+
+                (f32.const 1.5)
+                (f32.neg)
+                (drop)
+
+                (f64.const -0.0)
+                (f64.abs)
+                (drop)
+
+                (f64.const nan)
+                (f64.neg)
+                (drop)
+            )
+        )
+        "#;
+
+    test_circuit_noexternal(textual_repr).unwrap()
+}
+
+#[test]
+fn test_float_bin() {
+    let textual_repr = r#"
+        (module
+            (func (export "test")
+                (f32.const 1.5)
+                (f32.const -2.0)
+                (f32.copysign)
+                (drop)
+
+                (f64.const -1.5)
+                (f64.const 2.0)
+                (f64.copysign)
+                (drop)
+            )
+        )
+        "#;
+
+    test_circuit_noexternal(textual_repr).unwrap()
+}
+
+#[test]
+fn test_float_rel() {
+    let textual_repr = r#"
+        (module
+            (func (export "test")
+                (f32.const 1.0)
+                (f32.const 2.0)
+                (f32.lt)
+                (drop)
+
+                (f32.const 0.0)
+                (f32.const -0.0)
+                (f32.eq)
+                (drop)
+
+                (f32.const nan)
+                (f32.const nan)
+                (f32.ne)
+                (drop)
+
+                (f64.const -1.0)
+                (f64.const -2.0)
+                (f64.gt)
+                (drop)
+
+                (f64.const nan)
+                (f64.const 1.0)
+                (f64.le)
+                (drop)
+
+                (f64.const -inf)
+                (f64.const inf)
+                (f64.ge)
+                (drop)
+            )
+        )
+        "#;
+
+    test_circuit_noexternal(textual_repr).unwrap()
+}
+
+#[test]
+fn test_float_conversion() {
+    let textual_repr = r#"
+        (module
+            (func (export "test")
+                (f32.const 1.5)
+                (i32.reinterpret_f32)
+                (drop)
+
+                (i64.const 0x3ff8000000000000)
+                (f64.reinterpret_i64)
+                (drop)
+
+                (f32.const -0.0)
+                (i32.reinterpret_f32)
+                (f32.reinterpret_i32)
+                (drop)
+
+                (f64.const nan)
+                (i64.reinterpret_f64)
+                (drop)
+            )
+        )
+        "#;
+
+    test_circuit_noexternal(textual_repr).unwrap()
+}
+
+/// Run `test`, apply `tamper` to the step info of every step and expect the mock prover to reject
+/// the tampered trace. `tamper` returns whether the step is tampered.
+fn test_tampered_step(textual_repr: &str, tamper: impl Fn(&mut StepInfo) -> bool) {
+    let wasm = wabt::wat2wasm(textual_repr).expect("failed to parse wat");
+    let (_, monitor, result) = run_with_env(MIN_K, &wasm, "test", empty_arg(), false).unwrap();
+    let mut tables = monitor.into_tables().unwrap();
+
+    let mut tampered = false;
+    for etable in tables.execution_tables.etable.iter_mut() {
+        let TableBackend::Memory(etable) = etable else {
+            unreachable!()
+        };
+
+        for entry in etable.entries_mut() {
+            tampered |= tamper(&mut entry.step_info);
+        }
+    }
+    assert!(tampered, "no step is tampered");
+
+    let slices = Slices::new(MIN_K, tables, None).unwrap();
+    assert!(slices
+        .mock_test_all(result.public_inputs_and_outputs())
+        .is_err());
+}
+
+#[test]
+fn test_float_unary_tampered() {
+    let textual_repr = r#"
+        (module
+            (func (export "test")
+                (f64.const -1.5)
+                (f64.abs)
+                (drop)
+            )
+        )
+        "#;
+
+    test_tampered_step(textual_repr, |step| match step {
+        StepInfo::FloatUnaryOp { result, .. } => {
+            *result ^= 1 << 63;
+            true
+        }
+        _ => false,
+    });
+}
+
+#[test]
+fn test_float_bin_tampered() {
+    let textual_repr = r#"
+        (module
+            (func (export "test")
+                (f32.const 1.5)
+                (f32.const -2.0)
+                (f32.copysign)
+                (drop)
+            )
+        )
+        "#;
+
+    test_tampered_step(textual_repr, |step| match step {
+        StepInfo::FloatBinOp { value, .. } => {
+            *value += 1;
+            true
+        }
+        _ => false,
+    });
+}
+
+#[test]
+fn test_float_rel_tampered() {
+    let textual_repr = r#"
+        (module
+            (func (export "test")
+                (f32.const nan)
+                (f32.const nan)
+                (f32.eq)
+                (drop)
+            )
+        )
+        "#;
+
+    test_tampered_step(textual_repr, |step| match step {
+        StepInfo::FloatComp { value, .. } => {
+            *value = !*value;
+            true
+        }
+        _ => false,
+    });
+}
+
+#[test]
+fn test_float_conversion_tampered() {
+    let textual_repr = r#"
+        (module
+            (func (export "test")
+                (f32.const 1.5)
+                (i32.reinterpret_f32)
+                (drop)
+            )
+        )
+        "#;
+
+    test_tampered_step(textual_repr, |step| match step {
+        StepInfo::FloatConversion { result, .. } => {
+            *result += 1;
+            true
+        }
+        _ => false,
+    });
+}
@@ -127,3 +127,38 @@ fn test_load_maximal_memory() {
 
     test_circuit_noexternal(textual_repr).unwrap();
 }
+
+#[test]
+fn test_load_store_float() {
+    let textual_repr = r#"
+        (module
+            (memory $0 1)
+            (func (export "test")
+                (local f64)
+                (i32.const 0)
+                (f32.const 1.5)
+                (f32.store offset=0)
+
+                (i32.const 8)
+                (f64.const -2.5)
+                (f64.store offset=0)
+
+                (i32.const 0)
+                (f32.load offset=0)
+                (drop)
+
+                (i32.const 8)
+                (f64.load offset=0)
+                (local.set 0)
+
+                (local.get 0)
+                (local.get 0)
+                (i32.const 1)
+                (select)
+                (drop)
+            )
+        )
+        "#;
+
+    test_circuit_noexternal(textual_repr).unwrap()
+}
@@ -34,6 +34,17 @@ test_continuation_cli() {
     $CLI --params ./params fibonacci verify --output ./output
 }
 
+test_continuation_workers_cli() {
+    cargo build --release --features continuation $CUDA
+    rm -rf params/*.data params/*.config output
+    $CLI --params ./params fibonacci setup
+    $CLI --params ./params fibonacci prove --wasm crates/zkwasm/wasm/fibonacci.wasm --public 5:i64 --output ./output --mock --padding 4 --workers 2
+    # The proofs of the workers are stitched in the order of the slices.
+    diff <(grep -o 'fibonacci\.[0-9]*\.instance\.data' output/fibonacci.loadinfo.json) \
+        <(for i in 0 1 2 3; do echo fibonacci.$i.instance.data; done)
+    $CLI --params ./params fibonacci verify --output ./output
+}
+
 test_phantom_cli() {
     cargo build --release $CUDA
     rm -rf params/*.data params/*.config output
@@ -49,5 +60,6 @@ test_phantom_cli() {
     test_default_cli
     test_uniform_circuit_cli
     test_continuation_cli
+    test_continuation_workers_cli
 #    x=$(($x-1))
 #done